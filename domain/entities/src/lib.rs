@@ -2,8 +2,17 @@
 
 pub mod prelude;
 
+pub mod api_key;
+pub mod artwork;
+pub mod episode;
+pub mod episode_marker;
+pub mod external_id;
+pub mod genre;
+pub mod movie;
 pub mod season;
 pub mod series;
+pub mod series_alias;
+pub mod series_genre;
 pub mod session;
 pub mod theme_song;
 pub mod user;
@@ -1,7 +1,16 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.1
 
+pub use super::api_key::Entity as ApiKey;
+pub use super::artwork::Entity as Artwork;
+pub use super::episode::Entity as Episode;
+pub use super::episode_marker::Entity as EpisodeMarker;
+pub use super::external_id::Entity as ExternalId;
+pub use super::genre::Entity as Genre;
+pub use super::movie::Entity as Movie;
 pub use super::season::Entity as Season;
 pub use super::series::Entity as Series;
+pub use super::series_alias::Entity as SeriesAlias;
+pub use super::series_genre::Entity as SeriesGenre;
 pub use super::session::Entity as Session;
 pub use super::theme_song::Entity as ThemeSong;
 pub use super::user::Entity as User;
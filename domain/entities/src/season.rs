@@ -16,11 +16,13 @@ pub struct Model {
 	pub id: Uuid,
 	pub series_id: Uuid,
 	pub number: i16,
+	pub season_type: String,
 	pub name: Option<String>,
 	pub tvdb_id: i32,
 	pub theme_song_id: Option<Uuid>,
 	pub version: TimeDateTime,
 	pub image: Option<String>,
+	pub image_blurhash: Option<String>,
 	pub description: Option<String>,
 }
 
@@ -29,12 +31,14 @@ pub enum Column {
 	Id,
 	SeriesId,
 	Number,
+	SeasonType,
 	Name,
 	TvdbId,
 	ThemeSongId,
 	#[sea_orm(column_name = "_version")]
 	Version,
 	Image,
+	ImageBlurhash,
 	Description,
 }
 
@@ -52,6 +56,8 @@ impl PrimaryKeyTrait for PrimaryKey {
 
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
+	Artwork,
+	Episode,
 	Series,
 	ThemeSong,
 }
@@ -63,11 +69,13 @@ impl ColumnTrait for Column {
 			Self::Id => ColumnType::Uuid.def(),
 			Self::SeriesId => ColumnType::Uuid.def(),
 			Self::Number => ColumnType::SmallInteger.def(),
+			Self::SeasonType => ColumnType::String(None).def(),
 			Self::Name => ColumnType::String(None).def().null(),
 			Self::TvdbId => ColumnType::Integer.def(),
 			Self::ThemeSongId => ColumnType::Uuid.def().null(),
 			Self::Version => ColumnType::DateTime.def(),
 			Self::Image => ColumnType::String(None).def().null(),
+			Self::ImageBlurhash => ColumnType::String(None).def().null(),
 			Self::Description => ColumnType::Text.def().null(),
 		}
 	}
@@ -76,6 +84,8 @@ impl ColumnTrait for Column {
 impl RelationTrait for Relation {
 	fn def(&self) -> RelationDef {
 		match self {
+			Self::Artwork => Entity::has_many(super::artwork::Entity).into(),
+			Self::Episode => Entity::has_many(super::episode::Entity).into(),
 			Self::Series => Entity::belongs_to(super::series::Entity)
 				.from(Column::SeriesId)
 				.to(super::series::Column::Id)
@@ -88,6 +98,18 @@ impl RelationTrait for Relation {
 	}
 }
 
+impl Related<super::artwork::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Artwork.def()
+	}
+}
+
+impl Related<super::episode::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Episode.def()
+	}
+}
+
 impl Related<super::series::Entity> for Entity {
 	fn to() -> RelationDef {
 		Relation::Series.def()
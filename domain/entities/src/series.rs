@@ -18,8 +18,16 @@ pub struct Model {
 	pub tvdb_id: i32,
 	pub theme_song_id: Option<Uuid>,
 	pub version: TimeDateTime,
+	pub created: TimeDateTime,
 	pub image: Option<String>,
+	pub image_blurhash: Option<String>,
 	pub description: Option<String>,
+	pub year: Option<i32>,
+	pub status: Option<String>,
+	pub network: Option<String>,
+	pub network_slug: Option<String>,
+	pub original_language: Option<String>,
+	pub view_count: i64,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -30,8 +38,17 @@ pub enum Column {
 	ThemeSongId,
 	#[sea_orm(column_name = "_version")]
 	Version,
+	#[sea_orm(column_name = "_created")]
+	Created,
 	Image,
+	ImageBlurhash,
 	Description,
+	Year,
+	Status,
+	Network,
+	NetworkSlug,
+	OriginalLanguage,
+	ViewCount,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -48,7 +65,12 @@ impl PrimaryKeyTrait for PrimaryKey {
 
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
+	Artwork,
+	Episode,
+	ExternalId,
 	Season,
+	SeriesAlias,
+	SeriesGenre,
 	ThemeSong,
 }
 
@@ -61,8 +83,16 @@ impl ColumnTrait for Column {
 			Self::TvdbId => ColumnType::Integer.def(),
 			Self::ThemeSongId => ColumnType::Uuid.def().null(),
 			Self::Version => ColumnType::DateTime.def(),
+			Self::Created => ColumnType::DateTime.def(),
 			Self::Image => ColumnType::String(None).def().null(),
+			Self::ImageBlurhash => ColumnType::String(None).def().null(),
 			Self::Description => ColumnType::Text.def().null(),
+			Self::Year => ColumnType::Integer.def().null(),
+			Self::Status => ColumnType::String(None).def().null(),
+			Self::Network => ColumnType::String(None).def().null(),
+			Self::NetworkSlug => ColumnType::String(None).def().null(),
+			Self::OriginalLanguage => ColumnType::String(None).def().null(),
+			Self::ViewCount => ColumnType::BigInteger.def(),
 		}
 	}
 }
@@ -70,7 +100,12 @@ impl ColumnTrait for Column {
 impl RelationTrait for Relation {
 	fn def(&self) -> RelationDef {
 		match self {
+			Self::Artwork => Entity::has_many(super::artwork::Entity).into(),
+			Self::Episode => Entity::has_many(super::episode::Entity).into(),
+			Self::ExternalId => Entity::has_many(super::external_id::Entity).into(),
 			Self::Season => Entity::has_many(super::season::Entity).into(),
+			Self::SeriesAlias => Entity::has_many(super::series_alias::Entity).into(),
+			Self::SeriesGenre => Entity::has_many(super::series_genre::Entity).into(),
 			Self::ThemeSong => Entity::belongs_to(super::theme_song::Entity)
 				.from(Column::ThemeSongId)
 				.to(super::theme_song::Column::Id)
@@ -79,12 +114,51 @@ impl RelationTrait for Relation {
 	}
 }
 
+impl Related<super::artwork::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Artwork.def()
+	}
+}
+
+impl Related<super::episode::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Episode.def()
+	}
+}
+
+impl Related<super::external_id::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::ExternalId.def()
+	}
+}
+
 impl Related<super::season::Entity> for Entity {
 	fn to() -> RelationDef {
 		Relation::Season.def()
 	}
 }
 
+impl Related<super::series_alias::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::SeriesAlias.def()
+	}
+}
+
+impl Related<super::series_genre::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::SeriesGenre.def()
+	}
+}
+
+impl Related<super::genre::Entity> for Entity {
+	fn to() -> RelationDef {
+		super::series_genre::Relation::Genre.def()
+	}
+	fn via() -> Option<RelationDef> {
+		Some(super::series_genre::Relation::Series.def().rev())
+	}
+}
+
 impl Related<super::theme_song::Entity> for Entity {
 	fn to() -> RelationDef {
 		Relation::ThemeSong.def()
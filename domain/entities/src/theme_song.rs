@@ -19,6 +19,8 @@ pub struct Model {
 	pub youtube_starts_at: Option<i32>,
 	pub youtube_ends_at: Option<i32>,
 	pub version: TimeDateTime,
+	pub user_id: Option<Uuid>,
+	pub created: TimeDateTime,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -30,6 +32,9 @@ pub enum Column {
 	YoutubeEndsAt,
 	#[sea_orm(column_name = "_version")]
 	Version,
+	UserId,
+	#[sea_orm(column_name = "_created")]
+	Created,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -46,8 +51,11 @@ impl PrimaryKeyTrait for PrimaryKey {
 
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
+	EpisodeMarker,
+	Movie,
 	Season,
 	Series,
+	User,
 }
 
 impl ColumnTrait for Column {
@@ -60,6 +68,8 @@ impl ColumnTrait for Column {
 			Self::YoutubeStartsAt => ColumnType::Integer.def().null(),
 			Self::YoutubeEndsAt => ColumnType::Integer.def().null(),
 			Self::Version => ColumnType::DateTime.def(),
+			Self::UserId => ColumnType::Uuid.def().null(),
+			Self::Created => ColumnType::DateTime.def(),
 		}
 	}
 }
@@ -67,12 +77,30 @@ impl ColumnTrait for Column {
 impl RelationTrait for Relation {
 	fn def(&self) -> RelationDef {
 		match self {
+			Self::EpisodeMarker => Entity::has_many(super::episode_marker::Entity).into(),
+			Self::Movie => Entity::has_many(super::movie::Entity).into(),
 			Self::Season => Entity::has_many(super::season::Entity).into(),
 			Self::Series => Entity::has_many(super::series::Entity).into(),
+			Self::User => Entity::belongs_to(super::user::Entity)
+				.from(Column::UserId)
+				.to(super::user::Column::Id)
+				.into(),
 		}
 	}
 }
 
+impl Related<super::episode_marker::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::EpisodeMarker.def()
+	}
+}
+
+impl Related<super::movie::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Movie.def()
+	}
+}
+
 impl Related<super::season::Entity> for Entity {
 	fn to() -> RelationDef {
 		Relation::Season.def()
@@ -85,4 +113,10 @@ impl Related<super::series::Entity> for Entity {
 	}
 }
 
+impl Related<super::user::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::User.def()
+	}
+}
+
 impl ActiveModelBehavior for ActiveModel {}
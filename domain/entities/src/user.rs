@@ -41,6 +41,7 @@ impl PrimaryKeyTrait for PrimaryKey {
 
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
+	ApiKey,
 	Session,
 	UserLink,
 }
@@ -60,12 +61,19 @@ impl ColumnTrait for Column {
 impl RelationTrait for Relation {
 	fn def(&self) -> RelationDef {
 		match self {
+			Self::ApiKey => Entity::has_many(super::api_key::Entity).into(),
 			Self::Session => Entity::has_many(super::session::Entity).into(),
 			Self::UserLink => Entity::has_many(super::user_link::Entity).into(),
 		}
 	}
 }
 
+impl Related<super::api_key::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::ApiKey.def()
+	}
+}
+
 impl Related<super::session::Entity> for Entity {
 	fn to() -> RelationDef {
 		Relation::Session.def()
@@ -14,6 +14,20 @@ mod m20230811_170956_sessions;
 mod m20230813_063316_artwork;
 mod m20230813_131452_hot_indices;
 mod m20230818_124952_descriptions;
+mod m20240502_081512_series_aliases;
+mod m20240503_140327_artwork_catalog;
+mod m20240505_091844_image_blurhash;
+mod m20240506_183022_season_types;
+mod m20240507_102745_episodes;
+mod m20240508_164210_episode_markers;
+mod m20240510_111935_movies;
+mod m20240512_093204_series_metadata;
+mod m20240513_154410_series_created;
+mod m20240515_083127_series_view_count;
+mod m20240516_194052_theme_song_attribution;
+mod m20240517_102318_external_ids;
+mod m20240519_091527_api_keys;
+mod m20240520_083412_image_indices;
 
 pub struct Migrator;
 
@@ -30,6 +44,20 @@ impl MigratorTrait for Migrator {
 			Box::new(m20230813_063316_artwork::Migration),
 			Box::new(m20230813_131452_hot_indices::Migration),
 			Box::new(m20230818_124952_descriptions::Migration),
+			Box::new(m20240502_081512_series_aliases::Migration),
+			Box::new(m20240503_140327_artwork_catalog::Migration),
+			Box::new(m20240505_091844_image_blurhash::Migration),
+			Box::new(m20240506_183022_season_types::Migration),
+			Box::new(m20240507_102745_episodes::Migration),
+			Box::new(m20240508_164210_episode_markers::Migration),
+			Box::new(m20240510_111935_movies::Migration),
+			Box::new(m20240512_093204_series_metadata::Migration),
+			Box::new(m20240513_154410_series_created::Migration),
+			Box::new(m20240515_083127_series_view_count::Migration),
+			Box::new(m20240516_194052_theme_song_attribution::Migration),
+			Box::new(m20240517_102318_external_ids::Migration),
+			Box::new(m20240519_091527_api_keys::Migration),
+			Box::new(m20240520_083412_image_indices::Migration),
 		]
 	}
 }
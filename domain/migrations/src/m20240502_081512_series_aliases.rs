@@ -0,0 +1,92 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(SeriesAlias::Table)
+					.col(
+						ColumnDef::new(SeriesAlias::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(ColumnDef::new(SeriesAlias::SeriesId).uuid().not_null())
+					.col(ColumnDef::new(SeriesAlias::Name).string().not_null())
+					.col(ColumnDef::new(SeriesAlias::Language).string().null())
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-seriesalias_seriesid")
+							.from(SeriesAlias::Table, SeriesAlias::SeriesId)
+							.to(Series::Table, Series::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeriesAliasSeriesId)
+					.table(SeriesAlias::Table)
+					.col(SeriesAlias::SeriesId)
+					.to_owned(),
+			)
+			.await?;
+
+		log_and_exec(
+			manager,
+			format!(
+				"CREATE INDEX \"{index}\" ON \"{table}\" USING GIN(\"{col}\" gin_trgm_ops);",
+				index = Indices::SeriesAliasNameTrigram.to_string(),
+				table = SeriesAlias::Table.to_string(),
+				col = SeriesAlias::Name.to_string()
+			),
+		)
+		.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(
+				Index::drop()
+					.name(Indices::SeriesAliasNameTrigram)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::SeriesAliasSeriesId).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(SeriesAlias::Table).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-seriesalias_seriesid"]
+	SeriesAliasSeriesId,
+	#[iden = "ix-seriesalias_name_trigram"]
+	SeriesAliasNameTrigram,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
@@ -0,0 +1,109 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(Artwork::Table)
+					.col(
+						ColumnDef::new(Artwork::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(ColumnDef::new(Artwork::TvDbId).unsigned().not_null())
+					.col(ColumnDef::new(Artwork::SeriesId).uuid().not_null())
+					.col(ColumnDef::new(Artwork::SeasonId).uuid().null())
+					.col(ColumnDef::new(Artwork::Kind).small_unsigned().not_null())
+					.col(ColumnDef::new(Artwork::Url).string().not_null())
+					.col(ColumnDef::new(Artwork::Thumbnail).string().null())
+					.col(ColumnDef::new(Artwork::Language).string().null())
+					.col(ColumnDef::new(Artwork::Score).unsigned().not_null())
+					.col(ColumnDef::new(Artwork::Width).unsigned().null())
+					.col(ColumnDef::new(Artwork::Height).unsigned().null())
+					.index(
+						Index::create()
+							.name("uq-artwork_tvdbid")
+							.col(Artwork::TvDbId)
+							.unique(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-artwork_seriesid")
+							.from(Artwork::Table, Artwork::SeriesId)
+							.to(Series::Table, Series::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-artwork_seasonid")
+							.from(Artwork::Table, Artwork::SeasonId)
+							.to(Season::Table, Season::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::ArtworkSeriesIdKind)
+					.table(Artwork::Table)
+					.col(Artwork::SeriesId)
+					.col(Artwork::Kind)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::ArtworkSeasonId)
+					.table(Artwork::Table)
+					.col(Artwork::SeasonId)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::ArtworkSeasonId).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::ArtworkSeriesIdKind).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(Artwork::Table).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+enum Indices {
+	ArtworkSeriesIdKind,
+	ArtworkSeasonId,
+}
+
+impl From<Indices> for String {
+	fn from(val: Indices) -> Self {
+		match val {
+			Indices::ArtworkSeriesIdKind => "ix-artwork_seriesid_kind".to_owned(),
+			Indices::ArtworkSeasonId => "ix-artwork_seasonid".to_owned(),
+		}
+	}
+}
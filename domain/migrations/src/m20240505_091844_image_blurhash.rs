@@ -0,0 +1,51 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.add_column(ColumnDef::new(Series::ImageBlurhash).string().null())
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Season::Table)
+					.add_column(ColumnDef::new(Season::ImageBlurhash).string().null())
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.drop_column(Series::ImageBlurhash)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Season::Table)
+					.drop_column(Season::ImageBlurhash)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,105 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// all the seasons imported so far are from the official (aired) ordering
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Season::Table)
+					.add_column(
+						ColumnDef::new(Season::SeasonType)
+							.string()
+							.not_null()
+							.default("official"),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_index(
+				Index::drop()
+					.name(Indices::SeasonSeriesIdNumber)
+					.table(Season::Table)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeasonSeriesIdTypeNumber)
+					.table(Season::Table)
+					.col(Season::SeriesId)
+					.col(Season::SeasonType)
+					.col(Season::Number)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(
+				Index::drop()
+					.name(Indices::SeasonSeriesIdTypeNumber)
+					.table(Season::Table)
+					.to_owned(),
+			)
+			.await?;
+
+		// seasons from other orderings can't be told apart without the type
+		manager
+			.exec_stmt(
+				Query::delete()
+					.from_table(Season::Table)
+					.and_where(Expr::col(Season::SeasonType).ne("official"))
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Season::Table)
+					.drop_column(Season::SeasonType)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeasonSeriesIdNumber)
+					.table(Season::Table)
+					.col(Season::SeriesId)
+					.col(Season::Number)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+enum Indices {
+	SeasonSeriesIdNumber,
+	SeasonSeriesIdTypeNumber,
+}
+
+impl From<Indices> for String {
+	fn from(val: Indices) -> Self {
+		match val {
+			Indices::SeasonSeriesIdNumber => "ix-season_seriesid_number".to_owned(),
+			Indices::SeasonSeriesIdTypeNumber => "uq-season_seriesid_type_number".to_owned(),
+		}
+	}
+}
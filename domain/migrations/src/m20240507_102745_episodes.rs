@@ -0,0 +1,108 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// the same tvdb episode is listed once per season ordering, so the tvdb id
+		// is only unique within a season
+		manager
+			.create_table(
+				Table::create()
+					.table(Episode::Table)
+					.col(
+						ColumnDef::new(Episode::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(ColumnDef::new(Episode::SeriesId).uuid().not_null())
+					.col(ColumnDef::new(Episode::SeasonId).uuid().not_null())
+					.col(ColumnDef::new(Episode::TvDbId).unsigned().not_null())
+					.col(ColumnDef::new(Episode::Number).unsigned().not_null())
+					.col(ColumnDef::new(Episode::AbsoluteNumber).unsigned().null())
+					.col(ColumnDef::new(Episode::Name).string().null())
+					.col(ColumnDef::new(Episode::AirDate).date().null())
+					.index(
+						Index::create()
+							.name("uq-episode_seasonid_tvdbid")
+							.col(Episode::SeasonId)
+							.col(Episode::TvDbId)
+							.unique(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-episode_seriesid")
+							.from(Episode::Table, Episode::SeriesId)
+							.to(Series::Table, Series::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-episode_seasonid")
+							.from(Episode::Table, Episode::SeasonId)
+							.to(Season::Table, Season::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::EpisodeSeriesId)
+					.table(Episode::Table)
+					.col(Episode::SeriesId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::EpisodeTvDbId)
+					.table(Episode::Table)
+					.col(Episode::TvDbId)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::EpisodeTvDbId).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::EpisodeSeriesId).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(Episode::Table).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-episode_seriesid"]
+	EpisodeSeriesId,
+	#[iden = "ix-episode_tvdbid"]
+	EpisodeTvDbId,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
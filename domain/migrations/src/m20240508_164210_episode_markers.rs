@@ -0,0 +1,106 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// markers are keyed by the tvdb episode id rather than our episode rows,
+		// since the same episode exists once per season ordering and episodes
+		// are rebuilt on every tvdb import
+		manager
+			.create_table(
+				Table::create()
+					.table(EpisodeMarker::Table)
+					.col(
+						ColumnDef::new(EpisodeMarker::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(
+						ColumnDef::new(EpisodeMarker::EpisodeTvdbId)
+							.unsigned()
+							.not_null(),
+					)
+					.col(ColumnDef::new(EpisodeMarker::Kind).string().not_null())
+					.col(ColumnDef::new(EpisodeMarker::ThemeSongId).uuid().not_null())
+					.col(ColumnDef::new(EpisodeMarker::StartMs).unsigned().not_null())
+					.col(ColumnDef::new(EpisodeMarker::EndMs).unsigned().not_null())
+					.col(ColumnDef::new(EpisodeMarker::UserId).uuid().null())
+					.col(
+						ColumnDef::new(Versioned::Version)
+							.timestamp()
+							.not_null()
+							.default(PgTimeFunc::utc_now()),
+					)
+					.index(
+						Index::create()
+							.name("uq-episode_marker_tvdbid_kind")
+							.col(EpisodeMarker::EpisodeTvdbId)
+							.col(EpisodeMarker::Kind)
+							.unique(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-episode_marker_themesongid")
+							.from(EpisodeMarker::Table, EpisodeMarker::ThemeSongId)
+							.to(ThemeSong::Table, ThemeSong::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-episode_marker_userid")
+							.from(EpisodeMarker::Table, EpisodeMarker::UserId)
+							.to(User::Table, User::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::SetNull),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::EpisodeMarkerThemeSongId)
+					.table(EpisodeMarker::Table)
+					.col(EpisodeMarker::ThemeSongId)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(
+				Index::drop()
+					.name(Indices::EpisodeMarkerThemeSongId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(EpisodeMarker::Table).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-episode_marker_themesongid"]
+	EpisodeMarkerThemeSongId,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
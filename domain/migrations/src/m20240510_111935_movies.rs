@@ -0,0 +1,87 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(Movie::Table)
+					.col(
+						ColumnDef::new(Movie::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(ColumnDef::new(Movie::Name).string().not_null())
+					.col(ColumnDef::new(Movie::Description).text().null())
+					.col(ColumnDef::new(Movie::TvDbId).unsigned().not_null())
+					.col(ColumnDef::new(Movie::Year).unsigned().null())
+					.col(ColumnDef::new(Movie::ThemeSongId).uuid().null())
+					.col(ColumnDef::new(Movie::Image).string().null())
+					.col(ColumnDef::new(Movie::ImageBlurhash).string().null())
+					.col(
+						ColumnDef::new(Versioned::Version)
+							.timestamp()
+							.not_null()
+							.default(PgTimeFunc::utc_now()),
+					)
+					.index(
+						Index::create()
+							.name("uq-movie_tvdbid")
+							.col(Movie::TvDbId)
+							.unique(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-movie_themesongid")
+							.from(Movie::Table, Movie::ThemeSongId)
+							.to(ThemeSong::Table, ThemeSong::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::SetNull),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::MovieName)
+					.table(Movie::Table)
+					.col(Movie::Name)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::MovieName).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(Movie::Table).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-movie_name"]
+	MovieName,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
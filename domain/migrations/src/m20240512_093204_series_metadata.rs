@@ -0,0 +1,168 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.add_column(ColumnDef::new(Series::Year).unsigned().null())
+					.add_column(ColumnDef::new(Series::Status).string().null())
+					.add_column(ColumnDef::new(Series::Network).string().null())
+					.add_column(ColumnDef::new(Series::NetworkSlug).string().null())
+					.add_column(ColumnDef::new(Series::OriginalLanguage).string().null())
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::Year)
+					.table(Series::Table)
+					.col(Series::Year)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::NetworkSlug)
+					.table(Series::Table)
+					.col(Series::NetworkSlug)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(Genre::Table)
+					.col(
+						ColumnDef::new(Genre::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(ColumnDef::new(Genre::TvDbId).unsigned().not_null())
+					.col(ColumnDef::new(Genre::Name).string().not_null())
+					.col(ColumnDef::new(Genre::Slug).string().not_null())
+					.index(
+						Index::create()
+							.name("uq-genre_tvdbid")
+							.col(Genre::TvDbId)
+							.unique(),
+					)
+					.index(
+						Index::create()
+							.name("uq-genre_slug")
+							.col(Genre::Slug)
+							.unique(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(SeriesGenre::Table)
+					.col(ColumnDef::new(SeriesGenre::SeriesId).uuid().not_null())
+					.col(ColumnDef::new(SeriesGenre::GenreId).uuid().not_null())
+					.primary_key(
+						Index::create()
+							.col(SeriesGenre::SeriesId)
+							.col(SeriesGenre::GenreId),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-series_genre_seriesid")
+							.from(SeriesGenre::Table, SeriesGenre::SeriesId)
+							.to(Series::Table, Series::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-series_genre_genreid")
+							.from(SeriesGenre::Table, SeriesGenre::GenreId)
+							.to(Genre::Table, Genre::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::GenreId)
+					.table(SeriesGenre::Table)
+					.col(SeriesGenre::GenreId)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::GenreId).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(SeriesGenre::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(Genre::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::NetworkSlug).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::Year).to_owned())
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.drop_column(Series::Year)
+					.drop_column(Series::Status)
+					.drop_column(Series::Network)
+					.drop_column(Series::NetworkSlug)
+					.drop_column(Series::OriginalLanguage)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-series_year"]
+	Year,
+	#[iden = "ix-series_networkslug"]
+	NetworkSlug,
+	#[iden = "ix-series_genre_genreid"]
+	GenreId,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// existing series all get the time of the migration, there is no better
+		// record of when they were added
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.add_column(
+						ColumnDef::new(Versioned::Created)
+							.timestamp()
+							.not_null()
+							.default(PgTimeFunc::utc_now()),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeriesCreated)
+					.table(Series::Table)
+					.col(Versioned::Created)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeriesVersion)
+					.table(Series::Table)
+					.col(Versioned::Version)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::SeriesVersion).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::SeriesCreated).to_owned())
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.drop_column(Versioned::Created)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-series_created"]
+	SeriesCreated,
+	#[iden = "ix-series_version"]
+	SeriesVersion,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
@@ -0,0 +1,64 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.add_column(
+						ColumnDef::new(Series::ViewCount)
+							.big_integer()
+							.not_null()
+							.default(0),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeriesViewCount)
+					.table(Series::Table)
+					.col(Series::ViewCount)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::SeriesViewCount).to_owned())
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(Series::Table)
+					.drop_column(Series::ViewCount)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-series_viewcount"]
+	SeriesViewCount,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
@@ -0,0 +1,107 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// existing theme songs have no known contributor, and get the time of
+		// the migration as their creation time
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ThemeSong::Table)
+					.add_column(ColumnDef::new(ThemeSong::UserId).uuid().null())
+					.add_column(
+						ColumnDef::new(Versioned::Created)
+							.timestamp()
+							.not_null()
+							.default(PgTimeFunc::utc_now()),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_foreign_key(
+				ForeignKey::create()
+					.name(Indices::UserIdFk)
+					.from(ThemeSong::Table, ThemeSong::UserId)
+					.to(User::Table, User::Id)
+					.on_update(ForeignKeyAction::Cascade)
+					.on_delete(ForeignKeyAction::SetNull)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::UserId)
+					.table(ThemeSong::Table)
+					.col(ThemeSong::UserId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::Created)
+					.table(ThemeSong::Table)
+					.col(Versioned::Created)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::Created).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::UserId).to_owned())
+			.await?;
+
+		manager
+			.drop_foreign_key(
+				ForeignKey::drop()
+					.name(Indices::UserIdFk)
+					.table(ThemeSong::Table)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ThemeSong::Table)
+					.drop_column(Versioned::Created)
+					.drop_column(ThemeSong::UserId)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "fk-theme_song_userid"]
+	UserIdFk,
+	#[iden = "ix-theme_song_userid"]
+	UserId,
+	#[iden = "ix-theme_song_created"]
+	Created,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
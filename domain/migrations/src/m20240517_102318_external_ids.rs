@@ -0,0 +1,86 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// the same external id can end up on more than one series when tvdb
+		// splits a show up, so it is only unique per series
+		manager
+			.create_table(
+				Table::create()
+					.table(ExternalId::Table)
+					.col(
+						ColumnDef::new(ExternalId::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(ColumnDef::new(ExternalId::SeriesId).uuid().not_null())
+					.col(ColumnDef::new(ExternalId::Source).string().not_null())
+					.col(ColumnDef::new(ExternalId::Value).string().not_null())
+					.index(
+						Index::create()
+							.name("uq-external_id_seriesid_source_value")
+							.col(ExternalId::SeriesId)
+							.col(ExternalId::Source)
+							.col(ExternalId::Value)
+							.unique(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-external_id_seriesid")
+							.from(ExternalId::Table, ExternalId::SeriesId)
+							.to(Series::Table, Series::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::ExternalIdSourceValue)
+					.table(ExternalId::Table)
+					.col(ExternalId::Source)
+					.col(ExternalId::Value)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(
+				Index::drop()
+					.name(Indices::ExternalIdSourceValue)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(ExternalId::Table).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-external_id_source_value"]
+	ExternalIdSourceValue,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// only a hash of the secret part of a key is stored, the key itself is
+		// shown once when it is created
+		manager
+			.create_table(
+				Table::create()
+					.table(ApiKey::Table)
+					.col(
+						ColumnDef::new(ApiKey::Id)
+							.uuid()
+							.not_null()
+							.primary_key()
+							.default(PgFunc::gen_random_uuid()),
+					)
+					.col(ColumnDef::new(ApiKey::UserId).uuid().not_null())
+					.col(ColumnDef::new(ApiKey::Name).string().not_null())
+					.col(ColumnDef::new(ApiKey::Hash).binary().not_null())
+					.col(ColumnDef::new(ApiKey::Scopes).string().not_null())
+					.col(
+						ColumnDef::new(ApiKey::CreatedAt)
+							.timestamp()
+							.not_null()
+							.default(PgTimeFunc::utc_now()),
+					)
+					.col(ColumnDef::new(ApiKey::ExpiresAt).timestamp().null())
+					.col(ColumnDef::new(ApiKey::LastUsedAt).timestamp().null())
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk-api_key_userid")
+							.from(ApiKey::Table, ApiKey::UserId)
+							.to(User::Table, User::Id)
+							.on_update(ForeignKeyAction::Cascade)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::ApiKeyUserId)
+					.table(ApiKey::Table)
+					.col(ApiKey::UserId)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::ApiKeyUserId).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(ApiKey::Table).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-api_key_userid"]
+	ApiKeyUserId,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
@@ -0,0 +1,91 @@
+use crate::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// the image proxy looks up who uses an image before fetching it, and
+		// when storing its blurhash
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeriesImage)
+					.table(Series::Table)
+					.col(Series::Image)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::SeasonImage)
+					.table(Season::Table)
+					.col(Season::Image)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::MovieImage)
+					.table(Movie::Table)
+					.col(Movie::Image)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name(Indices::ArtworkUrl)
+					.table(Artwork::Table)
+					.col(Artwork::Url)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(Index::drop().name(Indices::ArtworkUrl).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::MovieImage).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::SeasonImage).to_owned())
+			.await?;
+
+		manager
+			.drop_index(Index::drop().name(Indices::SeriesImage).to_owned())
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum Indices {
+	#[iden = "ix-series_image"]
+	SeriesImage,
+	#[iden = "ix-season_image"]
+	SeasonImage,
+	#[iden = "ix-movie_image"]
+	MovieImage,
+	#[iden = "ix-artwork_url"]
+	ArtworkUrl,
+}
+
+impl From<Indices> for String {
+	fn from(index: Indices) -> Self {
+		index.to_string()
+	}
+}
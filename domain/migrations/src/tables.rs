@@ -10,6 +10,48 @@ pub enum Series {
 	TvDbId,
 	ThemeSongId,
 	Image,
+	ImageBlurhash,
+	Year,
+	Status,
+	Network,
+	NetworkSlug,
+	OriginalLanguage,
+	ViewCount,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum Genre {
+	Table,
+	Id,
+	#[iden = "tvdb_id"]
+	TvDbId,
+	Name,
+	Slug,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum SeriesGenre {
+	Table,
+	SeriesId,
+	GenreId,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum SeriesAlias {
+	Table,
+	Id,
+	SeriesId,
+	Name,
+	Language,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum ExternalId {
+	Table,
+	Id,
+	SeriesId,
+	Source,
+	Value,
 }
 
 #[derive(Iden, Clone, Copy)]
@@ -20,10 +62,69 @@ pub enum Season {
 	Description,
 	SeriesId,
 	Number,
+	SeasonType,
+	#[iden = "tvdb_id"]
+	TvDbId,
+	ThemeSongId,
+	Image,
+	ImageBlurhash,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum Artwork {
+	Table,
+	Id,
+	#[iden = "tvdb_id"]
+	TvDbId,
+	SeriesId,
+	SeasonId,
+	Kind,
+	Url,
+	Thumbnail,
+	Language,
+	Score,
+	Width,
+	Height,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum Episode {
+	Table,
+	Id,
+	SeriesId,
+	SeasonId,
+	#[iden = "tvdb_id"]
+	TvDbId,
+	Number,
+	AbsoluteNumber,
+	Name,
+	AirDate,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum EpisodeMarker {
+	Table,
+	Id,
+	EpisodeTvdbId,
+	Kind,
+	ThemeSongId,
+	StartMs,
+	EndMs,
+	UserId,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum Movie {
+	Table,
+	Id,
+	Name,
+	Description,
 	#[iden = "tvdb_id"]
 	TvDbId,
+	Year,
 	ThemeSongId,
 	Image,
+	ImageBlurhash,
 }
 
 #[derive(Iden, Clone, Copy)]
@@ -37,12 +138,15 @@ pub enum ThemeSong {
 	YouTubeStartsAt,
 	#[iden = "youtube_ends_at"]
 	YouTubeEndsAt,
+	UserId,
 }
 
 #[derive(Iden, Clone, Copy)]
 pub enum Versioned {
 	#[iden = "_version"]
 	Version,
+	#[iden = "_created"]
+	Created,
 }
 
 #[derive(Iden, Clone, Copy)]
@@ -67,6 +171,19 @@ pub enum User {
 	AvatarUrl,
 }
 
+#[derive(Iden, Clone, Copy)]
+pub enum ApiKey {
+	Table,
+	Id,
+	UserId,
+	Name,
+	Hash,
+	Scopes,
+	CreatedAt,
+	ExpiresAt,
+	LastUsedAt,
+}
+
 #[derive(Iden, Clone, Copy)]
 pub enum UserLink {
 	Table,
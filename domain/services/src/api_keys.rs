@@ -0,0 +1,300 @@
+use crate::macros::define_service;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use dbost_entities::{api_key, user};
+use dbost_utils::OffsetDateTimeExt;
+use rand::Rng;
+use sea_orm::{
+	prelude::TimeDateTime, sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition,
+	DatabaseConnection, DbErr, EntityTrait, NotSet, QueryFilter, QueryOrder, Set,
+};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+define_service! {
+	#[derive(Clone)]
+	pub struct ApiKeyService {
+		pub db: DatabaseConnection,
+	}
+}
+
+/// Keys start with this, so they are easy to recognise (and to find in leaked
+/// config files).
+const TOKEN_PREFIX: &str = "dbost_";
+
+/// How stale `last_used_at` may get, so using a key doesn't write to the
+/// database on every request.
+const LAST_USED_PRECISION: Duration = Duration::minutes(1);
+
+/// What an api key is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+	/// Looking things up.
+	Read,
+	/// Adding and changing theme songs.
+	Write,
+}
+
+impl Scope {
+	pub const ALL: [Scope; 2] = [Scope::Read, Scope::Write];
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Read => "read",
+			Self::Write => "write",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|s| s.as_str() == value)
+	}
+}
+
+/// The scopes of a key, stored space separated like OAuth scopes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Scopes {
+	pub read: bool,
+	pub write: bool,
+}
+
+impl Scopes {
+	pub fn contains(self, scope: Scope) -> bool {
+		match scope {
+			Scope::Read => self.read,
+			Scope::Write => self.write,
+		}
+	}
+
+	pub fn is_empty(self) -> bool {
+		!self.read && !self.write
+	}
+
+	/// Reads stored scopes, skipping any this version doesn't know.
+	pub fn parse(value: &str) -> Self {
+		value
+			.split_whitespace()
+			.filter_map(Scope::parse)
+			.fold(Self::default(), |scopes, scope| match scope {
+				Scope::Read => Self {
+					read: true,
+					..scopes
+				},
+				Scope::Write => Self {
+					write: true,
+					..scopes
+				},
+			})
+	}
+}
+
+impl fmt::Display for Scopes {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let scopes = Scope::ALL
+			.into_iter()
+			.filter(|s| self.contains(*s))
+			.map(Scope::as_str)
+			.collect::<Vec<_>>();
+
+		f.write_str(&scopes.join(" "))
+	}
+}
+
+/// A key as given to its owner: the id of the key, and the secret that is
+/// only stored hashed.
+struct Token<'a> {
+	id: Uuid,
+	secret: &'a str,
+}
+
+impl<'a> Token<'a> {
+	fn parse(token: &'a str) -> Option<Self> {
+		let token = token.strip_prefix(TOKEN_PREFIX)?;
+		let (id, secret) = token.split_once('_')?;
+		let id = Uuid::try_parse(id).ok()?;
+
+		Some(Self { id, secret })
+	}
+
+	fn format(id: Uuid, secret: &str) -> String {
+		format!("{TOKEN_PREFIX}{}_{secret}", id.simple())
+	}
+}
+
+fn hash_secret(secret: &str) -> Vec<u8> {
+	// the secret is 256 random bits, so a fast hash is as good as a slow one
+	Sha256::digest(secret.as_bytes()).to_vec()
+}
+
+/// The user an api request is made for, and the key it was made with.
+#[derive(Debug, Clone)]
+pub struct ApiUser {
+	pub key_id: Uuid,
+	pub scopes: Scopes,
+	pub user: user::Model,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewApiKey {
+	pub name: String,
+	pub scopes: Scopes,
+	pub expires_at: Option<TimeDateTime>,
+}
+
+/// A key that was just created. The token is the only copy of the key, it
+/// can't be shown again.
+#[derive(Debug, Clone)]
+pub struct CreatedApiKey {
+	pub key: api_key::Model,
+	pub token: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ApiKeyError {
+	#[error("api key name is required")]
+	MissingName,
+
+	#[error("api key needs at least one scope")]
+	NoScopes,
+
+	#[error("invalid api key")]
+	InvalidKey,
+
+	#[error("api key expired")]
+	Expired,
+
+	#[error(transparent)]
+	DbErr(#[from] DbErr),
+}
+
+impl ApiKeyService {
+	/// The keys of a user, newest first.
+	pub async fn list_keys(&self, user_id: Uuid) -> Result<Vec<api_key::Model>, DbErr> {
+		api_key::Entity::find()
+			.filter(api_key::Column::UserId.eq(user_id))
+			.order_by_desc(api_key::Column::CreatedAt)
+			.all(&self.db)
+			.await
+	}
+
+	pub async fn create_key(
+		&self,
+		user_id: Uuid,
+		key: NewApiKey,
+	) -> Result<CreatedApiKey, ApiKeyError> {
+		let name = key.name.trim();
+		if name.is_empty() {
+			return Err(ApiKeyError::MissingName);
+		}
+
+		if key.scopes.is_empty() {
+			return Err(ApiKeyError::NoScopes);
+		}
+
+		let secret = URL_SAFE_NO_PAD.encode(rand::thread_rng().gen::<[u8; 32]>());
+		let key = api_key::ActiveModel {
+			id: Set(Uuid::new_v4()),
+			user_id: Set(user_id),
+			name: Set(name.to_owned()),
+			hash: Set(hash_secret(&secret)),
+			scopes: Set(key.scopes.to_string()),
+			created_at: NotSet,
+			expires_at: Set(key.expires_at),
+			last_used_at: Set(None),
+		}
+		.insert(&self.db)
+		.await?;
+
+		Ok(CreatedApiKey {
+			token: Token::format(key.id, &secret),
+			key,
+		})
+	}
+
+	/// Deletes a key of the user, returns whether there was one.
+	pub async fn revoke_key(&self, user_id: Uuid, key_id: Uuid) -> Result<bool, DbErr> {
+		let result = api_key::Entity::delete_many()
+			.filter(api_key::Column::Id.eq(key_id))
+			.filter(api_key::Column::UserId.eq(user_id))
+			.exec(&self.db)
+			.await?;
+
+		Ok(result.rows_affected > 0)
+	}
+
+	/// Finds the user of an api key, and records that the key was used.
+	pub async fn authenticate(&self, token: &str) -> Result<ApiUser, ApiKeyError> {
+		let token = Token::parse(token).ok_or(ApiKeyError::InvalidKey)?;
+		let Some((key, Some(user))) = api_key::Entity::find_by_id(token.id)
+			.find_also_related(user::Entity)
+			.one(&self.db)
+			.await?
+		else {
+			return Err(ApiKeyError::InvalidKey);
+		};
+
+		let hash = hash_secret(token.secret);
+		if !bool::from(hash.ct_eq(&key.hash)) {
+			return Err(ApiKeyError::InvalidKey);
+		}
+
+		let now = OffsetDateTime::now_utc().into_primitive_utc();
+		if key.expires_at.is_some_and(|expires_at| expires_at <= now) {
+			return Err(ApiKeyError::Expired);
+		}
+
+		api_key::Entity::update_many()
+			.col_expr(api_key::Column::LastUsedAt, Expr::value(now))
+			.filter(api_key::Column::Id.eq(key.id))
+			.filter(
+				Condition::any()
+					.add(api_key::Column::LastUsedAt.is_null())
+					.add(api_key::Column::LastUsedAt.lt(now - LAST_USED_PRECISION)),
+			)
+			.exec(&self.db)
+			.await?;
+
+		Ok(ApiUser {
+			key_id: key.id,
+			scopes: Scopes::parse(&key.scopes),
+			user,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn token_roundtrip() {
+		let id = Uuid::new_v4();
+		let token = Token::format(id, "c2VjcmV0_c2VjcmV0");
+		let parsed = Token::parse(&token).unwrap();
+
+		assert_eq!(parsed.id, id);
+		assert_eq!(parsed.secret, "c2VjcmV0_c2VjcmV0");
+		assert!(Token::parse("c2VjcmV0").is_none());
+		assert!(Token::parse("dbost_nope_c2VjcmV0").is_none());
+	}
+
+	#[test]
+	fn scopes_roundtrip() {
+		let scopes = Scopes {
+			read: true,
+			write: true,
+		};
+
+		assert_eq!(scopes.to_string(), "read write");
+		assert_eq!(Scopes::parse("read write"), scopes);
+		assert_eq!(
+			Scopes::parse("read admin"),
+			Scopes {
+				read: true,
+				write: false,
+			}
+		);
+	}
+}
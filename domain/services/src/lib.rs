@@ -1,9 +1,14 @@
 // use macros::define_service;
 // use sea_orm::DatabaseConnection;
 
+pub mod api_keys;
 pub mod auth;
 mod macros;
+pub mod markers;
+pub mod movies;
 pub mod series;
+pub mod stats;
+pub mod themes;
 
 // define_service! {
 // 	pub struct SeriesService {
@@ -0,0 +1,219 @@
+use crate::macros::define_service;
+use dbost_entities::episode_marker;
+use sea_orm::{
+	sea_query::{Expr, OnConflict},
+	ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+use uuid::Uuid;
+
+define_service! {
+	#[derive(Clone)]
+	pub struct MarkerService {
+		pub db: DatabaseConnection,
+	}
+}
+
+/// Which part of an episode a marker covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MarkerKind {
+	Intro,
+	Outro,
+}
+
+impl MarkerKind {
+	pub const ALL: [MarkerKind; 2] = [MarkerKind::Intro, MarkerKind::Outro];
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Intro => "intro",
+			Self::Outro => "outro",
+		}
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Intro => "Intro",
+			Self::Outro => "Outro",
+		}
+	}
+}
+
+impl fmt::Display for MarkerKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[derive(Debug, Error)]
+#[error("unknown marker kind: {0}")]
+pub struct UnknownMarkerKind(String);
+
+impl FromStr for MarkerKind {
+	type Err = UnknownMarkerKind;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"intro" => Ok(Self::Intro),
+			"outro" => Ok(Self::Outro),
+			_ => Err(UnknownMarkerKind(s.to_owned())),
+		}
+	}
+}
+
+#[derive(Debug, Error)]
+pub enum MarkerServiceError {
+	#[error("marker must end after it starts ({start_ms}ms - {end_ms}ms)")]
+	InvalidRange { start_ms: u32, end_ms: u32 },
+
+	#[error(transparent)]
+	DbErr(#[from] DbErr),
+}
+
+/// Parses a timestamp as written by contributors, either `h:mm:ss`, `m:ss` or
+/// plain seconds, each optionally with a fractional part, into milliseconds.
+pub fn parse_timestamp(value: &str) -> Option<u32> {
+	let value = value.trim();
+	let (rest, fraction) = match value.split_once('.') {
+		None => (value, 0),
+		Some((rest, fraction)) => {
+			if fraction.is_empty() || fraction.len() > 3 || !fraction.bytes().all(|b| b.is_ascii_digit())
+			{
+				return None;
+			}
+
+			let scale = 10u32.pow(3 - fraction.len() as u32);
+			(rest, fraction.parse::<u32>().ok()? * scale)
+		}
+	};
+
+	let mut seconds = 0u32;
+	for (i, part) in rest.split(':').enumerate() {
+		// minutes and seconds after the first part are limited to 0-59
+		if i > 2 || part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+			return None;
+		}
+
+		let part = part.parse::<u32>().ok()?;
+		if i > 0 && part > 59 {
+			return None;
+		}
+
+		seconds = seconds.checked_mul(60)?.checked_add(part)?;
+	}
+
+	seconds.checked_mul(1000)?.checked_add(fraction)
+}
+
+/// Formats milliseconds as `m:ss` (or `h:mm:ss`), dropping the milliseconds.
+pub fn format_timestamp(ms: u32) -> String {
+	let seconds = ms / 1000;
+	let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+	match hours {
+		0 => format!("{minutes}:{seconds:02}"),
+		_ => format!("{hours}:{minutes:02}:{seconds:02}"),
+	}
+}
+
+impl MarkerService {
+	/// Gets the markers of all the given tvdb episodes, ordered by episode
+	/// and kind.
+	pub async fn get_for_episodes(
+		&self,
+		episode_tvdb_ids: impl IntoIterator<Item = i32>,
+	) -> Result<Vec<episode_marker::Model>, MarkerServiceError> {
+		let markers = episode_marker::Entity::find()
+			.filter(episode_marker::Column::EpisodeTvdbId.is_in(episode_tvdb_ids))
+			.order_by_asc(episode_marker::Column::EpisodeTvdbId)
+			.order_by_asc(episode_marker::Column::Kind)
+			.all(&self.db)
+			.await?;
+
+		Ok(markers)
+	}
+
+	/// Records the marker of the given kind for a tvdb episode, replacing the
+	/// previous one if any.
+	pub async fn set_marker(
+		&self,
+		episode_tvdb_id: i32,
+		kind: MarkerKind,
+		theme_song_id: Uuid,
+		start_ms: u32,
+		end_ms: u32,
+		user_id: Option<Uuid>,
+	) -> Result<episode_marker::Model, MarkerServiceError> {
+		if end_ms <= start_ms {
+			return Err(MarkerServiceError::InvalidRange { start_ms, end_ms });
+		}
+
+		let model = episode_marker::ActiveModel {
+			episode_tvdb_id: Set(episode_tvdb_id),
+			kind: Set(kind.as_str().to_owned()),
+			theme_song_id: Set(theme_song_id),
+			start_ms: Set(start_ms as i32),
+			end_ms: Set(end_ms as i32),
+			user_id: Set(user_id),
+			..Default::default()
+		};
+
+		let marker = episode_marker::Entity::insert(model)
+			.on_conflict(
+				OnConflict::columns([
+					episode_marker::Column::EpisodeTvdbId,
+					episode_marker::Column::Kind,
+				])
+				.update_columns([
+					episode_marker::Column::ThemeSongId,
+					episode_marker::Column::StartMs,
+					episode_marker::Column::EndMs,
+					episode_marker::Column::UserId,
+				])
+				.value(
+					episode_marker::Column::Version,
+					Expr::cust("timezone('utc', now())"),
+				)
+				.to_owned(),
+			)
+			.exec_with_returning(&self.db)
+			.await?;
+
+		Ok(marker)
+	}
+
+	/// Removes the marker of the given kind from a tvdb episode.
+	pub async fn delete_marker(
+		&self,
+		episode_tvdb_id: i32,
+		kind: MarkerKind,
+	) -> Result<bool, MarkerServiceError> {
+		let result = episode_marker::Entity::delete_many()
+			.filter(episode_marker::Column::EpisodeTvdbId.eq(episode_tvdb_id))
+			.filter(episode_marker::Column::Kind.eq(kind.as_str()))
+			.exec(&self.db)
+			.await?;
+
+		Ok(result.rows_affected > 0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn timestamps() {
+		assert_eq!(parse_timestamp("12"), Some(12_000));
+		assert_eq!(parse_timestamp("1:42"), Some(102_000));
+		assert_eq!(parse_timestamp(" 1:02:03.5 "), Some(3_723_500));
+		assert_eq!(parse_timestamp("0:05.25"), Some(5_250));
+		assert_eq!(parse_timestamp("1:60"), None);
+		assert_eq!(parse_timestamp("1::2"), None);
+		assert_eq!(parse_timestamp("1:2:3:4"), None);
+		assert_eq!(parse_timestamp("-1"), None);
+
+		assert_eq!(format_timestamp(102_500), "1:42");
+		assert_eq!(format_timestamp(3_723_000), "1:02:03");
+	}
+}
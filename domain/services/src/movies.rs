@@ -0,0 +1,168 @@
+use crate::macros::define_service;
+use dbost_entities::movie;
+use dbost_utils::{ActiveValueExt, ActiveVersion};
+use sea_orm::{
+	sea_query::Expr, ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, Condition,
+	DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, Set, TryIntoModel,
+};
+use std::sync::Arc;
+use thiserror::Error;
+use tvdb_client::TvDbClient;
+use uuid::Uuid;
+
+define_service! {
+	#[derive(Clone)]
+	pub struct MovieService {
+		pub db: DatabaseConnection,
+		pub tvdb: Arc<TvDbClient>,
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MovieRef {
+	Id(Uuid),
+	TvDbId(u64),
+}
+
+impl From<Uuid> for MovieRef {
+	fn from(value: Uuid) -> Self {
+		Self::Id(value)
+	}
+}
+
+#[derive(Debug, Error)]
+pub enum MovieServiceError {
+	#[error("movie not found: {0:?}")]
+	NotFound(MovieRef),
+
+	#[error(transparent)]
+	DbErr(#[from] DbErr),
+
+	#[error(transparent)]
+	TvDbError(#[from] tvdb_client::TvDbError),
+}
+
+impl MovieService {
+	pub async fn get_movie(
+		&self,
+		id: impl Into<MovieRef>,
+	) -> Result<Option<movie::Model>, MovieServiceError> {
+		let movie = match id.into() {
+			MovieRef::Id(v) => movie::Entity::find_by_id(v).one(&self.db).await?,
+			MovieRef::TvDbId(v) => match i32::try_from(v) {
+				Ok(v) => {
+					movie::Entity::find()
+						.filter(movie::Column::TvdbId.eq(v))
+						.one(&self.db)
+						.await?
+				}
+				Err(_) => None,
+			},
+		};
+
+		Ok(movie)
+	}
+
+	/// Stores the blurhash placeholder for every movie using `image`.
+	pub async fn set_image_blurhash(
+		&self,
+		image: &str,
+		blurhash: String,
+	) -> Result<(), MovieServiceError> {
+		// the blurhash is served with the movie, so its tag has to change too
+		movie::Entity::update_many()
+			.col_expr(
+				movie::Column::ImageBlurhash,
+				Expr::value(Some(blurhash.clone())),
+			)
+			.col_expr(
+				movie::Column::Version,
+				Expr::value(ActiveVersion::now().unwrap()),
+			)
+			.filter(movie::Column::Image.eq(image))
+			.filter(
+				Condition::any()
+					.add(movie::Column::ImageBlurhash.is_null())
+					.add(movie::Column::ImageBlurhash.ne(&blurhash)),
+			)
+			.exec(&self.db)
+			.await?;
+
+		Ok(())
+	}
+
+	/// Whether `image` is used by a movie.
+	pub async fn has_image(&self, image: &str) -> Result<bool, MovieServiceError> {
+		let movies = movie::Entity::find()
+			.filter(movie::Column::Image.eq(image))
+			.count(&self.db)
+			.await?;
+
+		Ok(movies > 0)
+	}
+
+	/// Whether a movie uses `image` without having its blurhash.
+	pub async fn needs_image_blurhash(&self, image: &str) -> Result<bool, MovieServiceError> {
+		let movies = movie::Entity::find()
+			.filter(movie::Column::Image.eq(image))
+			.filter(movie::Column::ImageBlurhash.is_null())
+			.count(&self.db)
+			.await?;
+
+		Ok(movies > 0)
+	}
+
+	/// Imports the movie from tvdb, or updates it if it was imported before.
+	pub async fn fetch_from_tvdb(&self, id: u64) -> Result<Option<movie::Model>, MovieServiceError> {
+		// tvdb ids are stored as i32, so a larger one can't be a movie we store
+		let Ok(tvdb_id) = i32::try_from(id) else {
+			return Ok(None);
+		};
+
+		let update = match self.tvdb.get_movie(id).await? {
+			None => return Ok(None),
+			Some(v) => v,
+		};
+
+		let existing = self.get_movie(MovieRef::TvDbId(id)).await?;
+		let movie = match existing {
+			None => {
+				let movie = movie::ActiveModel {
+					id: NotSet,
+					name: Set(update.name),
+					description: Set(update.description),
+					tvdb_id: Set(tvdb_id),
+					year: Set(update.year.map(i32::from)),
+					theme_song_id: Set(None),
+					version: NotSet,
+					image: Set(update.image),
+					image_blurhash: Set(None),
+				};
+
+				movie.insert(&self.db).await?
+			}
+			Some(movie) => {
+				let mut movie: movie::ActiveModel = movie.into();
+				movie.name.update(update.name);
+				movie.description.update(update.description);
+				movie.year.update(update.year.map(i32::from));
+				if let Some(image) = update.image {
+					movie.image.update(Some(image));
+					if movie.image.is_set() {
+						// the placeholder is computed again once the new image is cached
+						movie.image_blurhash.update(None);
+					}
+				}
+
+				if movie.is_changed() {
+					movie.version = ActiveVersion::now();
+					movie.update(&self.db).await?
+				} else {
+					movie.try_into_model()?
+				}
+			}
+		};
+
+		Ok(Some(movie))
+	}
+}
@@ -1,14 +1,22 @@
 use crate::macros::define_service;
-use dbost_entities::{season, series};
-use dbost_utils::ActiveValueExt;
+use dbost_entities::{
+	artwork, episode, external_id, genre, season, series, series_alias, series_genre, theme_song,
+};
+use dbost_utils::{ActiveValueExt, ActiveVersion};
 use futures::{future::BoxFuture, FutureExt};
 use sea_orm::{
-	ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
-	QueryFilter, TransactionError, TransactionTrait, TryIntoModel,
+	prelude::TimeDateTime,
+	sea_query::{Expr, OnConflict},
+	ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+	DatabaseTransaction, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+	TransactionError, TransactionTrait, TryIntoModel,
+};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	sync::Arc,
 };
-use std::{collections::BTreeMap, sync::Arc};
 use thiserror::Error;
-use tvdb_client::TvDbClient;
+use tvdb_client::{ExternalSource, TvDbClient};
 use uuid::Uuid;
 
 define_service! {
@@ -19,10 +27,15 @@ define_service! {
 	}
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum SeriesRef {
 	Id(Uuid),
 	TvDbId(u64),
+	/// The id of the series in another database, like IMDb.
+	External {
+		source: ExternalSource,
+		id: String,
+	},
 }
 
 impl From<Uuid> for SeriesRef {
@@ -52,15 +65,512 @@ impl From<TransactionError<SeriesServiceError>> for SeriesServiceError {
 	}
 }
 
+/// Narrows down the series listing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeriesFilter {
+	/// Only series changed at or after this time.
+	pub updated_since: Option<TimeDateTime>,
+	pub has_theme: Option<bool>,
+}
+
+/// Where a page of the series listing starts, as the `_version` and id of the
+/// series next to it.
+#[derive(Debug, Clone, Copy)]
+pub enum SeriesPosition {
+	Start,
+	After(TimeDateTime, Uuid),
+	Before(TimeDateTime, Uuid),
+}
+
 pub struct SeriesWithSeasons {
 	pub series: series::Model,
 	pub seasons: Vec<season::Model>,
+	pub artworks: Vec<artwork::Model>,
+	pub episodes: Vec<episode::Model>,
+	pub genres: Vec<genre::Model>,
+	/// The theme songs of the series and its seasons.
+	pub themes: Vec<theme_song::Model>,
 }
 
 impl SeriesWithSeasons {
-	pub fn new(series: series::Model, seasons: Vec<season::Model>) -> Self {
-		Self { series, seasons }
+	pub fn new(
+		series: series::Model,
+		seasons: Vec<season::Model>,
+		artworks: Vec<artwork::Model>,
+		episodes: Vec<episode::Model>,
+		genres: Vec<genre::Model>,
+		themes: Vec<theme_song::Model>,
+	) -> Self {
+		Self {
+			series,
+			seasons,
+			artworks,
+			episodes,
+			genres,
+			themes,
+		}
+	}
+
+	/// The `_version`s of the series, its seasons and their theme songs.
+	pub fn versions(&self) -> impl Iterator<Item = TimeDateTime> + '_ {
+		let seasons = self.seasons.iter().map(|s| s.version);
+		let themes = self.themes.iter().map(|t| t.version);
+		std::iter::once(self.series.version)
+			.chain(seasons)
+			.chain(themes)
+	}
+}
+
+/// A series with just its seasons and theme songs.
+pub struct SeriesThemes {
+	pub series: series::Model,
+	pub seasons: Vec<season::Model>,
+	/// The theme songs of the series and its seasons.
+	pub themes: Vec<theme_song::Model>,
+}
+
+/// The theme songs used by a series and its seasons.
+fn theme_ids<'a>(
+	series: impl IntoIterator<Item = &'a series::Model>,
+	seasons: impl IntoIterator<Item = &'a season::Model>,
+) -> BTreeSet<Uuid> {
+	series
+		.into_iter()
+		.filter_map(|s| s.theme_song_id)
+		.chain(seasons.into_iter().filter_map(|s| s.theme_song_id))
+		.collect()
+}
+
+/// Loads theme songs in one query.
+async fn themes_db(
+	db: &impl ConnectionTrait,
+	ids: BTreeSet<Uuid>,
+) -> Result<Vec<theme_song::Model>, DbErr> {
+	if ids.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	theme_song::Entity::find()
+		.filter(theme_song::Column::Id.is_in(ids))
+		.all(db)
+		.await
+}
+
+/// Whether tvdb lists other seasons than the ones we have, keyed by tvdb id.
+fn seasons_added_or_removed<T>(seasons: &[season::Model], updates: &BTreeMap<i32, T>) -> bool {
+	let existing = seasons.iter().map(|s| s.tvdb_id).collect::<BTreeSet<_>>();
+	!existing.iter().eq(updates.keys())
+}
+
+/// The aliases to insert, as `(language, name)`, and the ids of the ones to
+/// delete.
+fn diff_aliases(
+	existing: Vec<series_alias::Model>,
+	aliases: impl IntoIterator<Item = tvdb_client::Alias>,
+) -> (BTreeSet<(Option<String>, String)>, Vec<Uuid>) {
+	let mut updates = aliases
+		.into_iter()
+		.map(|a| (a.language, a.name))
+		.collect::<BTreeSet<_>>();
+
+	let mut to_delete = Vec::with_capacity(existing.len());
+	for alias in existing {
+		if !updates.remove(&(alias.language, alias.name)) {
+			to_delete.push(alias.id);
+		}
+	}
+
+	(updates, to_delete)
+}
+
+async fn sync_aliases_db(
+	tx: &DatabaseTransaction,
+	series_id: Uuid,
+	aliases: impl IntoIterator<Item = tvdb_client::Alias>,
+) -> Result<bool, SeriesServiceError> {
+	use sea_orm::ActiveValue::*;
+
+	let existing = series_alias::Entity::find()
+		.filter(series_alias::Column::SeriesId.eq(series_id))
+		.all(tx)
+		.await?;
+
+	let (to_insert, to_delete) = diff_aliases(existing, aliases);
+	let to_insert = to_insert
+		.into_iter()
+		.map(|(language, name)| series_alias::ActiveModel {
+			id: Set(Uuid::new_v4()),
+			series_id: Set(series_id),
+			name: Set(name),
+			language: Set(language),
+		})
+		.collect::<Vec<_>>();
+
+	let changed = !to_insert.is_empty() || !to_delete.is_empty();
+	series_alias::Entity::insert_many(to_insert)
+		.on_empty_do_nothing()
+		.exec(tx)
+		.await?;
+
+	if !to_delete.is_empty() {
+		series_alias::Entity::delete_many()
+			.filter(series_alias::Column::Id.is_in(to_delete))
+			.exec(tx)
+			.await?;
+	}
+
+	Ok(changed)
+}
+
+async fn sync_external_ids_db(
+	tx: &DatabaseTransaction,
+	series_id: Uuid,
+	external_ids: impl IntoIterator<Item = tvdb_client::ExternalId>,
+) -> Result<bool, SeriesServiceError> {
+	use sea_orm::ActiveValue::*;
+
+	let existing = external_id::Entity::find()
+		.filter(external_id::Column::SeriesId.eq(series_id))
+		.all(tx)
+		.await?;
+
+	let mut updates = external_ids
+		.into_iter()
+		.map(|e| (e.source.as_str().to_owned(), e.id))
+		.collect::<BTreeSet<_>>();
+
+	let mut to_delete = Vec::new();
+	for external_id in existing {
+		if !updates.remove(&(external_id.source, external_id.value)) {
+			to_delete.push(external_id.id);
+		}
+	}
+
+	let to_insert = updates
+		.into_iter()
+		.map(|(source, value)| external_id::ActiveModel {
+			id: Set(Uuid::new_v4()),
+			series_id: Set(series_id),
+			source: Set(source),
+			value: Set(value),
+		})
+		.collect::<Vec<_>>();
+
+	let changed = !to_insert.is_empty() || !to_delete.is_empty();
+	external_id::Entity::insert_many(to_insert)
+		.on_empty_do_nothing()
+		.exec(tx)
+		.await?;
+
+	if !to_delete.is_empty() {
+		external_id::Entity::delete_many()
+			.filter(external_id::Column::Id.is_in(to_delete))
+			.exec(tx)
+			.await?;
+	}
+
+	Ok(changed)
+}
+
+async fn sync_genres_db(
+	tx: &DatabaseTransaction,
+	series_id: Uuid,
+	genres: Vec<tvdb_client::Genre>,
+) -> Result<(Vec<genre::Model>, bool), SeriesServiceError> {
+	use sea_orm::ActiveValue::*;
+
+	let old_genres = genre::Entity::find()
+		.inner_join(series_genre::Entity)
+		.filter(series_genre::Column::SeriesId.eq(series_id))
+		.order_by_asc(genre::Column::Name)
+		.order_by_asc(genre::Column::Id)
+		.all(tx)
+		.await?;
+
+	// tvdb ids are stored as i32, so genres with larger ones are skipped
+	let genres = genres
+		.into_iter()
+		.filter_map(|g| Some((i32::try_from(g.id).ok()?, g)))
+		.collect::<Vec<_>>();
+	let tvdb_ids = genres.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+	let to_upsert = genres
+		.into_iter()
+		.map(|(tvdb_id, g)| genre::ActiveModel {
+			id: NotSet,
+			tvdb_id: Set(tvdb_id),
+			name: Set(g.name),
+			slug: Set(g.slug),
+		})
+		.collect::<Vec<_>>();
+
+	genre::Entity::insert_many(to_upsert)
+		.on_conflict(
+			OnConflict::column(genre::Column::TvdbId)
+				.update_columns([genre::Column::Name, genre::Column::Slug])
+				.to_owned(),
+		)
+		.on_empty_do_nothing()
+		.exec(tx)
+		.await?;
+
+	let genres = genre::Entity::find()
+		.filter(genre::Column::TvdbId.is_in(tvdb_ids))
+		.order_by_asc(genre::Column::Name)
+		.order_by_asc(genre::Column::Id)
+		.all(tx)
+		.await?;
+
+	series_genre::Entity::delete_many()
+		.filter(series_genre::Column::SeriesId.eq(series_id))
+		.filter(series_genre::Column::GenreId.is_not_in(genres.iter().map(|g| g.id)))
+		.exec(tx)
+		.await?;
+
+	let links = genres.iter().map(|g| series_genre::ActiveModel {
+		series_id: Set(series_id),
+		genre_id: Set(g.id),
+	});
+
+	series_genre::Entity::insert_many(links)
+		.on_conflict(
+			OnConflict::columns([
+				series_genre::Column::SeriesId,
+				series_genre::Column::GenreId,
+			])
+			.do_nothing()
+			.to_owned(),
+		)
+		.on_empty_do_nothing()
+		.exec(tx)
+		.await?;
+
+	let changed = genres != old_genres;
+	Ok((genres, changed))
+}
+
+fn take_season_artworks(
+	seasons: &mut [tvdb_client::Season],
+) -> Vec<(i32, Vec<tvdb_client::Artwork>)> {
+	seasons
+		.iter_mut()
+		.filter_map(|s| Some((i32::try_from(s.id).ok()?, std::mem::take(&mut s.artworks))))
+		.collect()
+}
+
+async fn sync_artworks_db(
+	tx: &DatabaseTransaction,
+	series_id: Uuid,
+	seasons: &[season::Model],
+	series_artworks: Vec<tvdb_client::Artwork>,
+	season_artworks: Vec<(i32, Vec<tvdb_client::Artwork>)>,
+) -> Result<(Vec<artwork::Model>, bool), SeriesServiceError> {
+	use sea_orm::ActiveValue::*;
+
+	let season_ids = seasons
+		.iter()
+		.map(|s| (s.tvdb_id, s.id))
+		.collect::<BTreeMap<_, _>>();
+
+	// season artwork is sometimes also listed on the series, in which case
+	// we want to keep it attached to the season. artwork with tvdb ids that
+	// don't fit the stored i32 is skipped
+	let mut updates = series_artworks
+		.into_iter()
+		.filter_map(|a| Some((i32::try_from(a.id).ok()?, (None, a))))
+		.collect::<BTreeMap<_, _>>();
+
+	for (season_tvdb_id, artworks) in season_artworks {
+		let season_id = season_ids.get(&season_tvdb_id).copied();
+		updates.extend(
+			artworks
+				.into_iter()
+				.filter_map(|a| Some((i32::try_from(a.id).ok()?, (season_id, a)))),
+		);
+	}
+
+	let old_artworks = artwork::Entity::find()
+		.filter(artwork::Column::SeriesId.eq(series_id))
+		.all(tx)
+		.await?;
+
+	let mut changed = false;
+	let mut artworks = Vec::with_capacity(updates.len());
+	let mut to_delete = Vec::with_capacity(old_artworks.len());
+	for artwork in old_artworks {
+		match updates.remove(&artwork.tvdb_id) {
+			Some((season_id, update)) => {
+				let mut artwork: artwork::ActiveModel = artwork.into();
+				artwork.season_id.update(season_id);
+				artwork.kind.update(u8::from(update.kind) as i16);
+				artwork.url.update(update.url);
+				artwork.thumbnail.update(update.thumbnail);
+				artwork.language.update(update.language);
+				artwork
+					.score
+					.update(i32::try_from(update.score).unwrap_or(i32::MAX));
+				artwork.width.update(update.width.map(|w| w as i32));
+				artwork.height.update(update.height.map(|h| h as i32));
+
+				let artwork = if artwork.is_changed() {
+					changed = true;
+					artwork.update(tx).await?
+				} else {
+					artwork.try_into_model()?
+				};
+				artworks.push(artwork);
+			}
+			None => to_delete.push(artwork.id),
+		}
+	}
+
+	if !to_delete.is_empty() {
+		changed = true;
+		artwork::Entity::delete_many()
+			.filter(artwork::Column::Id.is_in(to_delete))
+			.exec(tx)
+			.await?;
+	}
+
+	let (ids, to_insert): (Vec<_>, Vec<_>) = updates
+		.into_iter()
+		.map(|(tvdb_id, (season_id, update))| {
+			let artwork_id = Uuid::new_v4();
+			(
+				artwork_id,
+				artwork::ActiveModel {
+					id: Set(artwork_id),
+					tvdb_id: Set(tvdb_id),
+					series_id: Set(series_id),
+					season_id: Set(season_id),
+					kind: Set(u8::from(update.kind) as i16),
+					url: Set(update.url),
+					thumbnail: Set(update.thumbnail),
+					language: Set(update.language),
+					score: Set(i32::try_from(update.score).unwrap_or(i32::MAX)),
+					width: Set(update.width.map(|w| w as i32)),
+					height: Set(update.height.map(|h| h as i32)),
+				},
+			)
+		})
+		.unzip();
+
+	if !to_insert.is_empty() {
+		changed = true;
+		artwork::Entity::insert_many(to_insert).exec(tx).await?;
+		artworks.extend(
+			artwork::Entity::find()
+				.filter(artwork::Column::Id.is_in(ids))
+				.all(tx)
+				.await?,
+		);
+	}
+
+	Ok((artworks, changed))
+}
+
+fn take_season_episodes(
+	seasons: &mut [tvdb_client::Season],
+) -> Vec<(i32, Vec<tvdb_client::Episode>)> {
+	seasons
+		.iter_mut()
+		.filter_map(|s| Some((i32::try_from(s.id).ok()?, std::mem::take(&mut s.episodes))))
+		.collect()
+}
+
+async fn sync_episodes_db(
+	tx: &DatabaseTransaction,
+	series_id: Uuid,
+	seasons: &[season::Model],
+	season_episodes: Vec<(i32, Vec<tvdb_client::Episode>)>,
+) -> Result<(Vec<episode::Model>, bool), SeriesServiceError> {
+	use sea_orm::ActiveValue::*;
+
+	// postgres limits the number of parameters in a single statement
+	const INSERT_CHUNK_SIZE: usize = 1000;
+
+	let season_ids = seasons
+		.iter()
+		.map(|s| (s.tvdb_id, s.id))
+		.collect::<BTreeMap<_, _>>();
+
+	let mut updates = BTreeMap::new();
+	for (season_tvdb_id, episodes) in season_episodes {
+		let season_id = match season_ids.get(&season_tvdb_id) {
+			Some(season_id) => *season_id,
+			None => continue,
+		};
+
+		// episodes with tvdb ids that don't fit the stored i32 are skipped
+		updates.extend(
+			episodes
+				.into_iter()
+				.filter_map(|e| Some(((season_id, i32::try_from(e.id).ok()?), e))),
+		);
+	}
+
+	let old_episodes = episode::Entity::find()
+		.filter(episode::Column::SeriesId.eq(series_id))
+		.all(tx)
+		.await?;
+
+	let mut changed = false;
+	let mut to_delete = Vec::with_capacity(old_episodes.len());
+	for episode in old_episodes {
+		match updates.remove(&(episode.season_id, episode.tvdb_id)) {
+			Some(update) => {
+				let mut episode: episode::ActiveModel = episode.into();
+				episode.number.update(update.number as i32);
+				episode
+					.absolute_number
+					.update(update.absolute_number.map(|n| n as i32));
+				episode.name.update(update.name);
+				episode.air_date.update(update.aired);
+
+				if episode.is_changed() {
+					changed = true;
+					episode.update(tx).await?;
+				}
+			}
+			None => to_delete.push(episode.id),
+		}
 	}
+
+	if !to_delete.is_empty() {
+		changed = true;
+		episode::Entity::delete_many()
+			.filter(episode::Column::Id.is_in(to_delete))
+			.exec(tx)
+			.await?;
+	}
+
+	let to_insert = updates
+		.into_iter()
+		.map(|((season_id, tvdb_id), update)| episode::ActiveModel {
+			id: Set(Uuid::new_v4()),
+			series_id: Set(series_id),
+			season_id: Set(season_id),
+			tvdb_id: Set(tvdb_id),
+			number: Set(update.number as i32),
+			absolute_number: Set(update.absolute_number.map(|n| n as i32)),
+			name: Set(update.name),
+			air_date: Set(update.aired),
+		})
+		.collect::<Vec<_>>();
+
+	changed |= !to_insert.is_empty();
+	for chunk in to_insert.chunks(INSERT_CHUNK_SIZE) {
+		episode::Entity::insert_many(chunk.iter().cloned())
+			.exec(tx)
+			.await?;
+	}
+
+	let episodes = episode::Entity::find()
+		.filter(episode::Column::SeriesId.eq(series_id))
+		.order_by_asc(episode::Column::Number)
+		.all(tx)
+		.await?;
+
+	Ok((episodes, changed))
 }
 
 impl SeriesService {
@@ -74,9 +584,22 @@ impl SeriesService {
 		) -> Result<Option<SeriesWithSeasons>, SeriesServiceError> {
 			let series = match id {
 				SeriesRef::Id(v) => series::Entity::find_by_id(v).one(&service.db).await?,
-				SeriesRef::TvDbId(v) => {
+				SeriesRef::TvDbId(v) => match i32::try_from(v) {
+					Ok(v) => {
+						series::Entity::find()
+							.filter(series::Column::TvdbId.eq(v))
+							.one(&service.db)
+							.await?
+					}
+					Err(_) => None,
+				},
+				SeriesRef::External { source, id } => {
+					// should the id be on more than one series, the oldest one wins
 					series::Entity::find()
-						.filter(series::Column::TvdbId.eq(v as i32))
+						.inner_join(external_id::Entity)
+						.filter(external_id::Column::Source.eq(source.as_str()))
+						.filter(external_id::Column::Value.eq(id))
+						.order_by_asc(series::Column::TvdbId)
 						.one(&service.db)
 						.await?
 				}
@@ -92,12 +615,219 @@ impl SeriesService {
 				.all(&service.db)
 				.await?;
 
-			Ok(Some(SeriesWithSeasons::new(series, seasons)))
+			let artworks = artwork::Entity::find()
+				.filter(artwork::Column::SeriesId.eq(series.id))
+				.all(&service.db)
+				.await?;
+
+			let episodes = episode::Entity::find()
+				.filter(episode::Column::SeriesId.eq(series.id))
+				.order_by_asc(episode::Column::Number)
+				.all(&service.db)
+				.await?;
+
+			let genres = genre::Entity::find()
+				.inner_join(series_genre::Entity)
+				.filter(series_genre::Column::SeriesId.eq(series.id))
+				.order_by_asc(genre::Column::Name)
+				.all(&service.db)
+				.await?;
+
+			let themes = themes_db(&service.db, theme_ids([&series], &seasons)).await?;
+
+			Ok(Some(SeriesWithSeasons::new(
+				series, seasons, artworks, episodes, genres, themes,
+			)))
 		}
 
 		get_series(self, id.into()).await
 	}
 
+	/// Looks up many series by their TVDB ids at once, with a query each for
+	/// the series, their seasons and their theme songs. Ids that aren't known,
+	/// or are too large to be stored, are left out.
+	pub async fn lookup_by_tvdb_ids(
+		&self,
+		ids: impl IntoIterator<Item = u64>,
+	) -> Result<Vec<SeriesThemes>, DbErr> {
+		let ids = ids
+			.into_iter()
+			.filter_map(|id| i32::try_from(id).ok())
+			.collect::<BTreeSet<_>>();
+		if ids.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let series = series::Entity::find()
+			.filter(series::Column::TvdbId.is_in(ids))
+			.order_by_asc(series::Column::TvdbId)
+			.all(&self.db)
+			.await?;
+
+		let seasons = season::Entity::find()
+			.filter(season::Column::SeriesId.is_in(series.iter().map(|s| s.id)))
+			.order_by_asc(season::Column::Number)
+			.all(&self.db)
+			.await?;
+
+		let themes = themes_db(&self.db, theme_ids(&series, &seasons)).await?;
+
+		let mut series_seasons = BTreeMap::<Uuid, Vec<season::Model>>::new();
+		for season in seasons {
+			series_seasons
+				.entry(season.series_id)
+				.or_default()
+				.push(season);
+		}
+
+		Ok(
+			series
+				.into_iter()
+				.map(|series| {
+					let seasons = series_seasons.remove(&series.id).unwrap_or_default();
+					let themes = themes
+						.iter()
+						.filter(|theme| {
+							series.theme_song_id == Some(theme.id)
+								|| seasons.iter().any(|s| s.theme_song_id == Some(theme.id))
+						})
+						.cloned()
+						.collect();
+
+					SeriesThemes {
+						series,
+						seasons,
+						themes,
+					}
+				})
+				.collect(),
+		)
+	}
+
+	/// Lists series in the order they were last changed in (and by id for the
+	/// same `_version`), so clients can keep up with the catalog by listing
+	/// what changed since they last looked. Series before a position are
+	/// returned closest first, so in reverse order.
+	pub async fn list_series(
+		&self,
+		filter: SeriesFilter,
+		position: SeriesPosition,
+		limit: u64,
+	) -> Result<Vec<series::Model>, DbErr> {
+		let mut query = series::Entity::find();
+
+		if let Some(since) = filter.updated_since {
+			query = query.filter(series::Column::Version.gte(since));
+		}
+
+		query = match filter.has_theme {
+			Some(true) => query.filter(series::Column::ThemeSongId.is_not_null()),
+			Some(false) => query.filter(series::Column::ThemeSongId.is_null()),
+			None => query,
+		};
+
+		let key = Expr::tuple([
+			Expr::col(series::Column::Version).into(),
+			Expr::col(series::Column::Id).into(),
+		]);
+
+		query = match position {
+			SeriesPosition::Start => query
+				.order_by_asc(series::Column::Version)
+				.order_by_asc(series::Column::Id),
+			SeriesPosition::After(version, id) => query
+				.filter(key.gt(Expr::tuple([version.into(), id.into()])))
+				.order_by_asc(series::Column::Version)
+				.order_by_asc(series::Column::Id),
+			SeriesPosition::Before(version, id) => query
+				.filter(key.lt(Expr::tuple([version.into(), id.into()])))
+				.order_by_desc(series::Column::Version)
+				.order_by_desc(series::Column::Id),
+		};
+
+		query.limit(limit).all(&self.db).await
+	}
+
+	/// Stores the blurhash placeholder for every series and season using `image`.
+	pub async fn set_image_blurhash(
+		&self,
+		image: &str,
+		blurhash: String,
+	) -> Result<(), SeriesServiceError> {
+		// the blurhash is part of what the api serves, so rows it changes get a new
+		// version for their tags to change with
+		let now = ActiveVersion::now().unwrap();
+		series::Entity::update_many()
+			.col_expr(
+				series::Column::ImageBlurhash,
+				Expr::value(Some(blurhash.clone())),
+			)
+			.col_expr(series::Column::Version, Expr::value(now))
+			.filter(series::Column::Image.eq(image))
+			.filter(
+				Condition::any()
+					.add(series::Column::ImageBlurhash.is_null())
+					.add(series::Column::ImageBlurhash.ne(&blurhash)),
+			)
+			.exec(&self.db)
+			.await?;
+
+		season::Entity::update_many()
+			.col_expr(
+				season::Column::ImageBlurhash,
+				Expr::value(Some(blurhash.clone())),
+			)
+			.col_expr(season::Column::Version, Expr::value(now))
+			.filter(season::Column::Image.eq(image))
+			.filter(
+				Condition::any()
+					.add(season::Column::ImageBlurhash.is_null())
+					.add(season::Column::ImageBlurhash.ne(&blurhash)),
+			)
+			.exec(&self.db)
+			.await?;
+
+		Ok(())
+	}
+
+	/// Whether `image` is used by a series or season, or is in the artwork
+	/// catalog.
+	pub async fn has_image(&self, image: &str) -> Result<bool, SeriesServiceError> {
+		let series = series::Entity::find()
+			.filter(series::Column::Image.eq(image))
+			.count(&self.db)
+			.await?;
+
+		let seasons = season::Entity::find()
+			.filter(season::Column::Image.eq(image))
+			.count(&self.db)
+			.await?;
+
+		let artworks = artwork::Entity::find()
+			.filter(artwork::Column::Url.eq(image))
+			.count(&self.db)
+			.await?;
+
+		Ok(series + seasons + artworks > 0)
+	}
+
+	/// Whether a series or season uses `image` without having its blurhash.
+	pub async fn needs_image_blurhash(&self, image: &str) -> Result<bool, SeriesServiceError> {
+		let series = series::Entity::find()
+			.filter(series::Column::Image.eq(image))
+			.filter(series::Column::ImageBlurhash.is_null())
+			.count(&self.db)
+			.await?;
+
+		let seasons = season::Entity::find()
+			.filter(season::Column::Image.eq(image))
+			.filter(season::Column::ImageBlurhash.is_null())
+			.count(&self.db)
+			.await?;
+
+		Ok(series + seasons > 0)
+	}
+
 	pub async fn fetch_from_tvdb(
 		&self,
 		id: u64,
@@ -110,9 +840,11 @@ impl SeriesService {
 		) -> Result<Vec<season::Model>, SeriesServiceError> {
 			use sea_orm::ActiveValue::*;
 
+			// seasons with tvdb ids that don't fit the stored i32 are skipped
 			let (ids, seasons): (Vec<_>, Vec<_>) = seasons
 				.into_iter()
-				.map(|update| {
+				.filter_map(|update| Some((i32::try_from(update.id).ok()?, update)))
+				.map(|(tvdb_id, update)| {
 					let season_id = Uuid::new_v4();
 					(
 						season_id,
@@ -120,10 +852,12 @@ impl SeriesService {
 							id: Set(season_id),
 							name: Set(update.name),
 							description: Set(update.description),
+							season_type: Set(update.season_type.as_str().to_owned()),
 							number: Set(update.number as i16),
-							tvdb_id: Set(update.id as i32),
+							tvdb_id: Set(tvdb_id),
 							series_id: Set(series_id),
 							image: Set(update.image),
+							image_blurhash: Set(None),
 							theme_song_id: Set(None),
 							version: NotSet,
 						},
@@ -146,92 +880,194 @@ impl SeriesService {
 
 		async fn insert_series_db(
 			tx: &DatabaseTransaction,
-			update: tvdb_client::Series,
+			mut update: tvdb_client::Series,
 		) -> Result<SeriesWithSeasons, SeriesServiceError> {
 			use sea_orm::ActiveValue::*;
 
+			let Ok(tvdb_id) = i32::try_from(update.id) else {
+				return Err(SeriesServiceError::NotFound(SeriesRef::TvDbId(update.id)));
+			};
+
 			let series = series::ActiveModel {
 				id: Set(Uuid::new_v4()),
 				name: Set(update.name),
 				description: Set(update.description),
-				tvdb_id: Set(update.id as i32),
+				tvdb_id: Set(tvdb_id),
 				image: Set(update.image),
+				image_blurhash: Set(None),
 				theme_song_id: Set(None),
 				version: NotSet,
+				created: NotSet,
+				year: Set(update.year.map(i32::from)),
+				status: Set(update.status),
+				network: Set(update.original_network.as_ref().map(|n| n.name.clone())),
+				network_slug: Set(update.original_network.map(|n| n.slug)),
+				original_language: Set(update.original_language),
+				view_count: NotSet,
 			};
 
 			let series = series.insert(tx).await?;
 
+			sync_aliases_db(tx, series.id, update.aliases).await?;
+			sync_external_ids_db(tx, series.id, update.external_ids).await?;
+			let (genres, _) = sync_genres_db(tx, series.id, update.genres).await?;
+			let season_artworks = take_season_artworks(&mut update.seasons);
+			let season_episodes = take_season_episodes(&mut update.seasons);
 			let seasons = insert_seasons_db(tx, series.id, update.seasons).await?;
+			let (artworks, _) =
+				sync_artworks_db(tx, series.id, &seasons, update.artworks, season_artworks).await?;
+			let (episodes, _) = sync_episodes_db(tx, series.id, &seasons, season_episodes).await?;
 
-			Ok(SeriesWithSeasons::new(series, seasons))
+			// a series that was just imported has no theme songs yet
+			Ok(SeriesWithSeasons::new(
+				series,
+				seasons,
+				artworks,
+				episodes,
+				genres,
+				Vec::new(),
+			))
 		}
 
 		async fn update_series_db(
 			tx: &DatabaseTransaction,
-			update: tvdb_client::Series,
+			mut update: tvdb_client::Series,
 			series: series::Model,
 			seasons: Vec<season::Model>,
 		) -> Result<SeriesWithSeasons, SeriesServiceError> {
 			let mut series: series::ActiveModel = series.into();
 			series.name.update(update.name);
 			series.description.update(update.description);
+			series.year.update(update.year.map(i32::from));
+			series.status.update(update.status);
+			series
+				.network
+				.update(update.original_network.as_ref().map(|n| n.name.clone()));
+			series
+				.network_slug
+				.update(update.original_network.map(|n| n.slug));
+			series.original_language.update(update.original_language);
 			if let Some(image) = update.image {
 				series.image.update(Some(image));
+				if series.image.is_set() {
+					// the placeholder is computed again once the new image is cached
+					series.image_blurhash.update(None);
+				}
 			}
 
-			let series = if series.is_changed() {
-				series.update(tx).await?
-			} else {
-				series.try_into_model()?
-			};
-
-			let old_seasons = seasons;
-			let mut seasons = Vec::with_capacity(update.seasons.len());
-			let mut to_delete = Vec::with_capacity(old_seasons.len());
+			let series_id = series.id.clone().unwrap();
+			let aliases_changed = sync_aliases_db(tx, series_id, update.aliases).await?;
+			let external_ids_changed = sync_external_ids_db(tx, series_id, update.external_ids).await?;
+			let (genres, genres_changed) = sync_genres_db(tx, series_id, update.genres).await?;
 
+			let season_artworks = take_season_artworks(&mut update.seasons);
+			let season_episodes = take_season_episodes(&mut update.seasons);
 			let mut updates = update
 				.seasons
 				.into_iter()
-				.map(|s| (s.id as i32, s))
+				.filter_map(|s| Some((i32::try_from(s.id).ok()?, s)))
 				.collect::<BTreeMap<_, _>>();
+			let seasons_changed = seasons_added_or_removed(&seasons, &updates);
+
+			// seasons are unique per (series, type, number), so removed seasons have to
+			// go before their replacements are inserted
+			let (old_seasons, to_delete): (Vec<_>, Vec<_>) = seasons
+				.into_iter()
+				.partition(|s| updates.contains_key(&s.tvdb_id));
 
+			if !to_delete.is_empty() {
+				season::Entity::delete_many()
+					.filter(season::Column::Id.is_in(to_delete.into_iter().map(|s| s.id)))
+					.exec(tx)
+					.await?;
+			}
+
+			// when tvdb renumbers or swaps seasons, updating them one by one would
+			// clash with a season that still has the number, so seasons that move
+			// get a negative number first, which is distinct but never a real one
+			let moved = old_seasons
+				.iter()
+				.filter(|s| {
+					let update = &updates[&s.tvdb_id];
+					s.number != update.number as i16 || s.season_type != update.season_type.as_str()
+				})
+				.map(|s| s.id)
+				.collect::<BTreeSet<_>>();
+
+			if !moved.is_empty() {
+				season::Entity::update_many()
+					.col_expr(
+						season::Column::Number,
+						Expr::val(-1).sub(Expr::col(season::Column::Number)),
+					)
+					.filter(season::Column::Id.is_in(moved.iter().copied()))
+					.exec(tx)
+					.await?;
+			}
+
+			let mut seasons = Vec::with_capacity(updates.len());
 			for season in old_seasons {
-				match updates.remove(&season.tvdb_id) {
-					Some(update) => {
-						let mut season: season::ActiveModel = season.into();
-						season.name.update(update.name);
-						season.description.update(update.description);
-						season.number.update(update.number as i16);
-						if let Some(image) = update.image {
-							season.image.update(Some(image));
-						}
-
-						let season = if season.is_changed() {
-							season.update(tx).await?
-						} else {
-							season.try_into_model()?
-						};
-						seasons.push(season);
-					}
-					None => {
-						to_delete.push(season);
+				let update = updates
+					.remove(&season.tvdb_id)
+					.expect("season was partitioned by having an update");
+
+				let moved = moved.contains(&season.id);
+				let mut season: season::ActiveModel = season.into();
+				season.name.update(update.name);
+				season.description.update(update.description);
+				season
+					.season_type
+					.update(update.season_type.as_str().to_owned());
+				match moved {
+					// the number in the database is the temporary one
+					true => season.number = ActiveValue::Set(update.number as i16),
+					false => season.number.update(update.number as i16),
+				}
+				if let Some(image) = update.image {
+					season.image.update(Some(image));
+					if season.image.is_set() {
+						season.image_blurhash.update(None);
 					}
 				}
+
+				let season = if season.is_changed() {
+					season.version = ActiveVersion::now();
+					season.update(tx).await?
+				} else {
+					season.try_into_model()?
+				};
+				seasons.push(season);
 			}
 
 			if !updates.is_empty() {
-				seasons.extend(insert_seasons_db(tx, series.id, updates.into_values()).await?);
+				seasons.extend(insert_seasons_db(tx, series_id, updates.into_values()).await?);
 			}
 
-			if !to_delete.is_empty() {
-				season::Entity::delete_many()
-					.filter(season::Column::Id.is_in(to_delete.into_iter().map(|s| s.id)))
-					.exec(tx)
-					.await?;
-			}
+			let (artworks, artworks_changed) =
+				sync_artworks_db(tx, series_id, &seasons, update.artworks, season_artworks).await?;
+			let (episodes, episodes_changed) =
+				sync_episodes_db(tx, series_id, &seasons, season_episodes).await?;
 
-			Ok(SeriesWithSeasons::new(series, seasons))
+			// aliases, genres and the like have no version of their own, and seasons
+			// that come or go leave none behind, so the series version is what
+			// changes the tags of responses that show them
+			let children_changed = seasons_changed
+				|| aliases_changed
+				|| external_ids_changed
+				|| genres_changed
+				|| artworks_changed
+				|| episodes_changed;
+			let series = if series.is_changed() || children_changed {
+				series.version = ActiveVersion::now();
+				series.update(tx).await?
+			} else {
+				series.try_into_model()?
+			};
+			let themes = themes_db(tx, theme_ids([&series], &seasons)).await?;
+
+			Ok(SeriesWithSeasons::new(
+				series, seasons, artworks, episodes, genres, themes,
+			))
 		}
 
 		async fn insert_or_update_series_db(
@@ -249,6 +1085,12 @@ impl SeriesService {
 			}
 		}
 
+		// tvdb ids are stored as i32, so a larger one can't be a series we know
+		// nor one we could store
+		let Ok(tvdb_id) = i32::try_from(id) else {
+			return Ok(None);
+		};
+
 		let tx = match transaction {
 			None => {
 				fn run_in_transaction(
@@ -277,7 +1119,7 @@ impl SeriesService {
 		};
 
 		let series = series::Entity::find()
-			.filter(series::Column::TvdbId.eq(id as i32))
+			.filter(series::Column::TvdbId.eq(tvdb_id))
 			.one(tx)
 			.await?;
 
@@ -296,3 +1138,64 @@ impl SeriesService {
 			.map(Some)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn alias(name: &str) -> tvdb_client::Alias {
+		tvdb_client::Alias {
+			language: Some("eng".to_owned()),
+			name: name.to_owned(),
+		}
+	}
+
+	#[test]
+	fn renamed_alias_changes_the_series() {
+		let existing = series_alias::Model {
+			id: Uuid::new_v4(),
+			series_id: Uuid::new_v4(),
+			name: "Old".to_owned(),
+			language: Some("eng".to_owned()),
+		};
+
+		// the same aliases leave the series, and with it its tag, as it was
+		let (to_insert, to_delete) = diff_aliases(vec![existing.clone()], [alias("Old")]);
+		assert!(to_insert.is_empty() && to_delete.is_empty());
+
+		// a renamed alias is a change, which bumps the version of the series
+		let (to_insert, to_delete) = diff_aliases(vec![existing.clone()], [alias("New")]);
+		assert_eq!(
+			to_insert.into_iter().collect::<Vec<_>>(),
+			[(Some("eng".to_owned()), "New".to_owned())]
+		);
+		assert_eq!(to_delete, [existing.id]);
+	}
+
+	fn season(tvdb_id: i32) -> season::Model {
+		season::Model {
+			id: Uuid::new_v4(),
+			series_id: Uuid::new_v4(),
+			number: 1,
+			season_type: "official".to_owned(),
+			name: None,
+			tvdb_id,
+			theme_song_id: None,
+			version: TimeDateTime::MIN,
+			image: None,
+			image_blurhash: None,
+			description: None,
+		}
+	}
+
+	#[test]
+	fn added_or_removed_season_changes_the_series() {
+		let seasons = [season(1), season(2)];
+		let updates = |ids: &[i32]| ids.iter().map(|&id| (id, ())).collect::<BTreeMap<_, _>>();
+
+		assert!(!seasons_added_or_removed(&seasons, &updates(&[1, 2])));
+		assert!(seasons_added_or_removed(&seasons, &updates(&[1])));
+		assert!(seasons_added_or_removed(&seasons, &updates(&[1, 2, 3])));
+		assert!(seasons_added_or_removed(&seasons, &updates(&[1, 3])));
+	}
+}
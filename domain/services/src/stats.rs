@@ -0,0 +1,224 @@
+use crate::macros::define_service;
+use dbost_entities::{genre, season, series, series_genre, theme_song, user};
+use sea_orm::{
+	sea_query::{Alias, Expr, Func},
+	ColumnTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult, JoinType, PaginatorTrait,
+	QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+use serde::Serialize;
+use time::{Date, Duration, OffsetDateTime};
+use tvdb_client::SeasonType;
+use uuid::Uuid;
+
+define_service! {
+	#[derive(Clone)]
+	pub struct StatsService {
+		pub db: DatabaseConnection,
+	}
+}
+
+/// How many weeks of new theme songs are reported.
+const WEEKS: i64 = 12;
+
+/// How many contributors are listed.
+const TOP_CONTRIBUTORS: u64 = 10;
+
+/// Overall progress of the theme song catalog.
+#[derive(Debug, Clone, Serialize)]
+pub struct Stats {
+	pub series: u64,
+	pub series_with_theme: u64,
+	pub seasons: u64,
+	pub seasons_with_theme: u64,
+	pub themes: u64,
+	pub coverage_by_genre: Vec<GenreCoverage>,
+	pub coverage_by_year: Vec<YearCoverage>,
+	pub top_contributors: Vec<Contributor>,
+	/// Theme songs added per week, oldest week first, including weeks without
+	/// any.
+	pub themes_per_week: Vec<WeekCount>,
+}
+
+/// Share of the series in a genre that have a theme song.
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+pub struct GenreCoverage {
+	pub name: String,
+	pub slug: String,
+	pub series: i64,
+	pub with_theme: i64,
+}
+
+/// Share of the series first aired in a year that have a theme song.
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+pub struct YearCoverage {
+	pub year: i32,
+	pub series: i64,
+	pub with_theme: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+pub struct Contributor {
+	pub user_id: Uuid,
+	pub display_name: String,
+	pub themes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WeekCount {
+	/// The monday the week starts on.
+	#[serde(serialize_with = "serialize_date")]
+	pub week: Date,
+	pub themes: i64,
+}
+
+#[derive(FromQueryResult)]
+struct WeekCountDb {
+	week: Date,
+	themes: i64,
+}
+
+fn serialize_date<S: serde::Serializer>(date: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.collect_str(date)
+}
+
+/// Percentage of `part` in `total`, 0 for nothing at all.
+pub fn percentage(part: i64, total: i64) -> f64 {
+	match total {
+		0 => 0.0,
+		total => part as f64 * 100.0 / total as f64,
+	}
+}
+
+impl StatsService {
+	pub async fn get_stats(&self) -> Result<Stats, DbErr> {
+		let series = series::Entity::find().count(&self.db).await?;
+		let series_with_theme = series::Entity::find()
+			.filter(series::Column::ThemeSongId.is_not_null())
+			.count(&self.db)
+			.await?;
+
+		// alternative orderings repeat the same episodes, so only the aired
+		// order counts
+		let seasons = season::Entity::find()
+			.filter(season::Column::SeasonType.eq(SeasonType::Official.as_str()))
+			.count(&self.db)
+			.await?;
+		let seasons_with_theme = season::Entity::find()
+			.filter(season::Column::SeasonType.eq(SeasonType::Official.as_str()))
+			.filter(season::Column::ThemeSongId.is_not_null())
+			.count(&self.db)
+			.await?;
+
+		let themes = theme_song::Entity::find().count(&self.db).await?;
+
+		let coverage_by_genre = genre::Entity::find()
+			.select_only()
+			.column(genre::Column::Name)
+			.column(genre::Column::Slug)
+			.column_as(
+				Expr::col((series::Entity, series::Column::Id)).count(),
+				"series",
+			)
+			.column_as(
+				Expr::col((series::Entity, series::Column::ThemeSongId)).count(),
+				"with_theme",
+			)
+			.join(JoinType::InnerJoin, genre::Relation::SeriesGenre.def())
+			.join(JoinType::InnerJoin, series_genre::Relation::Series.def())
+			.group_by(genre::Column::Id)
+			.order_by_asc(genre::Column::Name)
+			.into_model::<GenreCoverage>()
+			.all(&self.db)
+			.await?;
+
+		let coverage_by_year = series::Entity::find()
+			.select_only()
+			.column(series::Column::Year)
+			.column_as(series::Column::Id.count(), "series")
+			.column_as(series::Column::ThemeSongId.count(), "with_theme")
+			.filter(series::Column::Year.is_not_null())
+			.group_by(series::Column::Year)
+			.order_by_desc(series::Column::Year)
+			.into_model::<YearCoverage>()
+			.all(&self.db)
+			.await?;
+
+		let top_contributors = theme_song::Entity::find()
+			.select_only()
+			.column_as(user::Column::Id, "user_id")
+			.column(user::Column::DisplayName)
+			.column_as(theme_song::Column::Id.count(), "themes")
+			.join(JoinType::InnerJoin, theme_song::Relation::User.def())
+			.group_by(user::Column::Id)
+			.order_by_desc(Expr::cust("themes"))
+			.order_by_asc(user::Column::DisplayName)
+			.limit(TOP_CONTRIBUTORS)
+			.into_model::<Contributor>()
+			.all(&self.db)
+			.await?;
+
+		let this_week = monday(OffsetDateTime::now_utc().date());
+		let first_week = this_week - Duration::weeks(WEEKS - 1);
+		let week = Expr::expr(Func::cust(Alias::new("date_trunc")).args([
+			Expr::val("week").into(),
+			Expr::col(theme_song::Column::Created).into(),
+		]))
+		.cast_as(Alias::new("date"));
+
+		let counts = theme_song::Entity::find()
+			.select_only()
+			.column_as(week, "week")
+			.column_as(theme_song::Column::Id.count(), "themes")
+			.filter(theme_song::Column::Created.gte(first_week.midnight()))
+			// grouped by the output column, as the bound `week` argument would
+			// otherwise differ from the selected expression
+			.group_by(Expr::cust("week"))
+			.into_model::<WeekCountDb>()
+			.all(&self.db)
+			.await?;
+
+		let themes_per_week = (0..WEEKS)
+			.map(|i| {
+				let week = first_week + Duration::weeks(i);
+				let themes = counts
+					.iter()
+					.find(|c| c.week == week)
+					.map_or(0, |c| c.themes);
+
+				WeekCount { week, themes }
+			})
+			.collect();
+
+		Ok(Stats {
+			series,
+			series_with_theme,
+			seasons,
+			seasons_with_theme,
+			themes,
+			coverage_by_genre,
+			coverage_by_year,
+			top_contributors,
+			themes_per_week,
+		})
+	}
+}
+
+/// The monday starting the week of `date`, matching postgres' `date_trunc`.
+fn monday(date: Date) -> Date {
+	date - Duration::days(date.weekday().number_days_from_monday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use time::Month;
+
+	#[test]
+	fn weeks_start_on_monday() {
+		let date = |day| Date::from_calendar_date(2024, Month::May, day).unwrap();
+		assert_eq!(monday(date(16)), date(13));
+		assert_eq!(monday(date(13)), date(13));
+		assert_eq!(monday(date(12)), date(6));
+		assert_eq!(date(13).to_string(), "2024-05-13");
+	}
+}
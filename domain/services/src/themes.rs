@@ -0,0 +1,431 @@
+use crate::macros::define_service;
+use dbost_entities::{season, series, theme_song};
+use dbost_utils::ActiveVersion;
+use sea_orm::{
+	prelude::TimeDateTime, sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition,
+	DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, IntoActiveModel, NotSet,
+	QueryFilter, Set, TransactionError, TransactionTrait,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+define_service! {
+	#[derive(Clone)]
+	pub struct ThemeService {
+		pub db: DatabaseConnection,
+	}
+}
+
+/// What a theme song is added to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeTarget {
+	Series(Uuid),
+	Season { series_id: Uuid, season_id: Uuid },
+}
+
+/// A theme song as submitted by a contributor. The video can be given as a
+/// YouTube url or just its id, and is limited to the `starts_at` - `ends_at`
+/// range (in seconds) when given.
+#[derive(Debug, Clone)]
+pub struct NewTheme {
+	pub name: String,
+	pub youtube: String,
+	pub starts_at: Option<u32>,
+	pub ends_at: Option<u32>,
+}
+
+/// Changes to an existing theme song. Fields left as `None` keep their current
+/// value, `Some(None)` clears the start or end of the video.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeChanges {
+	pub name: Option<String>,
+	pub youtube: Option<String>,
+	pub starts_at: Option<Option<u32>>,
+	pub ends_at: Option<Option<u32>>,
+}
+
+impl From<NewTheme> for ThemeChanges {
+	fn from(value: NewTheme) -> Self {
+		Self {
+			name: Some(value.name),
+			youtube: Some(value.youtube),
+			starts_at: Some(value.starts_at),
+			ends_at: Some(value.ends_at),
+		}
+	}
+}
+
+/// The theme song of a series or season after it was set.
+#[derive(Debug, Clone)]
+pub struct ThemeAssignment {
+	pub theme: Option<theme_song::Model>,
+	/// The new `_version` of the series or season.
+	pub version: TimeDateTime,
+}
+
+/// What a series or season must look like for its theme song to be set.
+#[derive(Debug, Clone)]
+enum Expect {
+	/// It has no theme song yet.
+	NoTheme,
+	/// It is still at one of the given `_version`s, if any.
+	Version(Option<Vec<TimeDateTime>>),
+}
+
+#[derive(Debug, Error)]
+pub enum ThemeServiceError {
+	#[error("theme song name is required")]
+	MissingName,
+
+	#[error("not a YouTube video: {0}")]
+	InvalidVideo(String),
+
+	#[error("theme song must end after it starts ({starts_at}s - {ends_at}s)")]
+	InvalidRange { starts_at: u32, ends_at: u32 },
+
+	#[error("already has a theme song")]
+	AlreadySet,
+
+	#[error("not found")]
+	NotFound,
+
+	#[error("theme song {0} does not exist")]
+	UnknownTheme(Uuid),
+
+	#[error("modified since the given version")]
+	VersionMismatch,
+
+	#[error(transparent)]
+	DbErr(#[from] DbErr),
+}
+
+impl From<TransactionError<ThemeServiceError>> for ThemeServiceError {
+	fn from(value: TransactionError<ThemeServiceError>) -> Self {
+		match value {
+			TransactionError::Connection(e) => e.into(),
+			TransactionError::Transaction(e) => e,
+		}
+	}
+}
+
+/// Gets the video id from a YouTube url (`youtube.com/watch?v=`, `youtu.be/`,
+/// `/embed/` or `/shorts/` links) or a bare video id.
+pub fn parse_youtube_id(value: &str) -> Option<String> {
+	fn is_video_id(id: &str) -> bool {
+		id.len() == 11
+			&& id
+				.bytes()
+				.all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+	}
+
+	let value = value.trim();
+	if is_video_id(value) {
+		return Some(value.to_owned());
+	}
+
+	let url = match url::Url::parse(value) {
+		Ok(url) => url,
+		Err(url::ParseError::RelativeUrlWithoutBase) => {
+			url::Url::parse(&format!("https://{value}")).ok()?
+		}
+		Err(_) => return None,
+	};
+
+	let host = url.host_str()?;
+	let host = host.strip_prefix("www.").unwrap_or(host);
+	let host = host.strip_prefix("m.").unwrap_or(host);
+	let id = match host {
+		"youtu.be" => url.path_segments()?.next().map(str::to_owned),
+		"youtube.com" | "music.youtube.com" | "youtube-nocookie.com" => {
+			let mut segments = url.path_segments()?;
+			match segments.next()? {
+				"watch" => url
+					.query_pairs()
+					.find(|(key, _)| key == "v")
+					.map(|(_, id)| id.into_owned()),
+				"embed" | "shorts" | "live" => segments.next().map(str::to_owned),
+				_ => None,
+			}
+		}
+		_ => None,
+	}?;
+
+	is_video_id(&id).then_some(id)
+}
+
+/// Checks a theme song, returning its trimmed name and YouTube video id.
+fn validate(
+	name: &str,
+	youtube: &str,
+	starts_at: Option<u32>,
+	ends_at: Option<u32>,
+) -> Result<(String, String), ThemeServiceError> {
+	let name = name.trim();
+	if name.is_empty() {
+		return Err(ThemeServiceError::MissingName);
+	}
+
+	let youtube_id = parse_youtube_id(youtube)
+		.ok_or_else(|| ThemeServiceError::InvalidVideo(youtube.trim().to_owned()))?;
+
+	if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at) {
+		if ends_at <= starts_at {
+			return Err(ThemeServiceError::InvalidRange { starts_at, ends_at });
+		}
+	}
+
+	Ok((name.to_owned(), youtube_id))
+}
+
+impl NewTheme {
+	fn into_active_model(
+		self,
+		user_id: Option<Uuid>,
+	) -> Result<theme_song::ActiveModel, ThemeServiceError> {
+		let (name, youtube_id) = validate(&self.name, &self.youtube, self.starts_at, self.ends_at)?;
+
+		Ok(theme_song::ActiveModel {
+			id: Set(Uuid::new_v4()),
+			name: Set(name),
+			youtube_id: Set(Some(youtube_id)),
+			youtube_starts_at: Set(self.starts_at.map(|s| s as i32)),
+			youtube_ends_at: Set(self.ends_at.map(|s| s as i32)),
+			version: ActiveVersion::now(),
+			user_id: Set(user_id),
+			created: NotSet,
+		})
+	}
+}
+
+impl ThemeService {
+	pub async fn get_theme(&self, id: Uuid) -> Result<Option<theme_song::Model>, DbErr> {
+		theme_song::Entity::find_by_id(id).one(&self.db).await
+	}
+
+	/// Creates a theme song that isn't used by anything yet, credited to
+	/// `user_id`.
+	pub async fn create_theme(
+		&self,
+		theme: NewTheme,
+		user_id: Option<Uuid>,
+	) -> Result<theme_song::Model, ThemeServiceError> {
+		let theme = theme.into_active_model(user_id)?;
+		Ok(theme.insert(&self.db).await?)
+	}
+
+	/// Creates a theme song and sets it on a series or season that doesn't have
+	/// one yet, so contributors filling the same gap don't overwrite each other.
+	/// The theme is credited to `user_id`.
+	pub async fn add_theme(
+		&self,
+		target: ThemeTarget,
+		theme: NewTheme,
+		user_id: Option<Uuid>,
+	) -> Result<theme_song::Model, ThemeServiceError> {
+		let theme = theme.into_active_model(user_id)?;
+
+		let theme = self
+			.db
+			.transaction(|tx| {
+				Box::pin(async move {
+					let theme = theme.insert(tx).await?;
+					set_theme_db(tx, target, Some(theme.id), Expect::NoTheme).await?;
+					Ok(theme)
+				})
+			})
+			.await?;
+
+		Ok(theme)
+	}
+
+	/// Updates a theme song, if it is still at one of `if_versions` when given.
+	pub async fn update_theme(
+		&self,
+		id: Uuid,
+		changes: ThemeChanges,
+		if_versions: Option<Vec<TimeDateTime>>,
+	) -> Result<theme_song::Model, ThemeServiceError> {
+		let current = self
+			.get_theme(id)
+			.await?
+			.ok_or(ThemeServiceError::NotFound)?;
+		if if_versions.is_some_and(|v| !v.contains(&current.version)) {
+			return Err(ThemeServiceError::VersionMismatch);
+		}
+
+		let starts_at = changes
+			.starts_at
+			.unwrap_or(current.youtube_starts_at.map(|s| s as u32));
+		let ends_at = changes
+			.ends_at
+			.unwrap_or(current.youtube_ends_at.map(|s| s as u32));
+		let name = changes.name.as_deref().unwrap_or(&current.name);
+		let youtube = changes
+			.youtube
+			.as_deref()
+			.or(current.youtube_id.as_deref())
+			.unwrap_or_default();
+		let (name, youtube_id) = validate(name, youtube, starts_at, ends_at)?;
+
+		let version = current.version;
+		let mut theme = current.into_active_model();
+		theme.name = Set(name);
+		theme.youtube_id = Set(Some(youtube_id));
+		theme.youtube_starts_at = Set(starts_at.map(|s| s as i32));
+		theme.youtube_ends_at = Set(ends_at.map(|s| s as i32));
+		theme.version = ActiveVersion::now();
+
+		// only update the version that was checked above, in case someone else
+		// got in between
+		let result = theme_song::Entity::update_many()
+			.set(theme)
+			.filter(theme_song::Column::Id.eq(id))
+			.filter(theme_song::Column::Version.eq(version))
+			.exec_with_returning(&self.db)
+			.await?;
+
+		result
+			.into_iter()
+			.next()
+			.ok_or(ThemeServiceError::VersionMismatch)
+	}
+
+	/// Sets (or with `None`, removes) the theme song of a series or season,
+	/// whether it had one before or not, if it is still at one of
+	/// `if_versions` when given.
+	pub async fn set_theme(
+		&self,
+		target: ThemeTarget,
+		theme_id: Option<Uuid>,
+		if_versions: Option<Vec<TimeDateTime>>,
+	) -> Result<ThemeAssignment, ThemeServiceError> {
+		let assignment = self
+			.db
+			.transaction(|tx| {
+				Box::pin(async move {
+					let theme = match theme_id {
+						None => None,
+						Some(id) => Some(
+							theme_song::Entity::find_by_id(id)
+								.one(tx)
+								.await?
+								.ok_or(ThemeServiceError::UnknownTheme(id))?,
+						),
+					};
+
+					let version = set_theme_db(tx, target, theme_id, Expect::Version(if_versions)).await?;
+					Ok(ThemeAssignment { theme, version })
+				})
+			})
+			.await?;
+
+		Ok(assignment)
+	}
+}
+
+/// Sets the theme song of a series or season as long as it matches `expect`,
+/// returning its new `_version`.
+async fn set_theme_db(
+	tx: &DatabaseTransaction,
+	target: ThemeTarget,
+	theme_id: Option<Uuid>,
+	expect: Expect,
+) -> Result<TimeDateTime, ThemeServiceError> {
+	let now = ActiveVersion::now().unwrap();
+	let (updated, exists) = match target {
+		ThemeTarget::Series(series_id) => {
+			let expected = match &expect {
+				Expect::NoTheme => Condition::all().add(series::Column::ThemeSongId.is_null()),
+				Expect::Version(Some(versions)) => {
+					Condition::all().add(series::Column::Version.is_in(versions.iter().copied()))
+				}
+				Expect::Version(None) => Condition::all(),
+			};
+
+			let result = series::Entity::update_many()
+				.col_expr(series::Column::ThemeSongId, Expr::value(theme_id))
+				.col_expr(series::Column::Version, Expr::value(now))
+				.filter(series::Column::Id.eq(series_id))
+				.filter(expected)
+				.exec(tx)
+				.await?;
+
+			let exists = result.rows_affected == 0
+				&& series::Entity::find_by_id(series_id)
+					.one(tx)
+					.await?
+					.is_some();
+
+			(result.rows_affected > 0, exists)
+		}
+
+		ThemeTarget::Season {
+			series_id,
+			season_id,
+		} => {
+			let expected = match &expect {
+				Expect::NoTheme => Condition::all().add(season::Column::ThemeSongId.is_null()),
+				Expect::Version(Some(versions)) => {
+					Condition::all().add(season::Column::Version.is_in(versions.iter().copied()))
+				}
+				Expect::Version(None) => Condition::all(),
+			};
+
+			let result = season::Entity::update_many()
+				.col_expr(season::Column::ThemeSongId, Expr::value(theme_id))
+				.col_expr(season::Column::Version, Expr::value(now))
+				.filter(season::Column::Id.eq(season_id))
+				.filter(season::Column::SeriesId.eq(series_id))
+				.filter(expected)
+				.exec(tx)
+				.await?;
+
+			let exists = result.rows_affected == 0
+				&& season::Entity::find_by_id(season_id)
+					.filter(season::Column::SeriesId.eq(series_id))
+					.one(tx)
+					.await?
+					.is_some();
+
+			(result.rows_affected > 0, exists)
+		}
+	};
+
+	match (updated, exists, expect) {
+		(true, _, _) => Ok(now),
+		(false, true, Expect::NoTheme) => Err(ThemeServiceError::AlreadySet),
+		(false, true, Expect::Version(_)) => Err(ThemeServiceError::VersionMismatch),
+		(false, false, _) => Err(ThemeServiceError::NotFound),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn youtube_ids() {
+		let id = Some("dQw4w9WgXcQ".to_owned());
+		assert_eq!(parse_youtube_id("dQw4w9WgXcQ"), id);
+		assert_eq!(
+			parse_youtube_id(" https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42 "),
+			id
+		);
+		assert_eq!(
+			parse_youtube_id("youtube.com/watch?list=x&v=dQw4w9WgXcQ"),
+			id
+		);
+		assert_eq!(parse_youtube_id("https://youtu.be/dQw4w9WgXcQ?si=abc"), id);
+		assert_eq!(
+			parse_youtube_id("https://m.youtube.com/shorts/dQw4w9WgXcQ"),
+			id
+		);
+		assert_eq!(
+			parse_youtube_id("https://www.youtube.com/embed/dQw4w9WgXcQ"),
+			id
+		);
+		assert_eq!(parse_youtube_id("https://vimeo.com/dQw4w9WgXcQ"), None);
+		assert_eq!(parse_youtube_id("https://youtube.com/watch?v=short"), None);
+		assert_eq!(parse_youtube_id("not a video"), None);
+	}
+}
@@ -20,6 +20,10 @@ struct Args {
 	/// Tag for the new images
 	#[arg(short, long, env = "TAG")]
 	tag: String,
+
+	/// EFS file system to cache images on
+	#[arg(long, env = "IMAGE_CACHE_FILE_SYSTEM")]
+	image_cache_file_system: String,
 }
 
 #[tokio::main]
@@ -59,7 +63,7 @@ async fn _main() -> Result<()> {
 	client
 		.update_service(
 			"arn:aws:ecs:eu-north-1:412850343551:service/dbost-cluster/dbost",
-			tasks::dbost_service,
+			|builder| tasks::dbost_service(builder, &args.image_cache_file_system),
 			&args.tag,
 		)
 		.await
@@ -35,7 +35,6 @@ impl Secrets {
 			.secrets
 			.get(name)
 			.ok_or_else(|| format_err!("secret {name} not found"))
-			.map_err(Into::into)
 	}
 }
 
@@ -2,7 +2,8 @@ use aws_sdk_ecs::{
 	config::Region,
 	operation::register_task_definition::builders::RegisterTaskDefinitionFluentBuilder,
 	types::{
-		ContainerDefinition as AwsContainerDefinition, KeyValuePair, LogConfiguration, PortMapping,
+		ContainerDefinition as AwsContainerDefinition, EfsTransitEncryption, EfsVolumeConfiguration,
+		KeyValuePair, LogConfiguration, MountPoint as AwsMountPoint, PortMapping, Volume,
 	},
 	types::{HealthCheck, LogDriver},
 };
@@ -56,6 +57,12 @@ pub struct Port {
 	pub name: &'static str,
 }
 
+#[derive(Clone, Copy)]
+pub struct MountPoint {
+	pub volume: &'static str,
+	pub path: &'static str,
+}
+
 #[derive(Clone, Copy)]
 pub struct ContainerDependency {
 	pub container: &'static str,
@@ -68,6 +75,7 @@ pub struct ContainerDefinition<
 	const SECRET: usize,
 	const PORT: usize,
 	const DEP: usize,
+	const MOUNT: usize,
 > {
 	pub name: &'static str,
 	pub image: &'static str,
@@ -75,6 +83,7 @@ pub struct ContainerDefinition<
 	pub ro_fs: bool,
 	pub memory: u16,
 	pub ports: [Port; PORT],
+	pub mounts: [MountPoint; MOUNT],
 	pub health_check: Option<&'static str>, // uri to curl
 	pub depends_on: [ContainerDependency; DEP],
 	pub env: [EnvironmentVariable; ENV],
@@ -82,8 +91,13 @@ pub struct ContainerDefinition<
 	pub log_prefix: &'static str,
 }
 
-impl<const ENV: usize, const SECRET: usize, const PORT: usize, const DEP: usize>
-	ContainerDefinition<ENV, SECRET, PORT, DEP>
+impl<
+		const ENV: usize,
+		const SECRET: usize,
+		const PORT: usize,
+		const DEP: usize,
+		const MOUNT: usize,
+	> ContainerDefinition<ENV, SECRET, PORT, DEP, MOUNT>
 {
 	pub fn success(&self) -> ContainerDependency {
 		ContainerDependency {
@@ -146,9 +160,36 @@ impl<'a> TaskDefinitionBuilder<'a> {
 		}
 	}
 
-	pub fn container<const ENV: usize, const SECRET: usize, const PORT: usize, const DEP: usize>(
+	/// Adds a volume on the given EFS file system, which outlives the tasks
+	/// that mount it.
+	pub fn efs_volume(self, name: &'static str, file_system_id: &str) -> Self {
+		let volume = Volume::builder()
+			.name(name)
+			.efs_volume_configuration(
+				EfsVolumeConfiguration::builder()
+					.file_system_id(file_system_id)
+					.transit_encryption(EfsTransitEncryption::Enabled)
+					.build(),
+			)
+			.build();
+
+		Self {
+			region: self.region,
+			tag: self.tag,
+			secrets: self.secrets,
+			builder: self.builder.volumes(volume),
+		}
+	}
+
+	pub fn container<
+		const ENV: usize,
+		const SECRET: usize,
+		const PORT: usize,
+		const DEP: usize,
+		const MOUNT: usize,
+	>(
 		self,
-		def: ContainerDefinition<ENV, SECRET, PORT, DEP>,
+		def: ContainerDefinition<ENV, SECRET, PORT, DEP, MOUNT>,
 	) -> Result<Self> {
 		let tag = self.tag;
 		let ContainerDefinition {
@@ -158,6 +199,7 @@ impl<'a> TaskDefinitionBuilder<'a> {
 			ro_fs,
 			memory,
 			ports,
+			mounts,
 			health_check,
 			depends_on,
 			env,
@@ -218,6 +260,16 @@ impl<'a> TaskDefinitionBuilder<'a> {
 			);
 		}
 
+		for MountPoint { volume, path } in mounts {
+			builder = builder.mount_points(
+				AwsMountPoint::builder()
+					.source_volume(volume)
+					.container_path(path)
+					.read_only(false)
+					.build(),
+			);
+		}
+
 		for EnvironmentVariable { name, value } in env {
 			builder = builder.environment(KeyValuePair::builder().name(name).value(value).build());
 		}
@@ -1,5 +1,5 @@
 use super::builder::{
-	ApplicationProtocol, ContainerDefinition, EnvironmentVariable, Port, Secret,
+	ApplicationProtocol, ContainerDefinition, EnvironmentVariable, MountPoint, Port, Secret,
 	TaskDefinitionBuilder,
 };
 use color_eyre::eyre::{Context, Result};
@@ -14,7 +14,17 @@ const RUST_LOG: EnvironmentVariable = EnvironmentVariable {
 	value: "INFO",
 };
 
-pub fn dbost_service(builder: TaskDefinitionBuilder) -> Result<TaskDefinitionBuilder> {
+/// The volume resized images are cached on, so they survive deploys and
+/// restarts.
+const IMAGE_CACHE: MountPoint = MountPoint {
+	volume: "image-cache",
+	path: "/var/cache/dbost/images",
+};
+
+pub fn dbost_service<'a>(
+	builder: TaskDefinitionBuilder<'a>,
+	image_cache_file_system: &str,
+) -> Result<TaskDefinitionBuilder<'a>> {
 	let migrator = ContainerDefinition {
 		name: "dbost-db-migrator",
 		image: "ghcr.io/alxandr/dbost/migrator",
@@ -22,6 +32,7 @@ pub fn dbost_service(builder: TaskDefinitionBuilder) -> Result<TaskDefinitionBui
 		ro_fs: true,
 		memory: 1024,
 		ports: [],
+		mounts: [],
 		health_check: None,
 		depends_on: [],
 		env: [DB_SCHEMA, RUST_LOG],
@@ -44,6 +55,7 @@ pub fn dbost_service(builder: TaskDefinitionBuilder) -> Result<TaskDefinitionBui
 			container_port: 80,
 			name: "www",
 		}],
+		mounts: [IMAGE_CACHE],
 		health_check: Some("http://localhost:80/healthz"),
 		depends_on: [migrator.success()],
 		env: [
@@ -61,6 +73,14 @@ pub fn dbost_service(builder: TaskDefinitionBuilder) -> Result<TaskDefinitionBui
 				name: "PORT",
 				value: "80",
 			},
+			EnvironmentVariable {
+				name: "TRUST_FORWARDED_FOR",
+				value: "true",
+			},
+			EnvironmentVariable {
+				name: "IMAGE_CACHE_PATH",
+				value: IMAGE_CACHE.path,
+			},
 		],
 		secrets: [
 			Secret {
@@ -78,11 +98,6 @@ pub fn dbost_service(builder: TaskDefinitionBuilder) -> Result<TaskDefinitionBui
 				secret: "dbost_web",
 				field: "csrf_key",
 			},
-			Secret {
-				name: "API_KEY",
-				secret: "dbost_web",
-				field: "api_key",
-			},
 			Secret {
 				name: "GITHUB_CLIENT_ID",
 				secret: "dbost_web",
@@ -111,6 +126,7 @@ pub fn dbost_service(builder: TaskDefinitionBuilder) -> Result<TaskDefinitionBui
 		.family("dbost")
 		.cpu("512")
 		.memory("1024")
+		.efs_volume(IMAGE_CACHE.volume, image_cache_file_system)
 		.container(migrator)
 		.wrap_err("building task definition 'dbost'")?
 		.container(dbost)
@@ -125,6 +141,7 @@ pub fn dbost_cron(builder: TaskDefinitionBuilder) -> Result<TaskDefinitionBuilde
 		ro_fs: true,
 		memory: 1024,
 		ports: [],
+		mounts: [],
 		health_check: None,
 		depends_on: [],
 		env: [DB_SCHEMA, RUST_LOG],
@@ -25,6 +25,7 @@ use std::{
 		Arc,
 	},
 };
+use subtle::ConstantTimeEq;
 use thiserror::Error;
 use time::Duration;
 use tower_layer::Layer;
@@ -72,6 +73,28 @@ impl AsRef<str> for CsrfToken {
 	}
 }
 
+impl CsrfToken {
+	/// Checks a token posted back by a form against this request's token.
+	pub fn verify(&self, token: &str) -> bool {
+		self.inner.as_bytes().ct_eq(token.as_bytes()).into()
+	}
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CsrfToken {
+	type Rejection = Infallible;
+
+	async fn from_request_parts(
+		parts: &mut request::Parts,
+		_state: &S,
+	) -> Result<Self, Self::Rejection> {
+		parts.extensions.get().cloned().ok_or_else(|| {
+			error!("csrf token not found in request extensions");
+			unreachable!()
+		})
+	}
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for Session {
 	type Rejection = Infallible;
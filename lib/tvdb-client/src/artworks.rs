@@ -20,6 +20,17 @@ macro_rules! artworks {
 			)*
 		}
 
+		impl $enum_name {
+			pub fn name(self) -> &'static str {
+				match self {
+					Self::Unknown => "Unknown",
+					$(
+						Self::$variant => stringify!($variant),
+					)*
+				}
+			}
+		}
+
 		impl From<u8> for $enum_name {
 			fn from(value: u8) -> Self {
 				match value {
@@ -18,7 +18,9 @@ use tracing::{debug, info, instrument};
 
 use crate::TvDbUrl;
 
+#[derive(Default)]
 enum ApiToken {
+	#[default]
 	Missing,
 	Present {
 		token: HeaderValue,
@@ -31,12 +33,6 @@ enum ApiToken {
 	Poisoned,
 }
 
-impl Default for ApiToken {
-	fn default() -> Self {
-		Self::Missing
-	}
-}
-
 impl ApiToken {
 	// tokens last for 1 month - knock down to 20 days to have good margin
 	const DURATION: Duration = Duration::from_secs(60 * 60 * 24 * 20);
@@ -0,0 +1,71 @@
+use std::{fmt, str::FromStr};
+
+/// The other databases TVDB links series to that we keep track of, so that
+/// clients can find series by the ids they already have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExternalSource {
+	Imdb,
+	Tmdb,
+	AniDb,
+	MyAnimeList,
+}
+
+impl ExternalSource {
+	pub const ALL: [ExternalSource; 4] = [Self::Imdb, Self::Tmdb, Self::AniDb, Self::MyAnimeList];
+
+	/// The key we store and use in urls for the source.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Imdb => "imdb",
+			Self::Tmdb => "tmdb",
+			Self::AniDb => "anidb",
+			Self::MyAnimeList => "mal",
+		}
+	}
+
+	/// Finds the source of a TVDB remote id from its `sourceName`.
+	pub(crate) fn from_tvdb_name(name: &str) -> Option<Self> {
+		match &*name.trim().to_ascii_lowercase() {
+			"imdb" => Some(Self::Imdb),
+			"themoviedb.com" | "tmdb" => Some(Self::Tmdb),
+			"anidb" => Some(Self::AniDb),
+			"myanimelist" | "mal" => Some(Self::MyAnimeList),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for ExternalSource {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnknownExternalSource;
+
+impl fmt::Display for UnknownExternalSource {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("unknown external id source")
+	}
+}
+
+impl std::error::Error for UnknownExternalSource {}
+
+impl FromStr for ExternalSource {
+	type Err = UnknownExternalSource;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|t| t.as_str() == s)
+			.ok_or(UnknownExternalSource)
+	}
+}
+
+/// The id of a series in another database.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExternalId {
+	pub source: ExternalSource,
+	pub id: String,
+}
@@ -10,6 +10,9 @@ use tracing::{info, info_span, Instrument};
 
 mod artworks;
 mod auth;
+mod external_ids;
+mod movies;
+mod season_types;
 mod series;
 
 pub static PKG_NAME: &str = env!("CARGO_PKG_NAME");
@@ -17,7 +20,11 @@ pub static PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
 
-pub use series::{Season, Series};
+pub use artworks::ArtworkKind;
+pub use external_ids::{ExternalId, ExternalSource, UnknownExternalSource};
+pub use movies::Movie;
+pub use season_types::{SeasonType, UnknownSeasonType};
+pub use series::{Alias, Artwork, Episode, Genre, Network, Season, Series};
 
 #[derive(Error, Debug)]
 pub enum TvDbError {
@@ -38,6 +45,13 @@ enum TvDbUrl {
 	Login,
 	Series(u64),
 	Season(u64),
+	Movie(u64),
+	SeriesEpisodes {
+		id: u64,
+		season_type: SeasonType,
+		language: Option<&'static str>,
+		page: u32,
+	},
 }
 
 impl TvDbUrl {
@@ -46,6 +60,19 @@ impl TvDbUrl {
 			Self::Login => "login".to_owned(),
 			Self::Series(id) => format!("series/{id}/extended?meta=translations"),
 			Self::Season(id) => format!("seasons/{id}/extended?meta=translations"),
+			Self::Movie(id) => format!("movies/{id}/extended?meta=translations"),
+			Self::SeriesEpisodes {
+				id,
+				season_type,
+				language: Some(language),
+				page,
+			} => format!("series/{id}/episodes/{season_type}/{language}?page={page}"),
+			Self::SeriesEpisodes {
+				id,
+				season_type,
+				language: None,
+				page,
+			} => format!("series/{id}/episodes/{season_type}?page={page}"),
 		};
 
 		reqwest::Url::parse(&format!("https://api4.thetvdb.com/v4/{path}")).unwrap()
@@ -82,6 +109,10 @@ impl TvDbClient {
 	pub async fn get_series(&self, id: u64) -> Result<Option<series::Series>, TvDbError> {
 		series::get_series(id, self).await
 	}
+
+	pub async fn get_movie(&self, id: u64) -> Result<Option<movies::Movie>, TvDbError> {
+		movies::get_movie(id, self).await
+	}
 }
 
 struct TracingMiddleware;
@@ -0,0 +1,60 @@
+use crate::{
+	artworks::ArtworkKind,
+	series::{get_image, nullable_vec, ArtworkDto, ResponseExt, ResultDto, TranslationsDto},
+	TvDbClient, TvDbError, TvDbUrl,
+};
+use serde::Deserialize;
+use tracing::{info, instrument};
+
+#[derive(Deserialize, Debug)]
+struct MovieDto {
+	id: u64,
+	name: String,
+	#[serde(default)]
+	overview: Option<String>,
+	#[serde(default)]
+	image: Option<String>,
+	#[serde(default)]
+	year: Option<String>,
+	/// Only used to pick a poster when `image` is missing.
+	#[serde(default, deserialize_with = "nullable_vec", alias = "artwork")]
+	artworks: Vec<ArtworkDto>,
+	#[serde(default)]
+	translations: TranslationsDto,
+}
+
+pub struct Movie {
+	pub id: u64,
+	pub name: String,
+	pub description: Option<String>,
+	pub year: Option<u16>,
+	pub image: Option<String>,
+}
+
+#[instrument(skip(client))]
+pub(crate) async fn get_movie(id: u64, client: &TvDbClient) -> Result<Option<Movie>, TvDbError> {
+	let url = TvDbUrl::Movie(id).into_url();
+	info!(url = %url, id = %id, "fetching tvdb movie");
+	let response = client.client.get(url).send().await?;
+
+	let response = match response.status() {
+		reqwest::StatusCode::NOT_FOUND => return Ok(None),
+		_ => response.if_ok().await?,
+	};
+
+	let mut movie = response.json::<ResultDto<MovieDto>>().await?.data;
+	let image = get_image(movie.image, &movie.artworks, ArtworkKind::MoviePoster);
+	let name = movie.translations.take_english_name().unwrap_or(movie.name);
+	let description = movie
+		.translations
+		.take_english_overview()
+		.or(movie.overview);
+
+	Ok(Some(Movie {
+		id: movie.id,
+		name,
+		description,
+		year: movie.year.and_then(|y| y.parse().ok()),
+		image,
+	}))
+}
@@ -0,0 +1,94 @@
+use serde::Deserialize;
+use std::{fmt, str::FromStr};
+
+/// The orderings TVDB can list the seasons of a series in. A series always
+/// has the official (aired) ordering, the others are optional.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SeasonType {
+	#[default]
+	Official,
+	Dvd,
+	Absolute,
+	Alternate,
+	Regional,
+	AltDvd,
+	AltTwo,
+}
+
+impl SeasonType {
+	pub const ALL: [SeasonType; 7] = [
+		Self::Official,
+		Self::Dvd,
+		Self::Absolute,
+		Self::Alternate,
+		Self::Regional,
+		Self::AltDvd,
+		Self::AltTwo,
+	];
+
+	/// The key TVDB uses for the season type, which is also what we store.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Official => "official",
+			Self::Dvd => "dvd",
+			Self::Absolute => "absolute",
+			Self::Alternate => "alternate",
+			Self::Regional => "regional",
+			Self::AltDvd => "altdvd",
+			Self::AltTwo => "alttwo",
+		}
+	}
+
+	/// Human readable name of the ordering.
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Official => "Aired Order",
+			Self::Dvd => "DVD Order",
+			Self::Absolute => "Absolute Order",
+			Self::Alternate => "Alternate Order",
+			Self::Regional => "Regional Order",
+			Self::AltDvd => "Alternate DVD Order",
+			Self::AltTwo => "Third Order",
+		}
+	}
+}
+
+impl fmt::Display for SeasonType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnknownSeasonType;
+
+impl fmt::Display for UnknownSeasonType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("unknown season type")
+	}
+}
+
+impl std::error::Error for UnknownSeasonType {}
+
+impl FromStr for SeasonType {
+	type Err = UnknownSeasonType;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|t| t.as_str() == s)
+			.ok_or(UnknownSeasonType)
+	}
+}
+
+/// Deserializes unknown season types as `None`, so that new orderings added
+/// by TVDB are skipped instead of failing the whole series.
+pub(crate) fn deserialize_season_type<'de, D>(
+	deserializer: D,
+) -> Result<Option<SeasonType>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	let value = String::deserialize(deserializer)?;
+	Ok(value.parse().ok())
+}
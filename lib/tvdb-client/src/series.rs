@@ -1,8 +1,15 @@
-use crate::{artworks::ArtworkKind, TvDbClient, TvDbError, TvDbUrl};
+use crate::{
+	artworks::ArtworkKind,
+	external_ids::{ExternalId, ExternalSource},
+	season_types::{deserialize_season_type, SeasonType},
+	TvDbClient, TvDbError, TvDbUrl,
+};
 use async_trait::async_trait;
 use futures::{stream::FuturesUnordered, StreamExt};
 use reqwest::Response;
 use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use time::{macros::format_description, Date};
 use tracing::{error, info, info_span, instrument, Instrument};
 
 #[derive(Deserialize, Debug)]
@@ -13,6 +20,22 @@ struct SeriesDto {
 	overview: Option<String>,
 	#[serde(default)]
 	image: Option<String>,
+	#[serde(rename = "originalLanguage", default)]
+	original_language: Option<String>,
+	#[serde(rename = "firstAired", default)]
+	first_aired: Option<String>,
+	#[serde(default)]
+	year: Option<String>,
+	#[serde(default)]
+	status: Option<StatusDto>,
+	#[serde(rename = "originalNetwork", default)]
+	original_network: Option<NetworkDto>,
+	#[serde(default, deserialize_with = "nullable_vec")]
+	genres: Vec<GenreDto>,
+	#[serde(default, deserialize_with = "nullable_vec")]
+	aliases: Vec<AliasDto>,
+	#[serde(rename = "remoteIds", default, deserialize_with = "nullable_vec")]
+	remote_ids: Vec<RemoteIdDto>,
 	seasons: Vec<SeriesSeasonDto>,
 	#[serde(default, deserialize_with = "nullable_vec", alias = "artwork")]
 	artworks: Vec<ArtworkDto>,
@@ -20,17 +43,59 @@ struct SeriesDto {
 }
 
 #[derive(Deserialize, Debug)]
-struct ArtworkDto {
+pub(crate) struct ArtworkDto {
+	#[serde(default)]
+	id: Option<u64>,
 	#[serde(rename = "type")]
 	kind: ArtworkKind,
 	#[serde(default)]
 	image: Option<String>,
-	// #[serde(default)]
-	// thumbnail: Option<String>,
-	// #[serde(default)]
-	// language: Option<String>,
+	#[serde(default)]
+	thumbnail: Option<String>,
+	#[serde(default)]
+	language: Option<String>,
 	#[serde(default)]
 	score: u32,
+	#[serde(default)]
+	width: Option<u32>,
+	#[serde(default)]
+	height: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StatusDto {
+	#[serde(default)]
+	name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NetworkDto {
+	id: u64,
+	name: String,
+	#[serde(default)]
+	slug: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GenreDto {
+	id: u64,
+	name: String,
+	#[serde(default)]
+	slug: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AliasDto {
+	#[serde(default)]
+	language: Option<String>,
+	name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RemoteIdDto {
+	id: String,
+	#[serde(rename = "sourceName", default)]
+	source_name: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -56,7 +121,7 @@ struct SeasonDto {
 }
 
 #[derive(Deserialize, Default, Debug)]
-struct TranslationsDto {
+pub(crate) struct TranslationsDto {
 	#[serde(rename = "nameTranslations", default)]
 	name_translations: Option<Vec<NameTranslationDto>>,
 
@@ -64,6 +129,30 @@ struct TranslationsDto {
 	overview_translations: Option<Vec<OverviewTranslationDto>>,
 }
 
+impl TranslationsDto {
+	/// Takes the english name, if there is one.
+	pub(crate) fn take_english_name(&mut self) -> Option<String> {
+		self
+			.name_translations
+			.take()
+			.into_iter()
+			.flatten()
+			.find(|t| t.language == "eng")
+			.map(|t| t.name)
+	}
+
+	/// Takes the english overview, if there is one.
+	pub(crate) fn take_english_overview(&mut self) -> Option<String> {
+		self
+			.overview_translations
+			.take()
+			.into_iter()
+			.flatten()
+			.find(|t| t.language == "eng")
+			.map(|t| t.overview)
+	}
+}
+
 #[derive(Deserialize, Debug)]
 struct NameTranslationDto {
 	language: String,
@@ -80,13 +169,47 @@ struct OverviewTranslationDto {
 struct SeasonTypeDto {
 	// id: u64,
 	// name: String,
-	#[serde(rename = "type")]
-	ty: String,
+	#[serde(rename = "type", deserialize_with = "deserialize_season_type")]
+	ty: Option<SeasonType>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpisodesDto {
+	#[serde(default, deserialize_with = "nullable_vec")]
+	episodes: Vec<EpisodeDto>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpisodeDto {
+	id: u64,
+	#[serde(rename = "seasonNumber", default)]
+	season_number: Option<u16>,
+	#[serde(default)]
+	number: Option<u32>,
+	#[serde(rename = "absoluteNumber", default)]
+	absolute_number: Option<u32>,
+	#[serde(default)]
+	name: Option<String>,
+	#[serde(default)]
+	aired: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ResultDto<T> {
+	pub(crate) data: T,
 }
 
 #[derive(Deserialize)]
-struct ResultDto<T> {
+struct PagedResultDto<T> {
 	data: T,
+	#[serde(default)]
+	links: Option<LinksDto>,
+}
+
+#[derive(Deserialize)]
+struct LinksDto {
+	#[serde(default)]
+	next: Option<String>,
 }
 
 pub struct Series {
@@ -94,15 +217,70 @@ pub struct Series {
 	pub name: String,
 	pub description: Option<String>,
 	pub image: Option<String>,
+	/// The year the series first aired.
+	pub year: Option<u16>,
+	/// The airing status, like `Continuing` or `Ended`.
+	pub status: Option<String>,
+	pub original_network: Option<Network>,
+	pub original_language: Option<String>,
+	pub genres: Vec<Genre>,
+	pub aliases: Vec<Alias>,
+	/// Ids of the series in the other databases we know of.
+	pub external_ids: Vec<ExternalId>,
+	pub artworks: Vec<Artwork>,
 	pub seasons: Vec<Season>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Network {
+	pub id: u64,
+	pub name: String,
+	pub slug: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Genre {
+	pub id: u64,
+	pub name: String,
+	pub slug: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Alias {
+	pub language: Option<String>,
+	pub name: String,
+}
+
 pub struct Season {
 	pub id: u64,
+	pub season_type: SeasonType,
 	pub number: u16,
 	pub name: Option<String>,
 	pub description: Option<String>,
 	pub image: Option<String>,
+	pub artworks: Vec<Artwork>,
+	pub episodes: Vec<Episode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Episode {
+	pub id: u64,
+	pub number: u32,
+	pub absolute_number: Option<u32>,
+	pub name: Option<String>,
+	pub aired: Option<Date>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Artwork {
+	pub id: u64,
+	pub kind: ArtworkKind,
+	pub url: String,
+	pub thumbnail: Option<String>,
+	pub language: Option<String>,
+	pub score: u32,
+	pub width: Option<u32>,
+	pub height: Option<u32>,
 }
 
 async fn check_respons_status(response: Response) -> Result<Response, TvDbError> {
@@ -118,7 +296,7 @@ async fn check_respons_status(response: Response) -> Result<Response, TvDbError>
 }
 
 #[async_trait]
-trait ResponseExt {
+pub(crate) trait ResponseExt {
 	async fn if_ok(self) -> Result<Response, TvDbError>;
 }
 
@@ -129,7 +307,11 @@ impl ResponseExt for Response {
 	}
 }
 
-async fn get_season(season: SeriesSeasonDto, client: &TvDbClient) -> Result<Season, TvDbError> {
+async fn get_season(
+	season: SeriesSeasonDto,
+	season_type: SeasonType,
+	client: &TvDbClient,
+) -> Result<Season, TvDbError> {
 	let id = season.id;
 	let number = season.number;
 
@@ -177,23 +359,87 @@ async fn get_season(season: SeriesSeasonDto, client: &TvDbClient) -> Result<Seas
 		})
 		.or(season.overview);
 
-	let image = get_image(season.image, season.artworks, ArtworkKind::SeasonPoster);
+	let image = get_image(season.image, &season.artworks, ArtworkKind::SeasonPoster);
+	let artworks = get_artworks(season.artworks);
 	Ok(Season {
 		id,
+		season_type,
 		number,
 		name,
 		description: overview,
 		image,
+		artworks,
+		episodes: Vec::new(),
 	})
 }
 
-fn get_image(
+/// Fetches all the episodes of a series in the given ordering, grouped by
+/// season number.
+async fn get_episodes(
+	series_id: u64,
+	season_type: SeasonType,
+	client: &TvDbClient,
+) -> Result<BTreeMap<u16, Vec<Episode>>, TvDbError> {
+	// series without any english translations 404 on the translated endpoint
+	let mut language = Some("eng");
+	let mut page = 0;
+	let mut episodes = BTreeMap::<u16, Vec<Episode>>::new();
+	loop {
+		let url = TvDbUrl::SeriesEpisodes {
+			id: series_id,
+			season_type,
+			language,
+			page,
+		}
+		.into_url();
+
+		let response = client.client.get(url).send().await?;
+		let response = match response.status() {
+			reqwest::StatusCode::NOT_FOUND if language.is_some() => {
+				language = None;
+				continue;
+			}
+			_ => response.if_ok().await?,
+		};
+
+		let result = response.json::<PagedResultDto<EpisodesDto>>().await?;
+		for episode in result.data.episodes {
+			let (season_number, number) = match (episode.season_number, episode.number) {
+				(Some(season_number), Some(number)) => (season_number, number),
+				_ => continue,
+			};
+
+			episodes.entry(season_number).or_default().push(Episode {
+				id: episode.id,
+				number,
+				absolute_number: episode.absolute_number.filter(|n| *n > 0),
+				name: episode.name.filter(|n| !n.trim().is_empty()),
+				aired: episode
+					.aired
+					.and_then(|a| Date::parse(&a, format_description!("[year]-[month]-[day]")).ok()),
+			});
+		}
+
+		match result.links.and_then(|l| l.next) {
+			Some(_) => page += 1,
+			None => break,
+		}
+	}
+
+	for episodes in episodes.values_mut() {
+		episodes.sort_by_key(|e| e.number);
+	}
+
+	Ok(episodes)
+}
+
+pub(crate) fn get_image(
 	image: Option<String>,
-	artworks: Vec<ArtworkDto>,
+	artworks: &[ArtworkDto],
 	kind: ArtworkKind,
 ) -> Option<String> {
 	let mut artworks = artworks
-		.into_iter()
+		.iter()
 		.filter(|a| a.image.is_some())
 		.filter(|a| a.kind == kind);
 
@@ -210,7 +456,26 @@ fn get_image(
 		}
 	}
 
-	max_score.image
+	max_score.image.clone()
+}
+
+fn get_artworks(artworks: Vec<ArtworkDto>) -> Vec<Artwork> {
+	artworks
+		.into_iter()
+		.filter(|a| a.kind != ArtworkKind::Unknown)
+		.filter_map(|a| {
+			Some(Artwork {
+				id: a.id?,
+				kind: a.kind,
+				url: a.image?,
+				thumbnail: a.thumbnail.filter(|t| !t.is_empty()),
+				language: a.language,
+				score: a.score,
+				width: a.width,
+				height: a.height,
+			})
+		})
+		.collect()
 }
 
 #[instrument(skip(client))]
@@ -226,7 +491,8 @@ pub(crate) async fn get_series(id: u64, client: &TvDbClient) -> Result<Option<Se
 
 	let series = response.json::<ResultDto<SeriesDto>>().await?.data;
 	let id = series.id;
-	let image = get_image(series.image, series.artworks, ArtworkKind::SeriesPoster);
+	let image = get_image(series.image, &series.artworks, ArtworkKind::SeriesPoster);
+	let artworks = get_artworks(series.artworks);
 	let name = series
 		.translations
 		.name_translations
@@ -239,7 +505,50 @@ pub(crate) async fn get_series(id: u64, client: &TvDbClient) -> Result<Option<Se
 				None
 			}
 		})
-		.unwrap_or(series.name);
+		.unwrap_or_else(|| series.name.clone());
+
+	let aliases = get_aliases(
+		&name,
+		series.name,
+		series.original_language.clone(),
+		series.aliases,
+	);
+
+	// the year is sometimes missing while the first aired date is not
+	let year = series
+		.year
+		.as_deref()
+		.or_else(|| series.first_aired.as_deref().and_then(|d| d.get(..4)))
+		.and_then(|y| y.parse().ok());
+
+	let external_ids = get_external_ids(series.remote_ids);
+
+	let status = series
+		.status
+		.and_then(|s| s.name)
+		.filter(|s| !s.trim().is_empty());
+
+	let original_network = series.original_network.map(|n| Network {
+		id: n.id,
+		slug: n
+			.slug
+			.filter(|s| !s.is_empty())
+			.unwrap_or_else(|| slugify(&n.name)),
+		name: n.name,
+	});
+
+	let genres = series
+		.genres
+		.into_iter()
+		.map(|g| Genre {
+			id: g.id,
+			slug: g
+				.slug
+				.filter(|s| !s.is_empty())
+				.unwrap_or_else(|| slugify(&g.name)),
+			name: g.name,
+		})
+		.collect();
 
 	let overview = series
 		.translations
@@ -257,25 +566,50 @@ pub(crate) async fn get_series(id: u64, client: &TvDbClient) -> Result<Option<Se
 
 	let mut seasons = Vec::with_capacity(series.seasons.len());
 	let mut futures_unordered = FuturesUnordered::new();
-	for season in series
-		.seasons
-		.into_iter()
-		.filter(|s| s.season_type.ty == "official")
-	{
+	for season in series.seasons {
+		let season_type = match season.season_type.ty {
+			Some(season_type) => season_type,
+			None => continue,
+		};
+
 		let span = info_span!(
 			"fetch season",
 			series.id = id,
 			series.name = %name,
 			season.id = season.id,
+			season.r#type = %season_type,
 			season.number = season.number);
-		futures_unordered.push(get_season(season, client).instrument(span));
+		futures_unordered.push(get_season(season, season_type, client).instrument(span));
 	}
 
 	while let Some(season) = futures_unordered.next().await {
 		seasons.push(season?);
 	}
 
-	seasons.sort_by(|l, r| l.number.cmp(&r.number));
+	let season_types = seasons
+		.iter()
+		.map(|s| s.season_type)
+		.collect::<BTreeSet<_>>();
+	let mut futures_unordered = season_types
+		.into_iter()
+		.map(|season_type| {
+			let span = info_span!(
+				"fetch episodes",
+				series.id = id,
+				series.name = %name,
+				season.r#type = %season_type);
+			async move { (season_type, get_episodes(id, season_type, client).await) }.instrument(span)
+		})
+		.collect::<FuturesUnordered<_>>();
+
+	while let Some((season_type, episodes)) = futures_unordered.next().await {
+		let mut episodes = episodes?;
+		for season in seasons.iter_mut().filter(|s| s.season_type == season_type) {
+			season.episodes = episodes.remove(&season.number).unwrap_or_default();
+		}
+	}
+
+	seasons.sort_by_key(|s| (s.season_type, s.number));
 
 	Ok(Some(Series {
 		id,
@@ -283,10 +617,83 @@ pub(crate) async fn get_series(id: u64, client: &TvDbClient) -> Result<Option<Se
 		description: overview,
 		seasons,
 		image,
+		year,
+		status,
+		original_network,
+		original_language: series.original_language,
+		genres,
+		aliases,
+		external_ids,
+		artworks,
 	}))
 }
 
-fn nullable_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+/// Turns a name into a url friendly slug, like tvdb does for the ones it has.
+pub(crate) fn slugify(name: &str) -> String {
+	let mut slug = String::with_capacity(name.len());
+	for c in name.chars().flat_map(char::to_lowercase) {
+		if c.is_alphanumeric() {
+			slug.push(c);
+		} else if !slug.is_empty() && !slug.ends_with('-') {
+			slug.push('-');
+		}
+	}
+
+	slug.truncate(slug.trim_end_matches('-').len());
+	slug
+}
+
+fn get_aliases(
+	name: &str,
+	original_name: String,
+	original_language: Option<String>,
+	aliases: Vec<AliasDto>,
+) -> Vec<Alias> {
+	// the untranslated name is usually the native or romanized title, which is
+	// what people search for - so keep it around as an alias as well
+	let original = Alias {
+		language: original_language,
+		name: original_name,
+	};
+
+	let mut aliases = std::iter::once(original)
+		.chain(aliases.into_iter().map(|a| Alias {
+			language: a.language,
+			name: a.name,
+		}))
+		.map(|a| Alias {
+			name: a.name.trim().to_owned(),
+			..a
+		})
+		.filter(|a| !a.name.is_empty() && a.name != name)
+		.collect::<Vec<_>>();
+
+	aliases.sort();
+	aliases.dedup();
+	aliases
+}
+
+/// Keeps the remote ids from the sources we know, skipping links to websites
+/// and social media.
+fn get_external_ids(remote_ids: Vec<RemoteIdDto>) -> Vec<ExternalId> {
+	let mut external_ids = remote_ids
+		.into_iter()
+		.filter_map(|r| {
+			let source = ExternalSource::from_tvdb_name(&r.source_name)?;
+			let id = r.id.trim();
+			(!id.is_empty()).then(|| ExternalId {
+				source,
+				id: id.to_owned(),
+			})
+		})
+		.collect::<Vec<_>>();
+
+	external_ids.sort();
+	external_ids.dedup();
+	external_ids
+}
+
+pub(crate) fn nullable_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
 	D: serde::Deserializer<'de>,
 	T: serde::Deserialize<'de>,
@@ -294,3 +701,42 @@ where
 	let opt = Option::deserialize(deserializer)?;
 	Ok(opt.unwrap_or_default())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn slugs() {
+		assert_eq!(slugify("Science Fiction"), "science-fiction");
+		assert_eq!(slugify("  Tokyo MX / BS11 "), "tokyo-mx-bs11");
+		assert_eq!(slugify("Action & Adventure!"), "action-adventure");
+	}
+
+	#[test]
+	fn external_ids() {
+		let remote_ids = serde_json::from_str::<Vec<RemoteIdDto>>(
+			r#"[
+				{ "id": "tt22248376", "type": 2, "sourceName": "IMDB" },
+				{ "id": "209867", "type": 12, "sourceName": "TheMovieDB.com" },
+				{ "id": "https://frieren-anime.jp/", "type": 4, "sourceName": "Official Website" },
+				{ "id": "209867", "type": 12, "sourceName": "TheMovieDB.com" }
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_external_ids(remote_ids),
+			vec![
+				ExternalId {
+					source: ExternalSource::Imdb,
+					id: "tt22248376".into()
+				},
+				ExternalId {
+					source: ExternalSource::Tmdb,
+					id: "209867".into()
+				},
+			]
+		);
+	}
+}
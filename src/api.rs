@@ -1,8 +1,33 @@
 use crate::AppState;
-use axum::Router;
+use axum::{routing::get, Router};
 
+pub(crate) mod episodes;
+pub(crate) mod movies;
+pub(crate) mod openapi;
 pub(crate) mod series;
+pub(crate) mod stats;
+pub(crate) mod themes;
 
 pub fn router() -> Router<AppState> {
-	Router::new().nest("/series", series::router())
+	Router::new()
+		.nest("/episodes", episodes::router())
+		.nest("/movies", movies::router())
+		.nest("/series", series::router())
+		.nest("/themes", themes::router())
+}
+
+/// Routes that are served without an api key: the description of the api,
+/// and the statistics that are public on the website too.
+pub fn public_router() -> Router<AppState> {
+	Router::new()
+		.route("/openapi.json", get(openapi::openapi_json))
+		.nest("/stats", stats::router())
+}
+
+fn openapi(spec: &mut openapi::ApiSpec) {
+	episodes::openapi(spec);
+	movies::openapi(spec);
+	series::openapi(spec);
+	stats::openapi(spec);
+	themes::openapi(spec);
 }
@@ -0,0 +1,100 @@
+use super::openapi::ApiSpec;
+use crate::{
+	preconditions::{Conditional, Validators},
+	AppState,
+};
+use axum::{
+	extract::{FromRequestParts, Path},
+	http::StatusCode,
+	response::IntoResponse,
+	routing::get,
+	Json, Router,
+};
+use dbost_entities::episode_marker;
+use dbost_services::markers::{MarkerKind, MarkerService};
+use schemars::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+
+static_assertions::assert_impl_all!(MarkerService: FromRequestParts<AppState>);
+
+/// Media servers count time in 100ns ticks.
+const TICKS_PER_MS: u64 = 10_000;
+
+async fn get_markers(
+	Path(id): Path<u64>,
+	conditional: Conditional,
+	service: MarkerService,
+) -> impl IntoResponse {
+	// tvdb ids are stored as i32, larger ones can't be an episode we know
+	let Ok(tvdb_id) = i32::try_from(id) else {
+		return (StatusCode::NOT_FOUND, "Episode not found").into_response();
+	};
+
+	let markers = match service.get_for_episodes([tvdb_id]).await {
+		Ok(markers) => markers,
+		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	};
+
+	let validators = Validators::aggregate(markers.iter().map(|m| m.version));
+	conditional.respond(validators, || {
+		Json(EpisodeMarkersDto {
+			episode_tvdb_id: id,
+			markers: markers.into_iter().filter_map(MarkerDto::new).collect(),
+		})
+	})
+}
+
+pub fn router() -> Router<AppState> {
+	Router::<AppState>::new().route("/tvdb/:id/markers", get(get_markers))
+}
+
+pub(super) fn openapi(spec: &mut ApiSpec) {
+	spec.operation(
+		"get",
+		"/episodes/tvdb/{id}/markers",
+		"Get the skippable segments of an episode",
+		|op| {
+			op.path::<u64>("id", "The TVDB id of the episode")
+				.conditional()
+				.response::<EpisodeMarkersDto>(200, "The markers of the episode")
+				.error(404, "Not a TVDB episode id");
+		},
+	);
+}
+
+#[derive(Serialize, JsonSchema)]
+struct EpisodeMarkersDto {
+	pub episode_tvdb_id: u64,
+	pub markers: Vec<MarkerDto>,
+}
+
+/// A skippable segment of an episode. `type` uses the segment names of
+/// Jellyfin's media segments, and the `*_ticks` fields are in the 100ns ticks
+/// Jellyfin and Emby use for positions, so the markers can be passed on as is.
+#[derive(Serialize, JsonSchema)]
+struct MarkerDto {
+	#[serde(rename = "type")]
+	pub kind: &'static str,
+	pub start_ms: u32,
+	pub end_ms: u32,
+	pub start_ticks: u64,
+	pub end_ticks: u64,
+	pub theme_song_id: Uuid,
+}
+
+impl MarkerDto {
+	fn new(marker: episode_marker::Model) -> Option<Self> {
+		let kind = marker.kind.parse::<MarkerKind>().ok()?;
+		let (start_ms, end_ms) = (marker.start_ms as u32, marker.end_ms as u32);
+
+		Some(Self {
+			kind: kind.name(),
+			start_ms,
+			end_ms,
+			start_ticks: start_ms as u64 * TICKS_PER_MS,
+			end_ticks: end_ms as u64 * TICKS_PER_MS,
+			theme_song_id: marker.theme_song_id,
+		})
+	}
+}
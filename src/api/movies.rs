@@ -0,0 +1,120 @@
+use super::openapi::ApiSpec;
+use crate::{
+	preconditions::{Conditional, Validators},
+	rate_limit::{Client, Quota, RateLimiter},
+	AppState,
+};
+use axum::{
+	extract::{FromRequestParts, Path, Query, State},
+	http::StatusCode,
+	response::IntoResponse,
+	routing::get,
+	Extension, Json, Router,
+};
+use dbost_entities::movie;
+use dbost_services::movies::{MovieRef, MovieService};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+static_assertions::assert_impl_all!(MovieService: FromRequestParts<AppState>);
+
+async fn get_movie(
+	Path(id): Path<Uuid>,
+	conditional: Conditional,
+	service: MovieService,
+) -> impl IntoResponse {
+	let movie = match service.get_movie(id).await {
+		Ok(Some(movie)) => movie,
+		Ok(None) => return (StatusCode::NOT_FOUND, "Movie not found").into_response(),
+		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	};
+
+	let validators = Validators::row(movie.version);
+	conditional.respond(Some(validators), || Json(MovieDto::new(movie)))
+}
+
+#[derive(Deserialize)]
+struct GetMovieQuery {
+	#[serde(default = "Default::default")]
+	update: bool,
+}
+
+async fn get_movie_by_tvdb_id(
+	Path(id): Path<u64>,
+	Query(query): Query<GetMovieQuery>,
+	Extension(client): Extension<Client>,
+	State(rate_limits): State<RateLimiter>,
+	conditional: Conditional,
+	service: MovieService,
+) -> impl IntoResponse {
+	let lookup = if query.update {
+		if let Some(response) = rate_limits.take(client, Quota::Tvdb) {
+			return response;
+		}
+
+		service.fetch_from_tvdb(id).await
+	} else {
+		service.get_movie(MovieRef::TvDbId(id)).await
+	};
+
+	let movie = match lookup {
+		Ok(Some(movie)) => movie,
+		Ok(None) => return (StatusCode::NOT_FOUND, "Movie not found").into_response(),
+		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	};
+
+	let validators = Validators::row(movie.version);
+	conditional.respond(Some(validators), || Json(MovieDto::new(movie)))
+}
+
+pub fn router() -> Router<AppState> {
+	Router::<AppState>::new()
+		.route("/:id", get(get_movie))
+		.route("/tvdb/:id", get(get_movie_by_tvdb_id))
+}
+
+pub(super) fn openapi(spec: &mut ApiSpec) {
+	spec
+		.operation("get", "/movies/{id}", "Get a movie", |op| {
+			op.path::<Uuid>("id", "The id of the movie")
+				.conditional()
+				.response::<MovieDto>(200, "The movie")
+				.error(404, "Movie not found");
+		})
+		.operation(
+			"get",
+			"/movies/tvdb/{id}",
+			"Get a movie by its TVDB id",
+			|op| {
+				op.path::<u64>("id", "The TVDB id of the movie")
+					.query::<bool>("update", "Fetch the movie from TVDB first")
+					.conditional()
+					.response::<MovieDto>(200, "The movie")
+					.error(404, "Movie not found");
+			},
+		);
+}
+
+#[derive(Serialize, JsonSchema)]
+struct MovieDto {
+	pub id: Uuid,
+	pub name: String,
+	pub tvdb_id: u32,
+	pub year: Option<u16>,
+	pub image: Option<String>,
+	pub image_blurhash: Option<String>,
+}
+
+impl MovieDto {
+	fn new(movie: movie::Model) -> Self {
+		Self {
+			id: movie.id,
+			name: movie.name,
+			tvdb_id: movie.tvdb_id as u32,
+			year: movie.year.map(|y| y as u16),
+			image: movie.image,
+			image_blurhash: movie.image_blurhash,
+		}
+	}
+}
@@ -0,0 +1,344 @@
+use axum::{response::IntoResponse, Json};
+use schemars::{
+	gen::{SchemaGenerator, SchemaSettings},
+	JsonSchema,
+};
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, sync::OnceLock};
+
+/// The OpenAPI 3 document of the api, with the json schemas of the DTOs as its
+/// components. The operations are described by each api module next to its
+/// routes.
+pub fn document() -> &'static Value {
+	static DOCUMENT: OnceLock<Value> = OnceLock::new();
+	DOCUMENT.get_or_init(|| {
+		let mut spec = ApiSpec::new();
+		super::openapi(&mut spec);
+		spec.into_document()
+	})
+}
+
+pub(super) async fn openapi_json() -> impl IntoResponse {
+	Json(document())
+}
+
+pub(super) struct ApiSpec {
+	generator: SchemaGenerator,
+	paths: BTreeMap<String, Map<String, Value>>,
+}
+
+impl ApiSpec {
+	fn new() -> Self {
+		Self {
+			generator: SchemaSettings::openapi3().into_generator(),
+			paths: BTreeMap::new(),
+		}
+	}
+
+	/// Describes the operation of a route, where `path` is relative to `/api`
+	/// and uses OpenAPI's `{param}` syntax.
+	pub(super) fn operation(
+		&mut self,
+		method: &str,
+		path: &str,
+		summary: &str,
+		describe: impl FnOnce(&mut Operation),
+	) -> &mut Self {
+		let mut operation = Operation {
+			generator: &mut self.generator,
+			value: Map::new(),
+			responses: Map::new(),
+		};
+
+		operation.value.insert("summary".into(), summary.into());
+		describe(&mut operation);
+		operation.error(
+			429,
+			"Too many requests, try again after `Retry-After` seconds",
+		);
+
+		let Operation {
+			mut value,
+			responses,
+			..
+		} = operation;
+		value.insert("responses".into(), responses.into());
+
+		self
+			.paths
+			.entry(path.to_owned())
+			.or_default()
+			.insert(method.to_owned(), value.into());
+
+		self
+	}
+
+	fn into_document(mut self) -> Value {
+		let schemas = self
+			.generator
+			.take_definitions()
+			.into_iter()
+			.map(|(name, schema)| (name, serde_json::to_value(schema).unwrap()))
+			.collect::<Map<_, _>>();
+
+		json!({
+			"openapi": "3.0.3",
+			"info": {
+				"title": "dbost",
+				"description": "Theme songs for TV series, keyed by TVDB ids.",
+				"version": env!("CARGO_PKG_VERSION"),
+			},
+			"servers": [{ "url": "/api" }],
+			"security": [{ "apiKey": [] }],
+			"paths": self.paths,
+			"components": {
+				"schemas": schemas,
+				"securitySchemes": {
+					"apiKey": {
+						"type": "apiKey",
+						"in": "header",
+						"name": "x-api-key",
+						"description": "A personal API key, created on the profile page.",
+					},
+				},
+			},
+		})
+	}
+}
+
+pub(super) struct Operation<'a> {
+	generator: &'a mut SchemaGenerator,
+	value: Map<String, Value>,
+	responses: Map<String, Value>,
+}
+
+impl<'a> Operation<'a> {
+	fn schema<T: JsonSchema>(&mut self) -> Value {
+		serde_json::to_value(self.generator.subschema_for::<T>()).unwrap()
+	}
+
+	fn parameter<T: JsonSchema>(&mut self, location: &str, name: &str, description: &str) {
+		let schema = self.schema::<T>();
+		let parameter = json!({
+			"name": name,
+			"in": location,
+			"description": description,
+			"required": location == "path",
+			"schema": schema,
+		});
+
+		self
+			.value
+			.entry("parameters")
+			.or_insert_with(|| Value::Array(Vec::new()))
+			.as_array_mut()
+			.unwrap()
+			.push(parameter);
+	}
+
+	pub(super) fn description(&mut self, description: &str) -> &mut Self {
+		self.value.insert("description".into(), description.into());
+		self
+	}
+
+	pub(super) fn path<T: JsonSchema>(&mut self, name: &str, description: &str) -> &mut Self {
+		self.parameter::<T>("path", name, description);
+		self
+	}
+
+	pub(super) fn query<T: JsonSchema>(&mut self, name: &str, description: &str) -> &mut Self {
+		self.parameter::<T>("query", name, description);
+		self
+	}
+
+	/// Marks an operation that is served without an api key.
+	pub(super) fn public(&mut self) -> &mut Self {
+		self.value.insert("security".into(), json!([]));
+		self
+	}
+
+	/// Marks an operation that needs an api key with the `write` scope.
+	pub(super) fn write_scope(&mut self) -> &mut Self {
+		self.error(403, "The API key lacks the `write` scope")
+	}
+
+	/// The `If-Match` precondition on the `_version` of what is changed.
+	pub(super) fn if_match(&mut self) -> &mut Self {
+		self.parameter::<String>(
+			"header",
+			"If-Match",
+			"The `ETag` of the version the change is based on, or `*`. For a series or season, that is its `version`.",
+		);
+		self.error(412, "Changed since the version in `If-Match`")
+	}
+
+	/// The `If-None-Match` and `If-Modified-Since` preconditions on the
+	/// `_version` of what is returned.
+	pub(super) fn conditional(&mut self) -> &mut Self {
+		self.parameter::<String>(
+			"header",
+			"If-None-Match",
+			"The `ETag` of a copy the client has, or `*`.",
+		);
+		self.parameter::<String>(
+			"header",
+			"If-Modified-Since",
+			"The `Last-Modified` date of a copy the client has.",
+		);
+		self.responses.insert(
+			"304".into(),
+			json!({ "description": "Not changed since the copy the client has" }),
+		);
+		self
+	}
+
+	pub(super) fn body<T: JsonSchema>(&mut self) -> &mut Self {
+		let schema = self.schema::<T>();
+		self.value.insert(
+			"requestBody".into(),
+			json!({
+				"required": true,
+				"content": { "application/json": { "schema": schema } },
+			}),
+		);
+		self
+	}
+
+	pub(super) fn response<T: JsonSchema>(&mut self, status: u16, description: &str) -> &mut Self {
+		let schema = self.schema::<T>();
+		self.responses.insert(
+			status.to_string(),
+			json!({
+				"description": description,
+				"content": { "application/json": { "schema": schema } },
+			}),
+		);
+		self
+	}
+
+	/// An error response, which has a plain text message.
+	pub(super) fn error(&mut self, status: u16, description: &str) -> &mut Self {
+		self.responses.insert(
+			status.to_string(),
+			json!({
+				"description": description,
+				"content": { "text/plain": { "schema": { "type": "string" } } },
+			}),
+		);
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeSet;
+
+	const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+	/// The api modules, as nested in `api.rs`.
+	const MODULES: [(&str, &str); 5] = [
+		("episodes", include_str!("episodes.rs")),
+		("movies", include_str!("movies.rs")),
+		("series", include_str!("series.rs")),
+		("stats", include_str!("stats.rs")),
+		("themes", include_str!("themes.rs")),
+	];
+
+	/// Splits the `.route(...)` and `.nest(...)` calls out of some source.
+	fn calls<'a>(source: &'a str, name: &str) -> Vec<&'a str> {
+		let needle = format!(".{name}(");
+		source
+			.match_indices(&needle)
+			.map(|(start, _)| {
+				let args = &source[start + needle.len()..];
+				let mut depth = 1;
+				let end = args
+					.char_indices()
+					.find(|(_, c)| {
+						match c {
+							'(' => depth += 1,
+							')' => depth -= 1,
+							_ => {}
+						}
+						depth == 0
+					})
+					.map(|(i, _)| i)
+					.unwrap();
+
+				&args[..end]
+			})
+			.collect()
+	}
+
+	fn first_string(args: &str) -> &str {
+		args.split('"').nth(1).unwrap()
+	}
+
+	/// Turns an axum path into an OpenAPI one.
+	fn openapi_path(prefix: &str, path: &str) -> String {
+		let path = path
+			.split('/')
+			.map(|segment| match segment.strip_prefix(':') {
+				Some(param) => format!("{{{param}}}"),
+				None => segment.to_owned(),
+			})
+			.collect::<Vec<_>>()
+			.join("/");
+
+		match &*path {
+			"/" => prefix.to_owned(),
+			path => format!("{prefix}{path}"),
+		}
+	}
+
+	#[test]
+	fn every_route_is_documented() {
+		let mut routes = BTreeSet::new();
+		for nest in calls(include_str!("../api.rs"), "nest") {
+			let prefix = first_string(nest);
+			let module = nest.split(',').nth(1).unwrap().trim();
+			let module = module.strip_suffix("::router()").unwrap();
+			let (_, source) = MODULES
+				.iter()
+				.find(|(name, _)| *name == module)
+				.unwrap_or_else(|| panic!("add the `{module}` module to the test"));
+
+			for route in calls(source, "route") {
+				let path = openapi_path(prefix, first_string(route));
+				let methods = METHODS.iter().filter(|method| {
+					route
+						.match_indices(&format!("{method}("))
+						.any(|(i, _)| !route[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_'))
+				});
+
+				for method in methods {
+					routes.insert((path.clone(), method.to_string()));
+				}
+			}
+		}
+
+		assert!(routes.contains(&("/series/{id}".to_owned(), "get".to_owned())));
+
+		let paths = document()["paths"].as_object().unwrap();
+		let documented = paths
+			.iter()
+			.flat_map(|(path, item)| {
+				let methods = item.as_object().unwrap().keys();
+				methods.map(move |method| (path.clone(), method.clone()))
+			})
+			.collect::<BTreeSet<_>>();
+
+		let undocumented = routes.difference(&documented).collect::<Vec<_>>();
+		assert!(
+			undocumented.is_empty(),
+			"undocumented routes: {undocumented:?}"
+		);
+
+		let unknown = documented.difference(&routes).collect::<Vec<_>>();
+		assert!(
+			unknown.is_empty(),
+			"documented routes that don't exist: {unknown:?}"
+		);
+	}
+}
@@ -1,40 +1,165 @@
-use crate::AppState;
+use super::{
+	openapi::ApiSpec,
+	themes::{self, ThemeDto},
+};
+use crate::{
+	auth::WriteAccess,
+	imports::ImportQueue,
+	pagination::{Cursor, CursorPage},
+	preconditions::{entity_tag, Conditional, IfMatch, Validators},
+	rate_limit::{Client, Quota, RateLimiter},
+	utils::{from_timestamp_micros, timestamp_micros},
+	AppState,
+};
 use axum::{
-	extract::{FromRequestParts, Path, Query},
-	http::StatusCode,
+	extract::{Extension, FromRequestParts, OriginalUri, Path, Query, State},
+	http::{header, StatusCode},
 	response::IntoResponse,
-	routing::get,
+	routing::{get, post, put},
 	Json, Router,
 };
-use dbost_entities::{season, series};
-use dbost_services::series::{SeriesRef, SeriesService};
+use dbost_entities::{artwork, episode, season, series};
+use dbost_services::{
+	api_keys::{ApiUser, Scope},
+	series::{
+		SeriesFilter, SeriesPosition, SeriesRef, SeriesService, SeriesThemes, SeriesWithSeasons,
+	},
+	themes::{ThemeService, ThemeTarget},
+};
+use dbost_utils::OffsetDateTimeExt;
+use schemars::JsonSchema;
+use sea_orm::prelude::TimeDateTime;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use tvdb_client::{ArtworkKind, ExternalSource};
 use uuid::Uuid;
 
 static_assertions::assert_impl_all!(SeriesService: FromRequestParts<AppState>);
 
-trait ResultExt<T, E> {
-	fn log_err(self, f: impl FnOnce(&E)) -> Self;
+/// Most TVDB ids that can be looked up at once.
+const MAX_LOOKUP_IDS: usize = 500;
+
+const DEFAULT_PAGE_SIZE: u64 = 50;
+const MAX_PAGE_SIZE: u64 = 200;
+
+/// Position in the series listing, as the `_version` in microseconds and id
+/// of a series.
+#[derive(Serialize, Deserialize)]
+struct SeriesListKey {
+	version: i64,
+	id: Uuid,
+}
+
+impl SeriesListKey {
+	fn new(series: &series::Model) -> Self {
+		Self {
+			version: timestamp_micros(series.version),
+			id: series.id,
+		}
+	}
+}
+
+#[derive(Deserialize)]
+struct ListSeriesQuery {
+	#[serde(default)]
+	after: Option<Cursor<SeriesListKey>>,
+	#[serde(default)]
+	before: Option<Cursor<SeriesListKey>>,
+	#[serde(default)]
+	limit: Option<u64>,
+	/// An RFC 3339 timestamp.
+	#[serde(default)]
+	updated_since: Option<String>,
+	#[serde(default)]
+	has_theme: Option<bool>,
 }
 
-impl<T, E> ResultExt<T, E> for Result<T, E> {
-	fn log_err(self, f: impl FnOnce(&E)) -> Self {
-		if let Err(e) = &self {
-			f(e);
+async fn list_series(
+	Query(query): Query<ListSeriesQuery>,
+	OriginalUri(uri): OriginalUri,
+	service: SeriesService,
+) -> impl IntoResponse {
+	let size = query
+		.limit
+		.unwrap_or(DEFAULT_PAGE_SIZE)
+		.clamp(1, MAX_PAGE_SIZE);
+
+	let updated_since = match query
+		.updated_since
+		.as_deref()
+		.map(|since| OffsetDateTime::parse(since, &Rfc3339))
+	{
+		None => None,
+		Some(Ok(since)) => Some(since.into_primitive_utc()),
+		Some(Err(_)) => {
+			return (
+				StatusCode::BAD_REQUEST,
+				"updated_since must be an RFC 3339 timestamp",
+			)
+				.into_response()
 		}
+	};
+
+	let filter = SeriesFilter {
+		updated_since,
+		has_theme: query.has_theme,
+	};
 
-		self
+	let position =
+		|key: SeriesListKey| from_timestamp_micros(key.version).map(|version| (version, key.id));
+	let position = match (query.after, query.before) {
+		(None, None) => Some(SeriesPosition::Start),
+		(Some(after), None) => position(after.into_key()).map(|(v, id)| SeriesPosition::After(v, id)),
+		(None, Some(before)) => {
+			position(before.into_key()).map(|(v, id)| SeriesPosition::Before(v, id))
+		}
+		(Some(_), Some(_)) => None,
+	};
+
+	let Some(position) = position else {
+		return (StatusCode::BAD_REQUEST, "Invalid pagination cursor").into_response();
+	};
+
+	let rows = match service.list_series(filter, position, size + 1).await {
+		Ok(rows) => rows,
+		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	};
+
+	let page = match position {
+		SeriesPosition::Start => CursorPage::new(rows, size, &uri, SeriesListKey::new),
+		SeriesPosition::After(..) => CursorPage::after(rows, size, &uri, SeriesListKey::new),
+		SeriesPosition::Before(..) => CursorPage::before(rows, size, &uri, SeriesListKey::new),
+	};
+
+	let link = page.link_header(&uri);
+	let items = page
+		.items
+		.into_iter()
+		.map(SeriesSummaryDto::new)
+		.collect::<Vec<_>>();
+
+	let mut response = Json(items).into_response();
+	if let Some(link) = link {
+		response.headers_mut().insert(header::LINK, link);
 	}
+
+	response
 }
 
-async fn get_series(Path(id): Path<Uuid>, service: SeriesService) -> impl IntoResponse {
+async fn get_series(
+	Path(id): Path<Uuid>,
+	conditional: Conditional,
+	service: SeriesService,
+) -> impl IntoResponse {
 	let series = match service.get_series(id).await {
 		Ok(Some(series)) => series,
 		Ok(None) => return (StatusCode::NOT_FOUND, "Series not found").into_response(),
 		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
 	};
 
-	Json(SeriesDto::new(series.series, series.seasons)).into_response()
+	let validators = Validators::aggregate(series.versions());
+	conditional.respond(validators, || Json(SeriesDto::new(series)))
 }
 
 #[derive(Deserialize)]
@@ -46,9 +171,16 @@ struct GetSeriesQuery {
 async fn get_series_by_tvdb_id(
 	Path(id): Path<u64>,
 	Query(query): Query<GetSeriesQuery>,
+	Extension(client): Extension<Client>,
+	State(rate_limits): State<RateLimiter>,
+	conditional: Conditional,
 	service: SeriesService,
 ) -> impl IntoResponse {
 	let lookup = if query.update {
+		if let Some(response) = rate_limits.take(client, Quota::Tvdb) {
+			return response;
+		}
+
 		service.fetch_from_tvdb(id, None).await
 	} else {
 		service.get_series(SeriesRef::TvDbId(id)).await
@@ -60,53 +192,543 @@ async fn get_series_by_tvdb_id(
 		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
 	};
 
-	Json(SeriesDto::new(series.series, series.seasons)).into_response()
+	let validators = Validators::aggregate(series.versions());
+	conditional.respond(validators, || Json(SeriesDto::new(series)))
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct LookupDto {
+	pub tvdb_ids: Vec<u64>,
+	/// Queue the ids that weren't found for import from TVDB.
+	#[serde(default)]
+	pub import_missing: bool,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct LookupResultDto {
+	pub series: Vec<LookupSeriesDto>,
+	pub missing: Vec<u64>,
+	/// The missing ids queued for import; look them up again later. Ids that
+	/// didn't fit in the key's TVDB budget are left out.
+	pub queued: Vec<u64>,
+}
+
+async fn lookup_by_tvdb_ids(
+	Extension(caller): Extension<ApiUser>,
+	Extension(client): Extension<Client>,
+	service: SeriesService,
+	State(imports): State<ImportQueue>,
+	State(rate_limits): State<RateLimiter>,
+	Json(body): Json<LookupDto>,
+) -> impl IntoResponse {
+	if body.tvdb_ids.len() > MAX_LOOKUP_IDS {
+		let message = format!("At most {MAX_LOOKUP_IDS} TVDB ids can be looked up at once");
+		return (StatusCode::UNPROCESSABLE_ENTITY, message).into_response();
+	}
+
+	if body.tvdb_ids.iter().any(|id| i32::try_from(*id).is_err()) {
+		let message = format!("TVDB ids must be at most {}", i32::MAX);
+		return (StatusCode::UNPROCESSABLE_ENTITY, message).into_response();
+	}
+
+	if body.import_missing && !caller.scopes.contains(Scope::Write) {
+		let message = "The API key lacks the `write` scope needed to import series";
+		return (StatusCode::FORBIDDEN, message).into_response();
+	}
+
+	let ids = body.tvdb_ids.into_iter().collect::<BTreeSet<_>>();
+	let found = match service.lookup_by_tvdb_ids(ids.iter().copied()).await {
+		Ok(found) => found,
+		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	};
+
+	let found_ids = found
+		.iter()
+		.map(|s| s.series.tvdb_id as u64)
+		.collect::<BTreeSet<_>>();
+	let missing = ids.difference(&found_ids).copied().collect::<Vec<_>>();
+
+	let queued = match body.import_missing {
+		true => {
+			// every import fetches from tvdb, so they come out of the same budget
+			// as `?update=true`
+			let allowed = rate_limits.take_up_to(client, Quota::Tvdb, missing.len());
+			let queued = missing[..allowed].to_vec();
+			queued.iter().for_each(|id| imports.enqueue(*id));
+			queued
+		}
+		false => Vec::new(),
+	};
+
+	let status = match queued.is_empty() {
+		true => StatusCode::OK,
+		false => StatusCode::ACCEPTED,
+	};
+
+	let result = LookupResultDto {
+		series: found.into_iter().map(LookupSeriesDto::new).collect(),
+		missing,
+		queued,
+	};
+
+	(status, Json(result)).into_response()
+}
+
+/// The theme song to set on a series or season, `null` to remove it.
+#[derive(Deserialize, JsonSchema)]
+struct SetThemeDto {
+	pub theme_id: Option<Uuid>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct SetThemeResultDto {
+	pub theme: Option<ThemeDto>,
+}
+
+async fn set_theme(
+	target: ThemeTarget,
+	IfMatch(version): IfMatch,
+	service: ThemeService,
+	body: SetThemeDto,
+) -> axum::response::Response {
+	match service.set_theme(target, body.theme_id, version).await {
+		Ok(assignment) => (
+			[(header::ETAG, entity_tag(assignment.version))],
+			Json(SetThemeResultDto {
+				theme: assignment.theme.map(ThemeDto::new),
+			}),
+		)
+			.into_response(),
+		Err(e) => themes::error_response(e),
+	}
+}
+
+async fn set_series_theme(
+	_: WriteAccess,
+	Path(id): Path<Uuid>,
+	version: IfMatch,
+	service: ThemeService,
+	Json(body): Json<SetThemeDto>,
+) -> impl IntoResponse {
+	set_theme(ThemeTarget::Series(id), version, service, body).await
+}
+
+async fn set_season_theme(
+	_: WriteAccess,
+	Path((series_id, season_id)): Path<(Uuid, Uuid)>,
+	version: IfMatch,
+	service: ThemeService,
+	Json(body): Json<SetThemeDto>,
+) -> impl IntoResponse {
+	let target = ThemeTarget::Season {
+		series_id,
+		season_id,
+	};
+
+	set_theme(target, version, service, body).await
+}
+
+async fn get_series_by_external_id(
+	Path((source, id)): Path<(String, String)>,
+	conditional: Conditional,
+	service: SeriesService,
+) -> impl IntoResponse {
+	let Ok(source) = source.parse::<ExternalSource>() else {
+		return (StatusCode::NOT_FOUND, "Unknown id source").into_response();
+	};
+
+	let series = match service.get_series(SeriesRef::External { source, id }).await {
+		Ok(Some(series)) => series,
+		Ok(None) => return (StatusCode::NOT_FOUND, "Series not found").into_response(),
+		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	};
+
+	let validators = Validators::aggregate(series.versions());
+	conditional.respond(validators, || Json(SeriesDto::new(series)))
 }
 
 pub fn router() -> Router<AppState> {
 	Router::<AppState>::new()
+		.route("/", get(list_series))
 		.route("/:id", get(get_series))
+		.route("/:id/theme", put(set_series_theme))
+		.route("/:id/season/:season_id/theme", put(set_season_theme))
+		.route("/by/:source/:id", get(get_series_by_external_id))
+		.route("/tvdb/lookup", post(lookup_by_tvdb_ids))
 		.route("/tvdb/:id", get(get_series_by_tvdb_id))
 }
 
-#[derive(Serialize)]
+pub(super) fn openapi(spec: &mut ApiSpec) {
+	spec
+		.operation("get", "/series", "List series", |op| {
+			op.description(
+				"Series ordered by when they last changed. The pages link to each other in the `Link` header.",
+			)
+			.query::<String>("after", "Cursor of the next page")
+			.query::<String>("before", "Cursor of the previous page")
+			.query::<u64>("limit", "Series per page, 50 by default and at most 200")
+			.query::<String>("updated_since", "Only series changed since this RFC 3339 timestamp")
+			.query::<bool>("has_theme", "Only series with or without a theme song")
+			.response::<Vec<SeriesSummaryDto>>(200, "A page of series")
+			.error(400, "Invalid cursor or timestamp");
+		})
+		.operation("get", "/series/{id}", "Get a series", |op| {
+			op.path::<Uuid>("id", "The id of the series")
+				.conditional()
+				.response::<SeriesDto>(200, "The series")
+				.error(404, "Series not found");
+		})
+		.operation(
+			"put",
+			"/series/{id}/theme",
+			"Set the theme song of a series",
+			|op| {
+				op.path::<Uuid>("id", "The id of the series")
+					.write_scope()
+					.if_match()
+					.body::<SetThemeDto>()
+					.response::<SetThemeResultDto>(200, "The theme song, with the new version as `ETag`")
+					.error(404, "Series not found")
+					.error(422, "Unknown theme song");
+			},
+		)
+		.operation(
+			"put",
+			"/series/{id}/season/{season_id}/theme",
+			"Set the theme song of a season",
+			|op| {
+				op.path::<Uuid>("id", "The id of the series")
+					.path::<Uuid>("season_id", "The id of the season")
+					.write_scope()
+					.if_match()
+					.body::<SetThemeDto>()
+					.response::<SetThemeResultDto>(200, "The theme song, with the new version as `ETag`")
+					.error(404, "Season not found")
+					.error(422, "Unknown theme song");
+			},
+		)
+		.operation(
+			"get",
+			"/series/by/{source}/{id}",
+			"Get a series by its id in another database",
+			|op| {
+				op.path::<String>("source", "One of `imdb`, `tmdb`, `anidb` or `mal`")
+					.path::<String>("id", "The id of the series in that database")
+					.conditional()
+					.response::<SeriesDto>(200, "The series")
+					.error(404, "Series or source not found");
+			},
+		)
+		.operation(
+			"post",
+			"/series/tvdb/lookup",
+			"Look up series by their TVDB ids",
+			|op| {
+				op.body::<LookupDto>()
+					.response::<LookupResultDto>(200, "The series that were found")
+					.response::<LookupResultDto>(202, "The series that were found, with missing ones queued")
+					.error(
+						403,
+						"`import_missing` needs an API key with the `write` scope",
+					)
+					.error(422, "Too many TVDB ids, or ids that are too large");
+			},
+		)
+		.operation(
+			"get",
+			"/series/tvdb/{id}",
+			"Get a series by its TVDB id",
+			|op| {
+				op.path::<u64>("id", "The TVDB id of the series")
+					.query::<bool>("update", "Fetch the series from TVDB first")
+					.conditional()
+					.response::<SeriesDto>(200, "The series")
+					.error(404, "Series not found");
+			},
+		);
+}
+
+#[derive(Serialize, JsonSchema)]
 struct SeriesDto {
 	pub id: Uuid,
 	pub name: String,
 	pub tvdb_id: u32,
+	/// The `ETag` of the series itself, which setting its theme song takes in
+	/// `If-Match`.
+	pub version: String,
+	pub theme: Option<ThemeDto>,
 	pub seasons: Vec<SeasonDto>,
 	pub image: Option<String>,
+	pub image_blurhash: Option<String>,
+	pub year: Option<u16>,
+	pub status: Option<String>,
+	pub network: Option<String>,
+	pub original_language: Option<String>,
+	pub genres: Vec<GenreDto>,
+	pub artworks: Vec<ArtworkDto>,
+}
+
+/// A series in the listing, without its seasons and artwork.
+#[derive(Serialize, JsonSchema)]
+struct SeriesSummaryDto {
+	pub id: Uuid,
+	pub name: String,
+	pub tvdb_id: u32,
+	pub image: Option<String>,
+	pub year: Option<u16>,
+	pub theme_song_id: Option<Uuid>,
+	/// When the series last changed, as an RFC 3339 timestamp.
+	pub updated_at: String,
 }
 
-#[derive(Serialize)]
+impl SeriesSummaryDto {
+	fn new(series: series::Model) -> Self {
+		Self {
+			id: series.id,
+			name: series.name,
+			tvdb_id: series.tvdb_id as u32,
+			image: series.image,
+			year: series.year.map(|y| y as u16),
+			theme_song_id: series.theme_song_id,
+			updated_at: series
+				.version
+				.assume_utc()
+				.format(&Rfc3339)
+				.expect("database timestamps format as RFC 3339"),
+		}
+	}
+}
+
+/// A looked up series, with just its seasons and theme songs.
+#[derive(Serialize, JsonSchema)]
+struct LookupSeriesDto {
+	pub id: Uuid,
+	pub name: String,
+	pub tvdb_id: u32,
+	pub theme: Option<ThemeDto>,
+	pub seasons: Vec<LookupSeasonDto>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct LookupSeasonDto {
+	pub id: Uuid,
+	pub season_type: String,
+	pub number: i32,
+	pub name: Option<String>,
+	pub tvdb_id: u32,
+	pub theme: Option<ThemeDto>,
+}
+
+impl LookupSeriesDto {
+	fn new(value: SeriesThemes) -> Self {
+		let SeriesThemes {
+			series,
+			seasons,
+			themes,
+		} = value;
+
+		let theme = |id: Option<Uuid>| {
+			id.and_then(|id| themes.iter().find(|theme| theme.id == id))
+				.cloned()
+				.map(ThemeDto::new)
+		};
+
+		Self {
+			id: series.id,
+			name: series.name,
+			tvdb_id: series.tvdb_id as u32,
+			theme: theme(series.theme_song_id),
+			seasons: seasons
+				.into_iter()
+				.map(|season| LookupSeasonDto {
+					id: season.id,
+					season_type: season.season_type,
+					number: season.number as i32,
+					name: season.name,
+					tvdb_id: season.tvdb_id as u32,
+					theme: theme(season.theme_song_id),
+				})
+				.collect(),
+		}
+	}
+}
+
+#[derive(Serialize, JsonSchema)]
+struct GenreDto {
+	pub name: String,
+	pub slug: String,
+}
+
+#[derive(Serialize, JsonSchema)]
 struct SeasonDto {
 	pub id: Uuid,
+	pub season_type: String,
 	pub number: i32,
 	pub name: Option<String>,
 	pub tvdb_id: u32,
+	/// The `ETag` of the season itself, which setting its theme song takes in
+	/// `If-Match`.
+	pub version: String,
+	pub theme: Option<ThemeDto>,
 	pub image: Option<String>,
+	pub image_blurhash: Option<String>,
+	pub artworks: Vec<ArtworkDto>,
+	pub episodes: Vec<EpisodeDto>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct EpisodeDto {
+	pub id: Uuid,
+	pub tvdb_id: u32,
+	pub number: u32,
+	pub absolute_number: Option<u32>,
+	pub name: Option<String>,
+	pub air_date: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct ArtworkDto {
+	pub id: Uuid,
+	pub tvdb_id: u32,
+	pub kind: &'static str,
+	pub url: String,
+	pub thumbnail: Option<String>,
+	pub language: Option<String>,
+	pub score: u32,
+	pub width: Option<u32>,
+	pub height: Option<u32>,
 }
 
 impl SeriesDto {
-	fn new(series: series::Model, seasons: Vec<season::Model>) -> Self {
+	fn new(value: SeriesWithSeasons) -> Self {
+		let SeriesWithSeasons {
+			series,
+			seasons,
+			artworks,
+			episodes,
+			genres,
+			themes,
+		} = value;
+
+		let themes = themes
+			.into_iter()
+			.map(|theme| (theme.id, theme))
+			.collect::<HashMap<_, _>>();
+		let theme = |id: Option<Uuid>| {
+			id.and_then(|id| themes.get(&id))
+				.cloned()
+				.map(ThemeDto::new)
+		};
+
+		let mut season_episodes = HashMap::<Uuid, Vec<EpisodeDto>>::new();
+		for episode in episodes {
+			season_episodes
+				.entry(episode.season_id)
+				.or_default()
+				.push(EpisodeDto::new(episode));
+		}
+
+		let mut season_artworks = HashMap::<Uuid, Vec<ArtworkDto>>::new();
+		let mut series_artworks = Vec::new();
+		for artwork in artworks {
+			match artwork.season_id {
+				None => series_artworks.push(ArtworkDto::new(artwork)),
+				Some(season_id) => season_artworks
+					.entry(season_id)
+					.or_default()
+					.push(ArtworkDto::new(artwork)),
+			}
+		}
+
 		Self {
 			id: series.id,
 			name: series.name,
 			tvdb_id: series.tvdb_id as u32,
-			seasons: seasons.into_iter().map(SeasonDto::new).collect(),
+			version: version_tag(series.version),
+			seasons: seasons
+				.into_iter()
+				.map(|season| {
+					let artworks = season_artworks.remove(&season.id).unwrap_or_default();
+					let episodes = season_episodes.remove(&season.id).unwrap_or_default();
+					let theme = theme(season.theme_song_id);
+					SeasonDto::new(season, theme, artworks, episodes)
+				})
+				.collect(),
+			theme: theme(series.theme_song_id),
 			image: series.image,
+			image_blurhash: series.image_blurhash,
+			year: series.year.map(|y| y as u16),
+			status: series.status,
+			network: series.network,
+			original_language: series.original_language,
+			genres: genres
+				.into_iter()
+				.map(|g| GenreDto {
+					name: g.name,
+					slug: g.slug,
+				})
+				.collect(),
+			artworks: series_artworks,
 		}
 	}
 }
 
+/// The entity tag of a row, the same one it gets as `ETag` on its own.
+fn version_tag(version: TimeDateTime) -> String {
+	entity_tag(version)
+		.to_str()
+		.expect("entity tags are ascii")
+		.to_owned()
+}
+
 impl SeasonDto {
-	fn new(season: season::Model) -> Self {
+	fn new(
+		season: season::Model,
+		theme: Option<ThemeDto>,
+		artworks: Vec<ArtworkDto>,
+		episodes: Vec<EpisodeDto>,
+	) -> Self {
 		Self {
 			id: season.id,
+			season_type: season.season_type,
 			number: season.number as i32,
 			name: season.name,
 			tvdb_id: season.tvdb_id as u32,
+			version: version_tag(season.version),
+			theme,
 			image: season.image,
+			image_blurhash: season.image_blurhash,
+			artworks,
+			episodes,
+		}
+	}
+}
+
+impl EpisodeDto {
+	fn new(episode: episode::Model) -> Self {
+		Self {
+			id: episode.id,
+			tvdb_id: episode.tvdb_id as u32,
+			number: episode.number as u32,
+			absolute_number: episode.absolute_number.map(|n| n as u32),
+			name: episode.name,
+			air_date: episode.air_date.map(|d| d.to_string()),
+		}
+	}
+}
+
+impl ArtworkDto {
+	fn new(artwork: artwork::Model) -> Self {
+		Self {
+			id: artwork.id,
+			tvdb_id: artwork.tvdb_id as u32,
+			kind: ArtworkKind::from(artwork.kind as u8).name(),
+			url: artwork.url,
+			thumbnail: artwork.thumbnail,
+			language: artwork.language,
+			score: artwork.score as u32,
+			width: artwork.width.map(|w| w as u32),
+			height: artwork.height.map(|h| h as u32),
 		}
 	}
 }
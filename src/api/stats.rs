@@ -0,0 +1,144 @@
+use super::openapi::ApiSpec;
+use crate::AppState;
+use axum::{
+	extract::FromRequestParts, http::StatusCode, response::IntoResponse, routing::get, Json, Router,
+};
+use dbost_services::stats::{self, Stats, StatsService};
+use schemars::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+
+static_assertions::assert_impl_all!(StatsService: FromRequestParts<AppState>);
+
+async fn get_stats(service: StatsService) -> impl IntoResponse {
+	match service.get_stats().await {
+		Ok(stats) => Json(StatsDto::new(stats)).into_response(),
+		Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	}
+}
+
+pub fn router() -> Router<AppState> {
+	Router::<AppState>::new().route("/", get(get_stats))
+}
+
+pub(super) fn openapi(spec: &mut ApiSpec) {
+	spec.operation(
+		"get",
+		"/stats",
+		"Get theme song coverage statistics",
+		|op| {
+			op.public().response::<StatsDto>(200, "The statistics");
+		},
+	);
+}
+
+/// Rounds a percentage to one decimal.
+fn coverage(part: i64, total: i64) -> f64 {
+	(stats::percentage(part, total) * 10.0).round() / 10.0
+}
+
+#[derive(Serialize, JsonSchema)]
+struct StatsDto {
+	pub series: CoverageDto,
+	pub seasons: CoverageDto,
+	pub themes: u64,
+	pub genres: Vec<GenreCoverageDto>,
+	pub years: Vec<YearCoverageDto>,
+	pub top_contributors: Vec<ContributorDto>,
+	pub themes_per_week: Vec<WeekDto>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct CoverageDto {
+	pub total: u64,
+	pub with_theme: u64,
+	pub coverage: f64,
+}
+
+impl CoverageDto {
+	fn new(total: u64, with_theme: u64) -> Self {
+		Self {
+			total,
+			with_theme,
+			coverage: coverage(with_theme as i64, total as i64),
+		}
+	}
+}
+
+#[derive(Serialize, JsonSchema)]
+struct GenreCoverageDto {
+	pub name: String,
+	pub slug: String,
+	pub series: i64,
+	pub with_theme: i64,
+	pub coverage: f64,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct YearCoverageDto {
+	pub year: i32,
+	pub series: i64,
+	pub with_theme: i64,
+	pub coverage: f64,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct ContributorDto {
+	pub user_id: Uuid,
+	pub display_name: String,
+	pub themes: i64,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct WeekDto {
+	pub week: String,
+	pub themes: i64,
+}
+
+impl StatsDto {
+	fn new(stats: Stats) -> Self {
+		Self {
+			series: CoverageDto::new(stats.series, stats.series_with_theme),
+			seasons: CoverageDto::new(stats.seasons, stats.seasons_with_theme),
+			themes: stats.themes,
+			genres: stats
+				.coverage_by_genre
+				.into_iter()
+				.map(|g| GenreCoverageDto {
+					coverage: coverage(g.with_theme, g.series),
+					name: g.name,
+					slug: g.slug,
+					series: g.series,
+					with_theme: g.with_theme,
+				})
+				.collect(),
+			years: stats
+				.coverage_by_year
+				.into_iter()
+				.map(|y| YearCoverageDto {
+					year: y.year,
+					series: y.series,
+					with_theme: y.with_theme,
+					coverage: coverage(y.with_theme, y.series),
+				})
+				.collect(),
+			top_contributors: stats
+				.top_contributors
+				.into_iter()
+				.map(|c| ContributorDto {
+					user_id: c.user_id,
+					display_name: c.display_name,
+					themes: c.themes,
+				})
+				.collect(),
+			themes_per_week: stats
+				.themes_per_week
+				.into_iter()
+				.map(|w| WeekDto {
+					week: w.week.to_string(),
+					themes: w.themes,
+				})
+				.collect(),
+		}
+	}
+}
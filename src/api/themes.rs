@@ -0,0 +1,228 @@
+use super::openapi::ApiSpec;
+use crate::{
+	auth::WriteAccess,
+	preconditions::{entity_tag, Conditional, IfMatch, Validators},
+	AppState,
+};
+use axum::{
+	extract::{FromRequestParts, Path},
+	http::{header, StatusCode},
+	response::{IntoResponse, Response},
+	routing::{get, post},
+	Json, Router,
+};
+use dbost_entities::theme_song;
+use dbost_services::themes::{NewTheme, ThemeChanges, ThemeService, ThemeServiceError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use uuid::Uuid;
+
+static_assertions::assert_impl_all!(ThemeService: FromRequestParts<AppState>);
+
+async fn get_theme(
+	Path(id): Path<Uuid>,
+	conditional: Conditional,
+	service: ThemeService,
+) -> impl IntoResponse {
+	match service.get_theme(id).await {
+		Ok(Some(theme)) => {
+			let validators = Validators::row(theme.version);
+			conditional.respond(Some(validators), || Json(ThemeDto::new(theme)))
+		}
+		Ok(None) => (StatusCode::NOT_FOUND, "Theme song not found").into_response(),
+		Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+	}
+}
+
+async fn create_theme(
+	WriteAccess(caller): WriteAccess,
+	service: ThemeService,
+	Json(body): Json<NewThemeDto>,
+) -> impl IntoResponse {
+	match service
+		.create_theme(body.into(), Some(caller.user.id))
+		.await
+	{
+		Ok(theme) => {
+			let location = format!("/api/themes/{}", theme.id);
+			let mut response = ThemeDto::response(StatusCode::CREATED, theme);
+			response
+				.headers_mut()
+				.insert(header::LOCATION, location.parse().unwrap());
+			response
+		}
+		Err(e) => error_response(e),
+	}
+}
+
+async fn replace_theme(
+	_: WriteAccess,
+	Path(id): Path<Uuid>,
+	IfMatch(version): IfMatch,
+	service: ThemeService,
+	Json(body): Json<NewThemeDto>,
+) -> impl IntoResponse {
+	let changes = NewTheme::from(body).into();
+	match service.update_theme(id, changes, version).await {
+		Ok(theme) => ThemeDto::response(StatusCode::OK, theme),
+		Err(e) => error_response(e),
+	}
+}
+
+async fn update_theme(
+	_: WriteAccess,
+	Path(id): Path<Uuid>,
+	IfMatch(version): IfMatch,
+	service: ThemeService,
+	Json(body): Json<ThemeChangesDto>,
+) -> impl IntoResponse {
+	match service.update_theme(id, body.into(), version).await {
+		Ok(theme) => ThemeDto::response(StatusCode::OK, theme),
+		Err(e) => error_response(e),
+	}
+}
+
+pub fn router() -> Router<AppState> {
+	Router::<AppState>::new()
+		.route("/", post(create_theme))
+		.route(
+			"/:id",
+			get(get_theme).put(replace_theme).patch(update_theme),
+		)
+}
+
+pub(super) fn openapi(spec: &mut ApiSpec) {
+	spec
+		.operation("post", "/themes", "Create a theme song", |op| {
+			op.write_scope()
+				.body::<NewThemeDto>()
+				.response::<ThemeDto>(201, "The created theme song")
+				.error(422, "Invalid theme song");
+		})
+		.operation("get", "/themes/{id}", "Get a theme song", |op| {
+			op.path::<Uuid>("id", "The id of the theme song")
+				.conditional()
+				.response::<ThemeDto>(200, "The theme song, with its version as `ETag`")
+				.error(404, "Theme song not found");
+		})
+		.operation("put", "/themes/{id}", "Replace a theme song", |op| {
+			op.path::<Uuid>("id", "The id of the theme song")
+				.write_scope()
+				.if_match()
+				.body::<NewThemeDto>()
+				.response::<ThemeDto>(200, "The changed theme song")
+				.error(404, "Theme song not found")
+				.error(422, "Invalid theme song");
+		})
+		.operation("patch", "/themes/{id}", "Change a theme song", |op| {
+			op.path::<Uuid>("id", "The id of the theme song")
+				.write_scope()
+				.if_match()
+				.body::<ThemeChangesDto>()
+				.response::<ThemeDto>(200, "The changed theme song")
+				.error(404, "Theme song not found")
+				.error(422, "Invalid theme song");
+		});
+}
+
+/// Responds with the status matching a failed theme song change.
+pub(super) fn error_response(error: ThemeServiceError) -> Response {
+	let status = match &error {
+		ThemeServiceError::MissingName
+		| ThemeServiceError::InvalidVideo(_)
+		| ThemeServiceError::InvalidRange { .. }
+		| ThemeServiceError::UnknownTheme(_) => StatusCode::UNPROCESSABLE_ENTITY,
+		ThemeServiceError::AlreadySet => StatusCode::CONFLICT,
+		ThemeServiceError::VersionMismatch => StatusCode::PRECONDITION_FAILED,
+		ThemeServiceError::NotFound => StatusCode::NOT_FOUND,
+		ThemeServiceError::DbErr(_) => {
+			return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+		}
+	};
+
+	(status, error.to_string()).into_response()
+}
+
+/// A theme song, with the start and end of the video in seconds.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct ThemeDto {
+	pub id: Uuid,
+	pub name: String,
+	pub youtube_id: Option<String>,
+	pub starts_at: Option<u32>,
+	pub ends_at: Option<u32>,
+}
+
+impl ThemeDto {
+	pub(super) fn new(theme: theme_song::Model) -> Self {
+		Self {
+			id: theme.id,
+			name: theme.name,
+			youtube_id: theme.youtube_id,
+			starts_at: theme.youtube_starts_at.map(|s| s as u32),
+			ends_at: theme.youtube_ends_at.map(|s| s as u32),
+		}
+	}
+
+	/// The theme song with its version as the `ETag`.
+	fn response(status: StatusCode, theme: theme_song::Model) -> Response {
+		let etag = entity_tag(theme.version);
+		(status, [(header::ETAG, etag)], Json(Self::new(theme))).into_response()
+	}
+}
+
+/// A new theme song. `youtube` can be a YouTube url or a video id.
+#[derive(Deserialize, JsonSchema)]
+struct NewThemeDto {
+	pub name: String,
+	pub youtube: String,
+	#[serde(default)]
+	pub starts_at: Option<u32>,
+	#[serde(default)]
+	pub ends_at: Option<u32>,
+}
+
+impl From<NewThemeDto> for NewTheme {
+	fn from(value: NewThemeDto) -> Self {
+		Self {
+			name: value.name,
+			youtube: value.youtube,
+			starts_at: value.starts_at,
+			ends_at: value.ends_at,
+		}
+	}
+}
+
+/// Changes to a theme song, where missing fields are kept as they are and
+/// `null` clears the start or end of the video.
+#[derive(Deserialize, JsonSchema)]
+struct ThemeChangesDto {
+	#[serde(default)]
+	pub name: Option<String>,
+	#[serde(default)]
+	pub youtube: Option<String>,
+	#[serde(default, deserialize_with = "present")]
+	pub starts_at: Option<Option<u32>>,
+	#[serde(default, deserialize_with = "present")]
+	pub ends_at: Option<Option<u32>>,
+}
+
+/// Tells a field set to `null` apart from a missing one.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Deserialize<'de>,
+{
+	Option::deserialize(deserializer).map(Some)
+}
+
+impl From<ThemeChangesDto> for ThemeChanges {
+	fn from(value: ThemeChangesDto) -> Self {
+		Self {
+			name: value.name,
+			youtube: value.youtube,
+			starts_at: value.starts_at,
+			ends_at: value.ends_at,
+		}
+	}
+}
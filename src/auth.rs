@@ -1,25 +1,26 @@
+use async_trait::async_trait;
 use axum::{
 	body::BoxBody,
-	http::{Request, StatusCode},
+	extract::FromRequestParts,
+	http::{request::Parts, Request, StatusCode},
 	response::{IntoResponse, Response},
 };
-use std::{
-	future::{ready, Ready},
-	sync::Arc,
-};
+use dbost_services::api_keys::{ApiKeyError, ApiKeyService, ApiUser, Scope};
+use futures::future::BoxFuture;
 use tower::Layer;
 use tower_http::auth::{AsyncAuthorizeRequest, AsyncRequireAuthorization};
+use tracing::error;
 
+/// Requires a valid `x-api-key` with the `read` scope, and adds the
+/// [`ApiUser`] it belongs to to the request extensions.
 #[derive(Clone)]
 pub struct AuthorizationLayer {
-	api_key: Arc<str>,
+	keys: ApiKeyService,
 }
 
 impl AuthorizationLayer {
-	pub fn new(api_key: impl Into<Arc<str>>) -> Self {
-		Self {
-			api_key: api_key.into(),
-		}
+	pub fn new(keys: ApiKeyService) -> Self {
+		Self { keys }
 	}
 }
 
@@ -30,7 +31,7 @@ impl<S> Layer<S> for AuthorizationLayer {
 		AsyncRequireAuthorization::new(
 			inner,
 			AuthorizationHandler {
-				api_key: self.api_key.clone(),
+				keys: self.keys.clone(),
 			},
 		)
 	}
@@ -38,26 +39,75 @@ impl<S> Layer<S> for AuthorizationLayer {
 
 #[derive(Clone)]
 pub struct AuthorizationHandler {
-	api_key: Arc<str>,
+	keys: ApiKeyService,
 }
 
-impl<B> AsyncAuthorizeRequest<B> for AuthorizationHandler {
+impl<B> AsyncAuthorizeRequest<B> for AuthorizationHandler
+where
+	B: Send + 'static,
+{
 	type RequestBody = B;
 	type ResponseBody = BoxBody;
-	type Future = Ready<Result<Request<B>, Response<Self::ResponseBody>>>;
-
-	fn authorize(&mut self, request: Request<B>) -> Self::Future {
-		let api_key = request
-			.headers()
-			.get("x-api-key")
-			.and_then(|h| h.to_str().ok());
-
-		let authorized = api_key == Some(&*self.api_key);
-		if authorized {
-			ready(Ok(request))
-		} else {
-			let response = (StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
-			ready(Err(response))
+	type Future = BoxFuture<'static, Result<Request<B>, Response<Self::ResponseBody>>>;
+
+	fn authorize(&mut self, mut request: Request<B>) -> Self::Future {
+		let keys = self.keys.clone();
+
+		Box::pin(async move {
+			let Some(api_key) = request
+				.headers()
+				.get("x-api-key")
+				.and_then(|h| h.to_str().ok())
+			else {
+				return Err((StatusCode::UNAUTHORIZED, "Unauthorized").into_response());
+			};
+
+			let user = match keys.authenticate(api_key).await {
+				Ok(user) => user,
+				Err(ApiKeyError::Expired) => {
+					return Err((StatusCode::UNAUTHORIZED, "API key expired").into_response())
+				}
+				Err(ApiKeyError::DbErr(e)) => {
+					error!("failed to check api key: {e}");
+					return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response());
+				}
+				Err(_) => return Err((StatusCode::UNAUTHORIZED, "Unauthorized").into_response()),
+			};
+
+			if !user.scopes.contains(Scope::Read) {
+				return Err(missing_scope(Scope::Read));
+			}
+
+			request.extensions_mut().insert(user);
+			Ok(request)
+		})
+	}
+}
+
+fn missing_scope(scope: Scope) -> Response {
+	let message = format!("The API key lacks the `{}` scope", scope.as_str());
+	(StatusCode::FORBIDDEN, message).into_response()
+}
+
+/// Checks that the api key of a request may make changes.
+pub struct WriteAccess(pub ApiUser);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for WriteAccess
+where
+	S: Send + Sync,
+{
+	type Rejection = Response;
+
+	async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+		let Some(user) = parts.extensions.get::<ApiUser>() else {
+			return Err((StatusCode::UNAUTHORIZED, "Unauthorized").into_response());
+		};
+
+		if !user.scopes.contains(Scope::Write) {
+			return Err(missing_scope(Scope::Write));
 		}
+
+		Ok(Self(user.clone()))
 	}
 }
@@ -1,7 +1,6 @@
 use axum::{async_trait, extract::FromRequestParts, http::request};
 use sea_orm::DatabaseConnection;
-use std::{convert::Infallible, sync::Arc};
-use tvdb_client::TvDbClient;
+use std::convert::Infallible;
 
 use crate::AppState;
 
@@ -18,17 +17,3 @@ impl FromRequestParts<AppState> for Db {
 		Ok(Db(state.db.clone()))
 	}
 }
-
-pub struct TvDb(pub Arc<TvDbClient>);
-
-#[async_trait]
-impl FromRequestParts<AppState> for TvDb {
-	type Rejection = Infallible;
-
-	async fn from_request_parts(
-		_parts: &mut request::Parts,
-		state: &AppState,
-	) -> Result<Self, Self::Rejection> {
-		Ok(TvDb(state.tvdb.clone()))
-	}
-}
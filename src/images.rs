@@ -0,0 +1,622 @@
+use crate::AppState;
+use axum::{
+	extract::{FromRef, Path, State},
+	http::{header, HeaderValue, StatusCode},
+	response::{IntoResponse, Response},
+	routing::get,
+	Router,
+};
+use base64::prelude::*;
+use dbost_services::{
+	movies::{MovieService, MovieServiceError},
+	series::{SeriesService, SeriesServiceError},
+};
+use image::{codecs::avif::AvifEncoder, imageops::FilterType, DynamicImage};
+use sha2::{Digest, Sha256};
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	fmt::{self, Write},
+	future::Future,
+	path::PathBuf,
+	str::FromStr,
+	sync::{Arc, Mutex, Weak},
+	time::Duration,
+};
+use thiserror::Error;
+use tokio::fs;
+use tracing::{info, warn};
+
+const UPSTREAM_HOST: &str = "https://artworks.thetvdb.com/";
+const CACHE_CONTROL: HeaderValue = HeaderValue::from_static("public, max-age=31536000, immutable");
+
+/// The widths we are willing to render. Requests for any other width are
+/// rejected, so that the cache can't be filled with arbitrary variants.
+pub const WIDTHS: [u32; 7] = [160, 320, 480, 640, 960, 1280, 1920];
+
+const WEBP_QUALITY: f32 = 80.0;
+const AVIF_QUALITY: f32 = 70.0;
+const AVIF_SPEED: u8 = 8;
+
+/// Images are shrunk to this size before computing the blurhash, the result
+/// is blurry anyways.
+const BLURHASH_SOURCE_SIZE: u32 = 32;
+const PLACEHOLDER_WIDTH: u32 = 16;
+const PLACEHOLDER_HEIGHT: u32 = 24;
+
+/// Placeholders are the same on every page, so they are rendered once and
+/// kept, by blurhash. Cleared when it grows past `MAX_PLACEHOLDERS`.
+static PLACEHOLDERS: Mutex<BTreeMap<String, Option<String>>> = Mutex::new(BTreeMap::new());
+const MAX_PLACEHOLDERS: usize = 10_000;
+
+#[derive(Clone)]
+pub struct ImageCache {
+	inner: Arc<ImageCacheInner>,
+}
+
+struct ImageCacheInner {
+	dir: PathBuf,
+	client: reqwest::Client,
+	series: SeriesService,
+	movies: MovieService,
+	/// Locks on the cache files being fetched or rendered, so concurrent
+	/// requests for an image that isn't cached yet only make it once.
+	filling: Mutex<HashMap<PathBuf, Weak<tokio::sync::Mutex<()>>>>,
+	/// Images whose blurhash has been computed or checked since the server
+	/// started.
+	blurhashed: Mutex<HashSet<String>>,
+}
+
+impl ImageCache {
+	pub fn new(
+		dir: impl Into<PathBuf>,
+		series: SeriesService,
+		movies: MovieService,
+	) -> Result<Self, reqwest::Error> {
+		let client = reqwest::Client::builder()
+			.user_agent(concat!(
+				env!("CARGO_PKG_NAME"),
+				"/",
+				env!("CARGO_PKG_VERSION")
+			))
+			.timeout(Duration::from_secs(30))
+			.build()?;
+
+		Ok(Self {
+			inner: Arc::new(ImageCacheInner {
+				dir: dir.into(),
+				client,
+				series,
+				movies,
+				filling: Default::default(),
+				blurhashed: Default::default(),
+			}),
+		})
+	}
+
+	fn cache_path(&self, variant: Variant, path: &str) -> PathBuf {
+		let hash = Sha256::digest(path.as_bytes());
+		let mut name = String::with_capacity(hash.len() * 2);
+		for byte in hash {
+			let _ = write!(name, "{byte:02x}");
+		}
+
+		self.inner.dir.join(variant.to_string()).join(name)
+	}
+
+	async fn read(&self, variant: Variant, path: &str) -> Option<Vec<u8>> {
+		fs::read(self.cache_path(variant, path)).await.ok()
+	}
+
+	async fn write(&self, variant: Variant, path: &str, data: &[u8]) {
+		async fn write(file: PathBuf, data: &[u8]) -> std::io::Result<()> {
+			// write to a temp file first, so concurrent readers never see a partial image
+			let tmp = file.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+			if let Some(dir) = file.parent() {
+				fs::create_dir_all(dir).await?;
+			}
+
+			fs::write(&tmp, data).await?;
+			fs::rename(&tmp, &file).await
+		}
+
+		let file = self.cache_path(variant, path);
+		if let Err(e) = write(file, data).await {
+			warn!(path, %variant, "failed to write image to cache: {e}");
+		}
+	}
+
+	/// Reads a variant from the cache, or makes it with `fill` and caches it.
+	/// Only one request at a time fills a variant, others wait for it and read
+	/// the result from the cache.
+	async fn cached(
+		&self,
+		variant: Variant,
+		path: &str,
+		fill: impl Future<Output = Result<Vec<u8>, ImageError>>,
+	) -> Result<Vec<u8>, ImageError> {
+		if let Some(data) = self.read(variant, path).await {
+			return Ok(data);
+		}
+
+		let file = self.cache_path(variant, path);
+		let lock = {
+			let mut filling = self.inner.filling.lock().unwrap();
+			filling.retain(|_, lock| lock.strong_count() > 0);
+			match filling.get(&file).and_then(Weak::upgrade) {
+				Some(lock) => lock,
+				None => {
+					let lock = Arc::new(tokio::sync::Mutex::new(()));
+					filling.insert(file, Arc::downgrade(&lock));
+					lock
+				}
+			}
+		};
+
+		let _guard = lock.lock().await;
+		if let Some(data) = self.read(variant, path).await {
+			return Ok(data);
+		}
+
+		let data = fill.await?;
+		self.write(variant, path, &data).await;
+		Ok(data)
+	}
+
+	async fn original(&self, path: &str) -> Result<Vec<u8>, ImageError> {
+		self
+			.cached(Variant::Original, path, async {
+				let url = format!("{UPSTREAM_HOST}{path}");
+
+				// only images we know about are fetched, so the cache can't be filled
+				// with anything else from upstream
+				let known =
+					self.inner.series.has_image(&url).await? || self.inner.movies.has_image(&url).await?;
+				if !known {
+					return Err(ImageError::NotFound);
+				}
+
+				info!(url, "fetching upstream image");
+				let response = self.inner.client.get(&url).send().await?;
+				let response = match response.status() {
+					reqwest::StatusCode::NOT_FOUND => return Err(ImageError::NotFound),
+					_ => response.error_for_status()?,
+				};
+
+				let data = response.bytes().await?.to_vec();
+				self
+					.inner
+					.blurhashed
+					.lock()
+					.unwrap()
+					.insert(path.to_owned());
+				self.store_blurhash(url, data.clone());
+				Ok(data)
+			})
+			.await
+	}
+
+	/// Computes the blurhash of a freshly fetched image in the background, and
+	/// stores it on the series, seasons and movies using the image.
+	fn store_blurhash(&self, url: String, data: Vec<u8>) {
+		let series = self.inner.series.clone();
+		let movies = self.inner.movies.clone();
+		tokio::spawn(async move {
+			let blurhash = tokio::task::spawn_blocking(move || blurhash(&data))
+				.await
+				.expect("blurhash computation panicked");
+
+			let result = match blurhash {
+				Ok(blurhash) => match series.set_image_blurhash(&url, blurhash.clone()).await {
+					Ok(()) => movies
+						.set_image_blurhash(&url, blurhash)
+						.await
+						.map_err(|e| e.to_string()),
+					Err(e) => Err(e.to_string()),
+				},
+				Err(e) => Err(e.to_string()),
+			};
+
+			if let Err(e) = result {
+				warn!(url, "failed to store image blurhash: {e}");
+			}
+		});
+	}
+
+	/// Computes the blurhash of a cached image in the background when something
+	/// uses the image without having one, like images that were cached before
+	/// blurhashes were. Each image is only checked once while the server runs.
+	fn ensure_blurhash(&self, path: &str) {
+		if !self
+			.inner
+			.blurhashed
+			.lock()
+			.unwrap()
+			.insert(path.to_owned())
+		{
+			return;
+		}
+
+		let cache = self.clone();
+		let path = path.to_owned();
+		tokio::spawn(async move {
+			let url = format!("{UPSTREAM_HOST}{path}");
+			let needed = match cache.inner.series.needs_image_blurhash(&url).await {
+				Ok(true) => Ok(true),
+				Ok(false) => cache
+					.inner
+					.movies
+					.needs_image_blurhash(&url)
+					.await
+					.map_err(|e| e.to_string()),
+				Err(e) => Err(e.to_string()),
+			};
+
+			match needed {
+				Ok(true) => {
+					if let Some(data) = cache.read(Variant::Original, &path).await {
+						cache.store_blurhash(url, data);
+					}
+				}
+				Ok(false) => {}
+				Err(e) => {
+					warn!(url, "failed to check image blurhash: {e}");
+					cache.inner.blurhashed.lock().unwrap().remove(&path);
+				}
+			}
+		});
+	}
+
+	async fn get(&self, variant: Variant, path: &str) -> Result<Vec<u8>, ImageError> {
+		let (width, format) = match variant {
+			Variant::Original => return self.original(path).await,
+			Variant::Resized { width, format } => (width, format),
+		};
+
+		self
+			.cached(variant, path, async {
+				let original = self.original(path).await?;
+				tokio::task::spawn_blocking(move || render(&original, width, format))
+					.await
+					.expect("image rendering panicked")
+			})
+			.await
+	}
+}
+
+impl FromRef<AppState> for ImageCache {
+	fn from_ref(input: &AppState) -> Self {
+		input.images.clone()
+	}
+}
+
+#[derive(Debug, Error)]
+enum ImageError {
+	#[error("image not found")]
+	NotFound,
+
+	#[error("upstream error: {0}")]
+	Upstream(#[from] reqwest::Error),
+
+	#[error("image error: {0}")]
+	Image(#[from] image::ImageError),
+
+	#[error("blurhash error: {0}")]
+	Blurhash(#[from] blurhash::Error),
+
+	#[error(transparent)]
+	Series(#[from] SeriesServiceError),
+
+	#[error(transparent)]
+	Movies(#[from] MovieServiceError),
+}
+
+impl IntoResponse for ImageError {
+	fn into_response(self) -> Response {
+		match self {
+			Self::NotFound => (StatusCode::NOT_FOUND, "Image not found").into_response(),
+			e @ (Self::Series(_) | Self::Movies(_)) => {
+				warn!("{e}");
+				(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+			}
+			e => {
+				warn!("{e}");
+				(StatusCode::BAD_GATEWAY, "Bad gateway").into_response()
+			}
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+	WebP,
+	Avif,
+}
+
+impl ImageFormat {
+	fn extension(self) -> &'static str {
+		match self {
+			Self::WebP => "webp",
+			Self::Avif => "avif",
+		}
+	}
+
+	pub fn mime_type(self) -> &'static str {
+		match self {
+			Self::WebP => "image/webp",
+			Self::Avif => "image/avif",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variant {
+	Original,
+	Resized { width: u32, format: ImageFormat },
+}
+
+impl fmt::Display for Variant {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Original => f.write_str("original"),
+			Self::Resized { width, format } => write!(f, "w{width}.{}", format.extension()),
+		}
+	}
+}
+
+impl FromStr for Variant {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s == "original" {
+			return Ok(Self::Original);
+		}
+
+		let (width, format) = s
+			.strip_prefix('w')
+			.and_then(|s| s.split_once('.'))
+			.ok_or(())?;
+		let width = width.parse::<u32>().map_err(|_| ())?;
+		if !WIDTHS.contains(&width) {
+			return Err(());
+		}
+
+		let format = match format {
+			"webp" => ImageFormat::WebP,
+			"avif" => ImageFormat::Avif,
+			_ => return Err(()),
+		};
+
+		Ok(Self::Resized { width, format })
+	}
+}
+
+fn render(original: &[u8], width: u32, format: ImageFormat) -> Result<Vec<u8>, ImageError> {
+	let image = image::load_from_memory(original)?;
+
+	// never upscale, just re-encode
+	let image = if width < image.width() {
+		image.resize(width, u32::MAX, FilterType::Lanczos3)
+	} else {
+		image
+	};
+
+	let image = if image.color().has_alpha() {
+		DynamicImage::ImageRgba8(image.into_rgba8())
+	} else {
+		DynamicImage::ImageRgb8(image.into_rgb8())
+	};
+
+	match format {
+		ImageFormat::WebP => {
+			let encoder = match &image {
+				DynamicImage::ImageRgba8(i) => webp::Encoder::from_rgba(i, i.width(), i.height()),
+				_ => webp::Encoder::from_rgb(image.as_bytes(), image.width(), image.height()),
+			};
+
+			Ok(encoder.encode(WEBP_QUALITY).to_vec())
+		}
+
+		ImageFormat::Avif => {
+			let mut data = Vec::new();
+			image.write_with_encoder(AvifEncoder::new_with_speed_quality(
+				&mut data,
+				AVIF_SPEED,
+				AVIF_QUALITY as u8,
+			))?;
+			Ok(data)
+		}
+	}
+}
+
+fn blurhash(original: &[u8]) -> Result<String, ImageError> {
+	let image = image::load_from_memory(original)?
+		.thumbnail(BLURHASH_SOURCE_SIZE, BLURHASH_SOURCE_SIZE)
+		.into_rgba8();
+
+	let (x, y) = match image.width() >= image.height() {
+		true => (4, 3),
+		false => (3, 4),
+	};
+
+	Ok(blurhash::encode(
+		x,
+		y,
+		image.width(),
+		image.height(),
+		image.as_raw(),
+	)?)
+}
+
+/// Renders a blurhash as a tiny inline image, to show while the real image
+/// loads.
+pub fn placeholder(blurhash: &str) -> Option<String> {
+	if let Some(placeholder) = PLACEHOLDERS.lock().unwrap().get(blurhash) {
+		return placeholder.clone();
+	}
+
+	let placeholder = render_placeholder(blurhash);
+	let mut placeholders = PLACEHOLDERS.lock().unwrap();
+	if placeholders.len() >= MAX_PLACEHOLDERS {
+		placeholders.clear();
+	}
+
+	placeholders.insert(blurhash.to_owned(), placeholder.clone());
+	placeholder
+}
+
+fn render_placeholder(blurhash: &str) -> Option<String> {
+	let pixels = blurhash::decode(blurhash, PLACEHOLDER_WIDTH, PLACEHOLDER_HEIGHT, 1.0).ok()?;
+	let data =
+		webp::Encoder::from_rgba(&pixels, PLACEHOLDER_WIDTH, PLACEHOLDER_HEIGHT).encode(WEBP_QUALITY);
+
+	Some(format!(
+		"data:image/webp;base64,{}",
+		BASE64_STANDARD.encode(&*data)
+	))
+}
+
+fn content_type(variant: Variant, path: &str) -> &'static str {
+	match variant {
+		Variant::Resized { format, .. } => format.mime_type(),
+		Variant::Original => match path.rsplit_once('.').map(|(_, ext)| ext) {
+			Some("png") => "image/png",
+			Some("webp") => "image/webp",
+			Some("gif") => "image/gif",
+			_ => "image/jpeg",
+		},
+	}
+}
+
+/// Returns the path (relative to the upstream host) for urls that can be served
+/// through the image proxy.
+fn proxy_path(url: &str) -> Option<&str> {
+	let path = url.strip_prefix(UPSTREAM_HOST)?;
+	let valid = !path.is_empty()
+		&& !path.contains(['?', '#', '\\'])
+		&& path
+			.split('/')
+			.all(|s| !s.is_empty() && s != "." && s != "..");
+
+	valid.then_some(path)
+}
+
+fn variant_url(url: &str, variant: Variant) -> String {
+	match proxy_path(url) {
+		None => url.to_owned(),
+		Some(path) => format!("/img/{variant}/{path}"),
+	}
+}
+
+/// Url of the original image served through the proxy. Urls that can't be
+/// proxied are returned as-is.
+pub fn proxied_url(url: &str) -> String {
+	variant_url(url, Variant::Original)
+}
+
+/// Url of a resized variant of the image. Urls that can't be proxied are
+/// returned as-is.
+pub fn resized_url(url: &str, width: u32, format: ImageFormat) -> String {
+	variant_url(url, Variant::Resized { width, format })
+}
+
+/// A `srcset` value with all the widths up to and including `max_width`, or
+/// `None` if the url can't be proxied.
+pub fn srcset(url: &str, max_width: u32, format: ImageFormat) -> Option<String> {
+	let path = proxy_path(url)?;
+	let mut srcset = String::new();
+	for width in WIDTHS.into_iter().filter(|w| *w <= max_width) {
+		if !srcset.is_empty() {
+			srcset.push_str(", ");
+		}
+
+		let variant = Variant::Resized { width, format };
+		let _ = write!(srcset, "/img/{variant}/{path} {width}w");
+	}
+
+	Some(srcset)
+}
+
+async fn image(
+	Path((variant, path)): Path<(String, String)>,
+	State(cache): State<ImageCache>,
+) -> Result<Response, ImageError> {
+	let path = path.trim_start_matches('/');
+	let variant = variant
+		.parse::<Variant>()
+		.map_err(|_| ImageError::NotFound)?;
+	let path = proxy_path(&format!("{UPSTREAM_HOST}{path}"))
+		.map(str::to_owned)
+		.ok_or(ImageError::NotFound)?;
+
+	let data = cache.get(variant, &path).await?;
+	cache.ensure_blurhash(&path);
+	Ok(
+		(
+			[
+				(
+					header::CONTENT_TYPE,
+					HeaderValue::from_static(content_type(variant, &path)),
+				),
+				(header::CACHE_CONTROL, CACHE_CONTROL),
+			],
+			data,
+		)
+			.into_response(),
+	)
+}
+
+pub fn router() -> Router<AppState> {
+	Router::new().route("/:variant/*path", get(image))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn variant_roundtrip() {
+		for variant in ["original", "w320.webp", "w1280.avif"] {
+			assert_eq!(variant.parse::<Variant>().unwrap().to_string(), variant);
+		}
+
+		assert!("w321.webp".parse::<Variant>().is_err());
+		assert!("w320.png".parse::<Variant>().is_err());
+	}
+
+	#[test]
+	fn blurhash_placeholder() {
+		let image = image::RgbImage::from_fn(200, 300, |x, y| {
+			image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+		});
+		let mut png = std::io::Cursor::new(Vec::new());
+		image.write_to(&mut png, image::ImageFormat::Png).unwrap();
+
+		let blurhash = blurhash(png.get_ref()).unwrap();
+		assert!(placeholder(&blurhash)
+			.unwrap()
+			.starts_with("data:image/webp;base64,"));
+		assert_eq!(placeholder("not a blurhash"), None);
+	}
+
+	#[test]
+	fn only_upstream_urls_are_proxied() {
+		assert_eq!(
+			resized_url(
+				"https://artworks.thetvdb.com/banners/v4/series/1/posters/a.jpg",
+				320,
+				ImageFormat::WebP
+			),
+			"/img/w320.webp/banners/v4/series/1/posters/a.jpg"
+		);
+
+		assert_eq!(
+			proxied_url("https://example.com/a.jpg"),
+			"https://example.com/a.jpg"
+		);
+
+		assert_eq!(
+			proxy_path("https://artworks.thetvdb.com/banners/../secret"),
+			None
+		);
+	}
+}
@@ -0,0 +1,64 @@
+use crate::AppState;
+use axum::extract::FromRef;
+use dbost_services::series::SeriesService;
+use std::{
+	collections::HashSet,
+	sync::{Arc, Mutex},
+};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// Imports series from TVDB in the background. Series are imported one at a
+/// time, so queueing a whole library doesn't flood TVDB.
+#[derive(Clone)]
+pub struct ImportQueue {
+	sender: mpsc::UnboundedSender<u64>,
+	pending: Arc<Mutex<HashSet<u64>>>,
+}
+
+impl ImportQueue {
+	pub fn new(series: SeriesService) -> Self {
+		let (sender, mut receiver) = mpsc::unbounded_channel::<u64>();
+		let pending = Arc::new(Mutex::new(HashSet::new()));
+
+		tokio::spawn({
+			let pending = pending.clone();
+			async move {
+				while let Some(id) = receiver.recv().await {
+					// imported in a task of its own, so a panicking import doesn't
+					// take the queue down with it
+					let import = tokio::spawn({
+						let series = series.clone();
+						async move { series.fetch_from_tvdb(id, None).await }
+					});
+
+					match import.await {
+						Ok(Ok(Some(_))) => info!(tvdb_id = id, "imported series"),
+						Ok(Ok(None)) => info!(tvdb_id = id, "series to import not found on TVDB"),
+						Ok(Err(e)) => warn!(tvdb_id = id, "failed to import series: {e}"),
+						Err(e) => warn!(tvdb_id = id, "failed to import series: {e}"),
+					}
+
+					pending.lock().unwrap().remove(&id);
+				}
+			}
+		});
+
+		Self { sender, pending }
+	}
+
+	/// Queues the import of a series by TVDB id, unless it is queued already.
+	pub fn enqueue(&self, id: u64) {
+		let mut pending = self.pending.lock().unwrap();
+		if pending.insert(id) && self.sender.send(id).is_err() {
+			warn!(tvdb_id = id, "import queue is closed");
+			pending.remove(&id);
+		}
+	}
+}
+
+impl FromRef<AppState> for ImportQueue {
+	fn from_ref(input: &AppState) -> Self {
+		input.imports.clone()
+	}
+}
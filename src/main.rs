@@ -2,6 +2,11 @@ mod api;
 mod assets;
 mod auth;
 mod extractors;
+mod images;
+mod imports;
+mod pagination;
+mod preconditions;
+mod rate_limit;
 mod utils;
 mod web;
 
@@ -15,18 +20,25 @@ mod built_info {
 use auth::AuthorizationLayer;
 use axum::{
 	extract::{FromRef, State},
+	middleware::from_fn_with_state,
 	response::IntoResponse,
 	routing::get,
 	Router,
 };
 use axum_healthcheck::{HealthCheck, ResultHealthStatusExt};
 use cfg_if::cfg_if;
-use dbost_services::auth::{AuthConfig, GithubAuthConfig};
+use dbost_services::{
+	api_keys::ApiKeyService,
+	auth::{AuthConfig, GithubAuthConfig},
+	movies::MovieService,
+	series::SeriesService,
+};
 use dbost_session::{CookieConfig, SessionLayer};
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
 use std::{
 	env,
 	net::{SocketAddr, SocketAddrV4},
+	path::PathBuf,
 	process::exit,
 	sync::Arc,
 };
@@ -39,13 +51,23 @@ use tracing_subscriber::{prelude::*, EnvFilter};
 use tvdb_client::TvDbClient;
 use url::Url;
 
-use crate::assets::BuiltAssets;
+use crate::{
+	assets::BuiltAssets,
+	images::ImageCache,
+	imports::ImportQueue,
+	rate_limit::{rate_limit, rate_limit_unauthorized, RateLimiter},
+	web::LetterCache,
+};
 
 #[derive(Clone)]
 pub struct AppState {
 	db: DatabaseConnection,
 	tvdb: Arc<TvDbClient>,
 	auth: AuthConfig,
+	images: ImageCache,
+	imports: ImportQueue,
+	rate_limits: RateLimiter,
+	letters: LetterCache,
 }
 
 impl FromRef<AppState> for DatabaseConnection {
@@ -103,7 +125,6 @@ async fn axum() -> ! {
 	let database_schema = required_env_var("DATABASE_SCHEMA");
 	let web_public_path = required_env_var("WEB_PUBLIC_PATH");
 	let session_key = required_env_var("SESSION_KEY");
-	let api_key = required_env_var("API_KEY");
 	let tvdb_api_key = required_env_var("TVDB_API_KEY");
 	let tvdb_user_pin = required_env_var("TVDB_USER_PIN");
 	let github_client_id = required_env_var("GITHUB_CLIENT_ID");
@@ -114,6 +135,13 @@ async fn axum() -> ! {
 	let secure_cookies = required_env_var("SECURE_COOKIES")
 		.parse::<bool>()
 		.expect("SECURE_COOKIES must be a boolean");
+	let trust_forwarded_for = env::var("TRUST_FORWARDED_FOR")
+		.map(|v| {
+			v.parse::<bool>()
+				.expect("TRUST_FORWARDED_FOR must be a boolean")
+		})
+		.unwrap_or(false);
+	let image_cache_path = PathBuf::from(required_env_var("IMAGE_CACHE_PATH"));
 
 	BuiltAssets::init(&web_public_path).await.unwrap();
 
@@ -139,10 +167,35 @@ async fn axum() -> ! {
 		))
 		.build();
 
+	let images = ImageCache::new(
+		image_cache_path,
+		SeriesService {
+			db: db.clone(),
+			tvdb: tvdb.clone(),
+		},
+		MovieService {
+			db: db.clone(),
+			tvdb: tvdb.clone(),
+		},
+	)
+	.unwrap();
+
+	let imports = ImportQueue::new(SeriesService {
+		db: db.clone(),
+		tvdb: tvdb.clone(),
+	});
+
+	let api_keys = ApiKeyService { db: db.clone() };
+	let rate_limits = RateLimiter::new(trust_forwarded_for);
+
 	let state = AppState {
 		db,
 		tvdb,
 		auth: auth_service,
+		images,
+		imports,
+		rate_limits: rate_limits.clone(),
+		letters: LetterCache::default(),
 	};
 
 	let ctrl_c = signal(SignalKind::terminate()).expect("register for ctrl+c failed");
@@ -167,9 +220,17 @@ async fn axum() -> ! {
 				)
 			}),
 		)
+		.nest("/img", images::router())
 		.nest(
 			"/api",
-			api::router().route_layer(AuthorizationLayer::new(api_key)),
+			api::router()
+				.route_layer(from_fn_with_state(rate_limits.clone(), rate_limit))
+				.route_layer(AuthorizationLayer::new(api_keys))
+				.route_layer(from_fn_with_state(
+					rate_limits.clone(),
+					rate_limit_unauthorized,
+				))
+				.merge(api::public_router().route_layer(from_fn_with_state(rate_limits, rate_limit))),
 		)
 		.merge(web::router().route_layer(SessionLayer::new(
 			&session_key,
@@ -208,7 +269,7 @@ async fn axum() -> ! {
 	info!(addr = %addr, "starting server");
 
 	let err = axum::Server::bind(&SocketAddr::V4(addr))
-		.serve(router.into_make_service())
+		.serve(router.into_make_service_with_connect_info::<SocketAddr>())
 		.with_graceful_shutdown(ct.cancelled_owned())
 		.await // runs forever(ish)
 		.unwrap_err();
@@ -0,0 +1,291 @@
+use axum::http::{HeaderValue, Uri};
+use base64::prelude::*;
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{fmt, marker::PhantomData};
+
+/// Number of items shown per page in the html listings.
+pub const PAGE_SIZE: u64 = 60;
+
+/// An opaque keyset pagination cursor, pointing at the last item of the
+/// previous page. The key is serialized as json and base64 encoded, so
+/// clients only ever pass it back as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor<K>(K);
+
+impl<K> Cursor<K> {
+	pub fn new(key: K) -> Self {
+		Self(key)
+	}
+
+	pub fn into_key(self) -> K {
+		self.0
+	}
+}
+
+impl<K: Serialize> Cursor<K> {
+	pub fn encode(&self) -> String {
+		let json = serde_json::to_vec(&self.0).expect("cursor keys serialize to json");
+		BASE64_URL_SAFE_NO_PAD.encode(json)
+	}
+}
+
+impl<K: DeserializeOwned> Cursor<K> {
+	pub fn decode(value: &str) -> Option<Self> {
+		let json = BASE64_URL_SAFE_NO_PAD.decode(value).ok()?;
+		serde_json::from_slice(&json).ok().map(Self)
+	}
+}
+
+impl<'de, K: DeserializeOwned> Deserialize<'de> for Cursor<K> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		struct CursorVisitor<K>(PhantomData<K>);
+
+		impl<'de, K: DeserializeOwned> serde::de::Visitor<'de> for CursorVisitor<K> {
+			type Value = Cursor<K>;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a pagination cursor")
+			}
+
+			fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+			where
+				E: serde::de::Error,
+			{
+				Cursor::decode(v).ok_or_else(|| E::custom("invalid pagination cursor"))
+			}
+		}
+
+		deserializer.deserialize_str(CursorVisitor(PhantomData))
+	}
+}
+
+/// Link to a page next to the current one. The rest of the query string
+/// (filters, sorting) is kept, and the cursor is set as the `after` parameter
+/// for the next page or the `before` parameter for the previous one.
+#[derive(Debug, Clone)]
+pub struct NextPage {
+	query: String,
+}
+
+impl NextPage {
+	pub fn new<K: Serialize>(url: &Uri, cursor: &Cursor<K>) -> Self {
+		Self::with_cursor(url, "after", cursor)
+	}
+
+	pub fn previous<K: Serialize>(url: &Uri, cursor: &Cursor<K>) -> Self {
+		Self::with_cursor(url, "before", cursor)
+	}
+
+	fn with_cursor<K: Serialize>(url: &Uri, param: &'static str, cursor: &Cursor<K>) -> Self {
+		let mut query: IndexMap<&str, Option<String>> =
+			serde_urlencoded::from_str(url.query().unwrap_or_default()).unwrap_or_default();
+
+		// leftover from offset pagination links
+		query.shift_remove("page");
+		query.shift_remove("after");
+		query.shift_remove("before");
+		query.insert(param, Some(cursor.encode()));
+
+		let query = serde_urlencoded::to_string(query).unwrap();
+		Self { query }
+	}
+
+	pub fn query(&self) -> &str {
+		&self.query
+	}
+
+	pub fn href(&self) -> String {
+		format!("?{}", self.query())
+	}
+}
+
+/// A page of a keyset paginated listing.
+pub struct CursorPage<T> {
+	pub items: Vec<T>,
+	pub next: Option<NextPage>,
+	pub previous: Option<NextPage>,
+}
+
+impl<T> CursorPage<T> {
+	/// Builds a page from `rows`, which must have been fetched with a limit of
+	/// `size + 1`; the extra row only signals that there is a next page.
+	pub fn new<K: Serialize>(rows: Vec<T>, size: u64, url: &Uri, key: impl Fn(&T) -> K) -> Self {
+		Self::forward(rows, size, url, false, key)
+	}
+
+	/// Builds a page from `rows` fetched after a cursor, like [`Self::new`], and
+	/// links back to the page before it.
+	pub fn after<K: Serialize>(rows: Vec<T>, size: u64, url: &Uri, key: impl Fn(&T) -> K) -> Self {
+		Self::forward(rows, size, url, true, key)
+	}
+
+	/// Builds a page from `rows` fetched before a cursor, in reverse order and
+	/// with a limit of `size + 1`.
+	pub fn before<K: Serialize>(
+		mut rows: Vec<T>,
+		size: u64,
+		url: &Uri,
+		key: impl Fn(&T) -> K,
+	) -> Self {
+		let has_more = rows.len() as u64 > size;
+		rows.truncate(size as usize);
+		rows.reverse();
+
+		let previous = match (has_more, rows.first()) {
+			(true, Some(first)) => Some(NextPage::previous(url, &Cursor::new(key(first)))),
+			_ => None,
+		};
+		let next = rows
+			.last()
+			.map(|last| NextPage::new(url, &Cursor::new(key(last))));
+
+		Self {
+			items: rows,
+			next,
+			previous,
+		}
+	}
+
+	fn forward<K: Serialize>(
+		mut rows: Vec<T>,
+		size: u64,
+		url: &Uri,
+		has_previous: bool,
+		key: impl Fn(&T) -> K,
+	) -> Self {
+		let has_more = rows.len() as u64 > size;
+		rows.truncate(size as usize);
+
+		let next = match (has_more, rows.last()) {
+			(true, Some(last)) => Some(NextPage::new(url, &Cursor::new(key(last)))),
+			_ => None,
+		};
+		let previous = match (has_previous, rows.first()) {
+			(true, Some(first)) => Some(NextPage::previous(url, &Cursor::new(key(first)))),
+			_ => None,
+		};
+
+		Self {
+			items: rows,
+			next,
+			previous,
+		}
+	}
+
+	/// Href of the next page, if any.
+	pub fn next_page_href(&self) -> Option<String> {
+		self.next.as_ref().map(NextPage::href)
+	}
+
+	/// The RFC 8288 `Link` header value pointing to the next and previous
+	/// pages, relative to the path of `url`.
+	pub fn link_header(&self, url: &Uri) -> Option<HeaderValue> {
+		let links = [(&self.next, "next"), (&self.previous, "prev")]
+			.into_iter()
+			.filter_map(|(page, rel)| {
+				let page = page.as_ref()?;
+				Some(format!("<{}?{}>; rel=\"{rel}\"", url.path(), page.query()))
+			})
+			.collect::<Vec<_>>();
+
+		match links.is_empty() {
+			true => None,
+			false => {
+				Some(HeaderValue::from_str(&links.join(", ")).expect("links are valid header values"))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Key {
+		name: String,
+		id: u32,
+	}
+
+	#[test]
+	fn cursor_roundtrip() {
+		let cursor = Cursor::new(Key {
+			name: "Attack on Titan".into(),
+			id: 42,
+		});
+
+		let encoded = cursor.encode();
+		assert!(encoded
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+		assert_eq!(Cursor::<Key>::decode(&encoded), Some(cursor));
+		assert_eq!(Cursor::<Key>::decode("not a cursor"), None);
+	}
+
+	#[test]
+	fn next_page_keeps_query() {
+		let url: Uri = "/?sort=updated&page=3&genre=action".parse().unwrap();
+		let cursor = Cursor::new(Key {
+			name: "Frieren".into(),
+			id: 1,
+		});
+
+		let page = CursorPage::new(vec![1, 2, 3], 2, &url, |_| 1);
+		assert_eq!(page.items, vec![1, 2]);
+		let next = page.next.unwrap();
+		assert_eq!(
+			next.query(),
+			format!(
+				"sort=updated&genre=action&after={}",
+				Cursor::new(1).encode()
+			)
+		);
+
+		let page = CursorPage::new(vec![1, 2], 2, &url, |_| 0);
+		assert!(page.next.is_none());
+		assert!(page.link_header(&url).is_none());
+
+		let next = NextPage::new(&url, &cursor);
+		let query: IndexMap<String, String> = serde_urlencoded::from_str(next.query()).unwrap();
+		let after = Cursor::<Key>::decode(&query["after"]).unwrap();
+		assert_eq!(after, cursor);
+	}
+
+	#[test]
+	fn pages_link_both_ways() {
+		let url: Uri = "/api/series?limit=2&after=abc".parse().unwrap();
+		let link = |page: &Option<NextPage>| page.as_ref().map(|p| p.query().to_owned());
+
+		let page = CursorPage::after(vec![3, 4, 5], 2, &url, |n| *n);
+		assert_eq!(page.items, vec![3, 4]);
+		assert_eq!(
+			link(&page.next),
+			Some(format!("limit=2&after={}", Cursor::new(4).encode()))
+		);
+		assert_eq!(
+			link(&page.previous),
+			Some(format!("limit=2&before={}", Cursor::new(3).encode()))
+		);
+		assert_eq!(
+			page.link_header(&url).unwrap(),
+			format!(
+				"</api/series?limit=2&after={}>; rel=\"next\", </api/series?limit=2&before={}>; rel=\"prev\"",
+				Cursor::new(4).encode(),
+				Cursor::new(3).encode()
+			)
+		);
+
+		// fetched backwards from before 3, and there is nothing before 1
+		let page = CursorPage::before(vec![2, 1], 2, &url, |n| *n);
+		assert_eq!(page.items, vec![1, 2]);
+		assert!(page.previous.is_none());
+		assert_eq!(
+			link(&page.next),
+			Some(format!("limit=2&after={}", Cursor::new(2).encode()))
+		);
+	}
+}
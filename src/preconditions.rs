@@ -0,0 +1,306 @@
+use crate::utils::{from_timestamp_micros, timestamp_micros};
+use axum::{
+	async_trait,
+	extract::FromRequestParts,
+	http::{header, request, HeaderMap, HeaderValue, StatusCode},
+	response::{IntoResponse, Response},
+};
+use dbost_entities::user;
+use sea_orm::prelude::TimeDateTime;
+use sha2::{Digest, Sha256};
+use std::{convert::Infallible, time::SystemTime};
+
+/// The entity tag of a row with the given `_version`: its unix timestamp in
+/// microseconds.
+pub fn entity_tag(version: TimeDateTime) -> HeaderValue {
+	let micros = timestamp_micros(version);
+	HeaderValue::from_str(&format!("\"{micros}\"")).expect("entity tags are valid header values")
+}
+
+/// Parses an entity tag made by [`entity_tag`] back into a `_version`. Weak
+/// tags are never used for the preconditions, so they don't parse.
+fn parse_entity_tag(tag: &str) -> Option<TimeDateTime> {
+	let micros = tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()?;
+	from_timestamp_micros(micros)
+}
+
+/// The `_version`s a request expects to change, from its `If-Match` header.
+/// It's `None` without the header or for `If-Match: *`, both of which match
+/// any version.
+pub struct IfMatch(pub Option<Vec<TimeDateTime>>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for IfMatch
+where
+	S: Send + Sync,
+{
+	type Rejection = (StatusCode, &'static str);
+
+	async fn from_request_parts(
+		parts: &mut request::Parts,
+		_state: &S,
+	) -> Result<Self, Self::Rejection> {
+		let Some(value) = parts.headers.get(header::IF_MATCH) else {
+			return Ok(Self(None));
+		};
+
+		let value = value
+			.to_str()
+			.map_err(|_| (StatusCode::BAD_REQUEST, "Invalid If-Match header"))?;
+
+		let tags = value.split(',').map(str::trim);
+		if tags.clone().any(|tag| tag == "*") {
+			return Ok(Self(None));
+		}
+
+		// any of the tags may match, but if none can be a version we handed out,
+		// none can match
+		let versions = tags.filter_map(parse_entity_tag).collect::<Vec<_>>();
+		if versions.is_empty() {
+			return Err((StatusCode::PRECONDITION_FAILED, "Precondition failed"));
+		}
+
+		Ok(Self(Some(versions)))
+	}
+}
+
+/// Pages are rendered differently per user and per build, and forms carry
+/// the session's CSRF token, so their tags have to differ as well.
+fn page_variant(user: Option<&user::Model>, csrf_token: Option<&str>) -> String {
+	let mut hash = Sha256::new();
+	hash.update(env!("CARGO_PKG_VERSION"));
+	hash.update(option_env!("GIT_SHA").unwrap_or_default());
+	if let Some(user) = user {
+		hash.update(user.id.as_bytes());
+	}
+	if let Some(csrf_token) = csrf_token {
+		hash.update(csrf_token);
+	}
+
+	let hash = hash.finalize();
+	hash[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The `ETag` and `Last-Modified` of a response, from the `_version` of the
+/// rows it was made from.
+#[derive(Debug, Clone)]
+pub struct Validators {
+	etag: HeaderValue,
+	modified: SystemTime,
+	private: bool,
+}
+
+impl Validators {
+	/// Validators of a single row. The tag is the same one `If-Match` takes.
+	pub fn row(version: TimeDateTime) -> Self {
+		Self {
+			etag: entity_tag(version),
+			modified: version.assume_utc().into(),
+			private: false,
+		}
+	}
+
+	/// Validators of a response made from several rows, from the newest
+	/// `_version` and how many rows there are, so removing one changes the tag
+	/// too. The tag is weak, so it's never mistaken for the version of a row.
+	/// There are none without any rows.
+	pub fn aggregate(versions: impl IntoIterator<Item = TimeDateTime>) -> Option<Self> {
+		let (newest, rows) = versions
+			.into_iter()
+			.fold((None, 0), |(newest, rows), version| {
+				(newest.max(Some(version)), rows + 1)
+			});
+
+		let newest = newest?;
+		let micros = timestamp_micros(newest);
+		let etag = HeaderValue::from_str(&format!("W/\"{micros}-{rows}\""))
+			.expect("entity tags are valid header values");
+
+		Some(Self {
+			etag,
+			modified: newest.assume_utc().into(),
+			private: false,
+		})
+	}
+
+	/// Makes the validators fit a page rendered for the given user, with forms
+	/// carrying the given CSRF token.
+	pub fn page(self, user: Option<&user::Model>, csrf_token: Option<&str>) -> Self {
+		let tag = self.etag.to_str().expect("entity tags are ascii");
+		let tag = tag.strip_suffix('"').expect("entity tags are quoted");
+		let tag = format!("{tag}-{}\"", page_variant(user, csrf_token));
+
+		Self {
+			etag: HeaderValue::from_str(&tag).expect("entity tags are valid header values"),
+			private: true,
+			..self
+		}
+	}
+
+	fn write_headers(&self, headers: &mut HeaderMap) {
+		let modified = httpdate::fmt_http_date(self.modified);
+		let cache_control = match self.private {
+			true => "private, no-cache",
+			false => "no-cache",
+		};
+
+		headers.insert(header::ETAG, self.etag.clone());
+		headers.insert(
+			header::LAST_MODIFIED,
+			HeaderValue::from_str(&modified).expect("http dates are valid header values"),
+		);
+		headers.insert(
+			header::CACHE_CONTROL,
+			HeaderValue::from_static(cache_control),
+		);
+	}
+}
+
+/// Compares entity tags the way `If-None-Match` does, where weak tags match
+/// strong ones.
+fn weak_eq(a: &str, b: &str) -> bool {
+	a.trim_start_matches("W/") == b.trim_start_matches("W/")
+}
+
+/// The `If-None-Match` and `If-Modified-Since` headers of a request, telling
+/// what the client has cached already.
+pub struct Conditional {
+	if_none_match: Option<String>,
+	if_modified_since: Option<SystemTime>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Conditional
+where
+	S: Send + Sync,
+{
+	type Rejection = Infallible;
+
+	async fn from_request_parts(
+		parts: &mut request::Parts,
+		_state: &S,
+	) -> Result<Self, Self::Rejection> {
+		// invalid headers are ignored, the full response is sent instead
+		let header = |name| parts.headers.get(name).and_then(|v| v.to_str().ok());
+
+		Ok(Self {
+			if_none_match: header(header::IF_NONE_MATCH).map(str::to_owned),
+			if_modified_since: header(header::IF_MODIFIED_SINCE)
+				.and_then(|v| httpdate::parse_http_date(v).ok()),
+		})
+	}
+}
+
+impl Conditional {
+	fn is_fresh(&self, validators: &Validators) -> bool {
+		// `If-Modified-Since` only counts without `If-None-Match`
+		if let Some(tags) = &self.if_none_match {
+			let etag = validators.etag.to_str().unwrap_or_default();
+			return tags
+				.split(',')
+				.map(str::trim)
+				.any(|tag| tag == "*" || weak_eq(tag, etag));
+		}
+
+		match self.if_modified_since {
+			// http dates are in whole seconds
+			Some(since) => httpdate::HttpDate::from(validators.modified) <= since.into(),
+			None => false,
+		}
+	}
+
+	/// Responds with `304 Not Modified` when the client has the current
+	/// version already, and with `response` otherwise. Responses without
+	/// validators are always sent in full.
+	pub fn respond<R: IntoResponse>(
+		self,
+		validators: Option<Validators>,
+		response: impl FnOnce() -> R,
+	) -> Response {
+		let Some(validators) = validators else {
+			return response().into_response();
+		};
+
+		let mut response = match self.is_fresh(&validators) {
+			true => StatusCode::NOT_MODIFIED.into_response(),
+			false => response().into_response(),
+		};
+
+		if response.status().is_success() || response.status() == StatusCode::NOT_MODIFIED {
+			validators.write_headers(response.headers_mut());
+		}
+
+		response
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use time::{Date, Month};
+
+	#[test]
+	fn entity_tag_roundtrip() {
+		let version = Date::from_calendar_date(2024, Month::May, 16)
+			.unwrap()
+			.with_hms_micro(19, 40, 52, 123456)
+			.unwrap();
+		let tag = entity_tag(version);
+		assert_eq!(tag, "\"1715888452123456\"");
+		assert_eq!(parse_entity_tag(tag.to_str().unwrap()), Some(version));
+		assert_eq!(parse_entity_tag("W/\"1715888452123456\""), None);
+		assert_eq!(parse_entity_tag("\"abc\""), None);
+	}
+
+	#[tokio::test]
+	async fn if_match_keeps_every_tag() {
+		let if_match = |value: &'static str| async move {
+			let request = axum::http::Request::builder()
+				.header(header::IF_MATCH, value)
+				.body(())
+				.unwrap();
+			let (mut parts, _) = request.into_parts();
+			IfMatch::from_request_parts(&mut parts, &()).await
+		};
+
+		let versions = if_match("W/\"1-2\", \"1715888452123456\", \"1715888452000000\"")
+			.await
+			.unwrap()
+			.0
+			.unwrap();
+		assert_eq!(versions.len(), 2);
+
+		assert!(if_match("\"1\", *").await.unwrap().0.is_none());
+		assert!(if_match("W/\"1-2\"").await.is_err());
+	}
+
+	#[test]
+	fn aggregate_changes_with_removed_rows() {
+		let at = |second| {
+			Date::from_calendar_date(2024, Month::May, 19)
+				.unwrap()
+				.with_hms(12, 0, second)
+				.unwrap()
+		};
+
+		let all = Validators::aggregate([at(1), at(3), at(2)]).unwrap();
+		let fewer = Validators::aggregate([at(1), at(3)]).unwrap();
+		assert_eq!(all.etag, "W/\"1716120003000000-3\"");
+		assert_ne!(all.etag, fewer.etag);
+		assert_eq!(all.modified, fewer.modified);
+		assert!(Validators::aggregate([]).is_none());
+
+		let conditional = |if_none_match: Option<&str>, if_modified_since| Conditional {
+			if_none_match: if_none_match.map(str::to_owned),
+			if_modified_since,
+		};
+
+		assert!(conditional(Some("\"1716120003000000-3\""), None).is_fresh(&all));
+		assert!(!conditional(Some("W/\"1716120003000000-2\""), None).is_fresh(&all));
+		assert!(conditional(None, Some(all.modified)).is_fresh(&all));
+		assert!(!conditional(None, Some(at(2).assume_utc().into())).is_fresh(&all));
+
+		let page = all.clone().page(None, None);
+		assert!(!conditional(Some("W/\"1716120003000000-3\""), None).is_fresh(&page));
+	}
+}
@@ -0,0 +1,366 @@
+use crate::AppState;
+use axum::{
+	extract::{ConnectInfo, FromRef, State},
+	http::{header, HeaderMap, HeaderName, HeaderValue, Request, StatusCode},
+	middleware::Next,
+	response::{IntoResponse, Response},
+};
+use dbost_services::api_keys::ApiUser;
+use std::{
+	collections::HashMap,
+	net::{IpAddr, SocketAddr},
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+/// Buckets are dropped once they are full again when there are more than
+/// this many, so clients that went away don't keep using memory.
+const MAX_BUCKETS: usize = 10_000;
+
+const LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+const REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+const RESET: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// How many requests can be made at once, and how fast that refills.
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+	burst: u32,
+	per_second: f64,
+}
+
+/// Who a request is charged to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Client {
+	ApiKey(Uuid),
+	/// Requests without a key, like the ones for the OpenAPI document.
+	Ip(IpAddr),
+}
+
+/// The kinds of requests that have their own budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quota {
+	Requests,
+	/// Requests that fetch from TVDB, which rate limits us in turn.
+	Tvdb,
+}
+
+impl Quota {
+	fn budget(self, client: Client) -> Budget {
+		match (self, client) {
+			(Quota::Requests, Client::ApiKey(_)) => Budget {
+				burst: 60,
+				per_second: 2.0,
+			},
+			(Quota::Requests, Client::Ip(_)) => Budget {
+				burst: 30,
+				per_second: 0.5,
+			},
+			(Quota::Tvdb, _) => Budget {
+				burst: 10,
+				per_second: 1.0 / 30.0,
+			},
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+	tokens: f64,
+	updated: Instant,
+}
+
+impl Bucket {
+	fn full(budget: Budget, now: Instant) -> Self {
+		Self {
+			tokens: budget.burst as f64,
+			updated: now,
+		}
+	}
+
+	fn refill(&mut self, budget: Budget, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+		self.tokens = (self.tokens + elapsed * budget.per_second).min(budget.burst as f64);
+		self.updated = now;
+	}
+
+	fn usage(&self, budget: Budget) -> Usage {
+		let missing = budget.burst as f64 - self.tokens;
+
+		Usage {
+			limit: budget.burst,
+			remaining: self.tokens.floor() as u32,
+			reset: Duration::from_secs_f64(missing / budget.per_second),
+			retry_after: Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / budget.per_second),
+		}
+	}
+}
+
+/// The state of a client's budget, as told in the rate limit headers.
+#[derive(Debug, Clone, Copy)]
+pub struct Usage {
+	limit: u32,
+	remaining: u32,
+	/// Until the budget is full again.
+	reset: Duration,
+	/// Until there is room for another request.
+	retry_after: Duration,
+}
+
+impl Usage {
+	fn write_headers(&self, headers: &mut HeaderMap) {
+		let secs = |d: Duration| HeaderValue::from(d.as_secs_f64().ceil() as u64);
+
+		headers.insert(LIMIT, HeaderValue::from(self.limit));
+		headers.insert(REMAINING, HeaderValue::from(self.remaining));
+		headers.insert(RESET, secs(self.reset));
+	}
+}
+
+/// Token buckets limiting how fast each client can use the api.
+#[derive(Clone)]
+pub struct RateLimiter {
+	buckets: Arc<Mutex<HashMap<(Client, Quota), Bucket>>>,
+	/// Whether the client's ip address is taken from `X-Forwarded-For`, which
+	/// is only safe behind a proxy that sets it.
+	trust_forwarded_for: bool,
+}
+
+impl FromRef<AppState> for RateLimiter {
+	fn from_ref(input: &AppState) -> Self {
+		input.rate_limits.clone()
+	}
+}
+
+impl RateLimiter {
+	pub fn new(trust_forwarded_for: bool) -> Self {
+		Self {
+			buckets: Default::default(),
+			trust_forwarded_for,
+		}
+	}
+
+	/// Takes a request from every quota, or from none of them when one of
+	/// them is used up. The usage of the first quota is returned either way.
+	fn check(&self, client: Client, quotas: &[Quota], now: Instant) -> Result<Usage, (Quota, Usage)> {
+		let mut buckets = self.buckets.lock().unwrap();
+		if buckets.len() > MAX_BUCKETS {
+			buckets.retain(|(client, quota), bucket| {
+				let budget = quota.budget(*client);
+				bucket.refill(budget, now);
+				bucket.tokens < budget.burst as f64
+			});
+		}
+
+		for quota in quotas {
+			let budget = quota.budget(client);
+			let bucket = buckets
+				.entry((client, *quota))
+				.or_insert_with(|| Bucket::full(budget, now));
+
+			bucket.refill(budget, now);
+			if bucket.tokens < 1.0 {
+				return Err((*quota, bucket.usage(budget)));
+			}
+		}
+
+		let mut usage = None;
+		for quota in quotas {
+			let budget = quota.budget(client);
+			let bucket = buckets.get_mut(&(client, *quota)).unwrap();
+			bucket.tokens -= 1.0;
+			usage.get_or_insert(bucket.usage(budget));
+		}
+
+		Ok(usage.unwrap())
+	}
+
+	/// Gives a request taken from a quota back.
+	fn refund(&self, client: Client, quota: Quota) {
+		let budget = quota.budget(client);
+		let mut buckets = self.buckets.lock().unwrap();
+		if let Some(bucket) = buckets.get_mut(&(client, quota)) {
+			bucket.tokens = (bucket.tokens + 1.0).min(budget.burst as f64);
+		}
+	}
+
+	/// Takes a request from a quota, or returns the response that turns the
+	/// request away when it's used up.
+	pub fn take(&self, client: Client, quota: Quota) -> Option<Response> {
+		let (quota, usage) = self.check(client, &[quota], Instant::now()).err()?;
+		Some(too_many_requests(quota, usage))
+	}
+
+	/// Takes as many of `wanted` requests from a quota as it has room for, and
+	/// returns how many that was.
+	pub fn take_up_to(&self, client: Client, quota: Quota, wanted: usize) -> usize {
+		let budget = quota.budget(client);
+		let now = Instant::now();
+		let mut buckets = self.buckets.lock().unwrap();
+		let bucket = buckets
+			.entry((client, quota))
+			.or_insert_with(|| Bucket::full(budget, now));
+
+		bucket.refill(budget, now);
+		let taken = (bucket.tokens.floor() as usize).min(wanted);
+		bucket.tokens -= taken as f64;
+		taken
+	}
+
+	fn client_ip(&self, headers: &HeaderMap, peer: SocketAddr) -> IpAddr {
+		// the proxy appends the address it got the request from, anything
+		// before that is up to the client
+		let forwarded = self
+			.trust_forwarded_for
+			.then(|| headers.get("x-forwarded-for")?.to_str().ok())
+			.flatten()
+			.and_then(|value| value.rsplit(',').next())
+			.and_then(|ip| ip.trim().parse().ok());
+
+		forwarded.unwrap_or(peer.ip())
+	}
+}
+
+fn too_many_requests(quota: Quota, usage: Usage) -> Response {
+	let message = match quota {
+		Quota::Requests => "Too many requests",
+		Quota::Tvdb => "Too many requests that update from TVDB",
+	};
+
+	let retry_after = usage.retry_after.as_secs_f64().ceil() as u64;
+	let mut response = (
+		StatusCode::TOO_MANY_REQUESTS,
+		[(header::RETRY_AFTER, retry_after)],
+		message,
+	)
+		.into_response();
+	usage.write_headers(response.headers_mut());
+	response
+}
+
+/// Limits requests per api key, or per ip address for requests without one.
+/// Runs after the api key is checked, and adds the [`Client`] to the request
+/// and response extensions, for handlers that fetch from TVDB to take from
+/// [`Quota::Tvdb`] as well.
+pub async fn rate_limit<B>(
+	State(limiter): State<RateLimiter>,
+	ConnectInfo(peer): ConnectInfo<SocketAddr>,
+	mut request: Request<B>,
+	next: Next<B>,
+) -> Response {
+	let client = match request.extensions().get::<ApiUser>() {
+		Some(user) => Client::ApiKey(user.key_id),
+		None => Client::Ip(limiter.client_ip(request.headers(), peer)),
+	};
+
+	let usage = match limiter.check(client, &[Quota::Requests], Instant::now()) {
+		Ok(usage) => usage,
+		Err((quota, usage)) => return too_many_requests(quota, usage),
+	};
+
+	request.extensions_mut().insert(client);
+	let mut response = next.run(request).await;
+	usage.write_headers(response.headers_mut());
+	response.extensions_mut().insert(client);
+	response
+}
+
+/// Limits requests per ip address before their api key is checked, so
+/// missing and guessed keys get the budget of anonymous requests. Once that
+/// is used up, requests from the address are turned away without looking
+/// their key up until it refills. Requests that get past the check are
+/// charged by [`rate_limit`] instead, and get their token back.
+pub async fn rate_limit_unauthorized<B>(
+	State(limiter): State<RateLimiter>,
+	ConnectInfo(peer): ConnectInfo<SocketAddr>,
+	request: Request<B>,
+	next: Next<B>,
+) -> Response {
+	let client = Client::Ip(limiter.client_ip(request.headers(), peer));
+	let usage = match limiter.check(client, &[Quota::Requests], Instant::now()) {
+		Ok(usage) => usage,
+		Err((quota, usage)) => return too_many_requests(quota, usage),
+	};
+
+	let mut response = next.run(request).await;
+	match response.extensions().get::<Client>() {
+		Some(_) => limiter.refund(client, Quota::Requests),
+		None => usage.write_headers(response.headers_mut()),
+	}
+
+	response
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bucket_refills_after_burst() {
+		let limiter = RateLimiter::new(false);
+		let client = Client::Ip([127, 0, 0, 1].into());
+		let budget = Quota::Requests.budget(client);
+		let start = Instant::now();
+
+		for _ in 0..budget.burst {
+			limiter.check(client, &[Quota::Requests], start).unwrap();
+		}
+
+		let (quota, usage) = limiter
+			.check(client, &[Quota::Requests], start)
+			.unwrap_err();
+		assert_eq!(quota, Quota::Requests);
+		assert_eq!(usage.remaining, 0);
+		assert_eq!(usage.retry_after, Duration::from_secs(2));
+
+		let later = start + Duration::from_secs(2);
+		let usage = limiter.check(client, &[Quota::Requests], later).unwrap();
+		assert_eq!(usage.remaining, 0);
+	}
+
+	#[test]
+	fn exhausted_quota_takes_nothing() {
+		let limiter = RateLimiter::new(false);
+		let client = Client::ApiKey(Uuid::nil());
+		let tvdb = Quota::Tvdb.budget(client);
+		let now = Instant::now();
+
+		assert_eq!(
+			limiter.take_up_to(client, Quota::Tvdb, 100),
+			tvdb.burst as usize
+		);
+
+		let (quota, _) = limiter
+			.check(client, &[Quota::Requests, Quota::Tvdb], now)
+			.unwrap_err();
+		assert_eq!(quota, Quota::Tvdb);
+
+		let usage = limiter.check(client, &[Quota::Requests], now).unwrap();
+		assert_eq!(usage.remaining, Quota::Requests.budget(client).burst - 1);
+	}
+
+	#[test]
+	fn refund_gives_the_request_back() {
+		let limiter = RateLimiter::new(false);
+		let client = Client::Ip([127, 0, 0, 1].into());
+		let budget = Quota::Requests.budget(client);
+		let now = Instant::now();
+
+		for _ in 0..budget.burst {
+			limiter.check(client, &[Quota::Requests], now).unwrap();
+		}
+		assert!(limiter.check(client, &[Quota::Requests], now).is_err());
+
+		limiter.refund(client, Quota::Requests);
+		let usage = limiter.check(client, &[Quota::Requests], now).unwrap();
+		assert_eq!(usage.remaining, 0);
+
+		// a refund never fills the bucket past its burst
+		let client = Client::Ip([127, 0, 0, 2].into());
+		limiter.check(client, &[Quota::Requests], now).unwrap();
+		limiter.refund(client, Quota::Requests);
+		limiter.refund(client, Quota::Requests);
+		let usage = limiter.check(client, &[Quota::Requests], now).unwrap();
+		assert_eq!(usage.remaining, budget.burst - 1);
+	}
+}
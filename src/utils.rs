@@ -1,5 +1,17 @@
+use sea_orm::prelude::TimeDateTime;
 use std::iter::Fuse;
 
+/// Unix timestamp of a database timestamp in microseconds, the precision
+/// postgres keeps.
+pub fn timestamp_micros(at: TimeDateTime) -> i64 {
+	(at.assume_utc().unix_timestamp_nanos() / 1000) as i64
+}
+
+pub fn from_timestamp_micros(micros: i64) -> Option<TimeDateTime> {
+	let at = time::OffsetDateTime::from_unix_timestamp_nanos(micros as i128 * 1000).ok()?;
+	Some(TimeDateTime::new(at.date(), at.time()))
+}
+
 pub struct Concat<I1, I2> {
 	iter1: Fuse<I1>,
 	iter2: Fuse<I2>,
@@ -1,33 +1,65 @@
 mod auth;
-mod pagination;
+mod letters;
 mod views;
 
-use self::{
-	pagination::PageNumber,
-	views::{IndexPage, SeriesCard, SeriesEdit, SeriesPage},
+pub use letters::LetterCache;
+
+use self::views::{
+	marker_themes, ActiveFilter, ApiDocsPage, Category, CategoryPage, EpisodeRow, IndexControls,
+	IndexPage, JumpLink, LetterBar, MissingThemeRow, MissingThemesPage, MovieCard, MoviePage,
+	MoviesPage, ProfilePage, SeriesArtwork, SeriesCard, SeriesEdit, SeriesPage, SeriesSeasons,
+	SeriesSort, StatsPage, ThemeFilter, ThemeFormError, ThemeFormValues, WorklistOrder,
+	KEY_LIFETIMES,
+};
+use crate::{
+	api,
+	extractors::Db,
+	pagination::{Cursor, CursorPage, PAGE_SIZE},
+	preconditions::{Conditional, Validators},
+	utils::{from_timestamp_micros, timestamp_micros, Concat},
+	AppState,
 };
-use crate::{extractors::Db, utils::Concat, web::pagination::Pagination, AppState};
 use axum::{
 	body::BoxBody,
-	extract::{OriginalUri, Path, Query},
-	http::{Response, StatusCode},
+	extract::{OriginalUri, Path, Query, State},
+	http::{Response, StatusCode, Uri},
 	response::{IntoResponse, Redirect},
-	routing::get,
-	Router,
+	routing::{get, post},
+	Form, Router,
+};
+use dbost_entities::{
+	artwork, episode, episode_marker, genre, movie, season, series, series_alias, series_genre,
+	theme_song,
 };
-use dbost_entities::{season, series, theme_song};
 use dbost_htmx::extractors::{HtmxRequestInfo, HxRequestInfo};
-use dbost_session::Session;
+use dbost_services::{
+	api_keys::{ApiKeyError, ApiKeyService, NewApiKey, Scopes},
+	markers::{self, MarkerKind, MarkerService, MarkerServiceError},
+	stats::StatsService,
+	themes::{NewTheme, ThemeService, ThemeServiceError, ThemeTarget},
+};
+use dbost_session::{CsrfToken, Session};
+use dbost_utils::OffsetDateTimeExt;
 use indexmap::IndexMap;
 use sea_orm::{
-	ColumnTrait, DatabaseConnection, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter,
-	QueryOrder, QuerySelect, RelationTrait, TransactionError,
+	prelude::TimeDateTime, ColumnTrait, Condition, DatabaseConnection, DbBackend, EntityTrait,
+	FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait,
+	TransactionError,
+};
+use sea_query::{
+	extension::postgres::PgBinOper, Alias, Expr, Func, IntoColumnRef, IntoCondition, JoinType,
+	SimpleExpr,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::{HashMap, HashSet},
+	error,
+	sync::Arc,
 };
-use sea_query::JoinType;
-use serde::Deserialize;
-use std::{error, sync::Arc};
 use thiserror::Error;
+use time::{Duration, OffsetDateTime};
 use tracing::log::warn;
+use tvdb_client::{ArtworkKind, SeasonType};
 use uuid::Uuid;
 
 #[derive(Error, Debug)]
@@ -40,6 +72,9 @@ enum WebError {
 
 	#[error("TvDb client error: {0}")]
 	TvDbError(#[from] tvdb_client::TvDbError),
+
+	#[error("Invalid CSRF token")]
+	InvalidCsrfToken,
 }
 
 impl<E> From<TransactionError<E>> for WebError
@@ -67,6 +102,8 @@ impl IntoResponse for WebError {
 			Self::TvDbError(_) => {
 				(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
 			}
+
+			Self::InvalidCsrfToken => (StatusCode::FORBIDDEN, "Invalid CSRF token").into_response(),
 		}
 	}
 }
@@ -76,59 +113,668 @@ struct SeriesCardDb {
 	name: String,
 	id: Uuid,
 	image: Option<String>,
+	image_blurhash: Option<String>,
 	season_count: i64,
+	special_count: i64,
+	version: TimeDateTime,
+	created: TimeDateTime,
+}
+
+/// Position of a series in the index listing. Sorting by anything other than
+/// the name keys on that timestamp first, with name and id as tiebreakers.
+#[derive(Serialize, Deserialize)]
+struct SeriesKey {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	at: Option<i64>,
+	name: String,
+	id: Uuid,
+}
+
+impl SeriesKey {
+	fn new(sort: SeriesSort, series: &SeriesCardDb) -> Self {
+		let at = match sort {
+			SeriesSort::Name => None,
+			SeriesSort::Updated => Some(series.version),
+			SeriesSort::Added => Some(series.created),
+		};
+
+		Self {
+			at: at.map(timestamp_micros),
+			name: series.name.clone(),
+			id: series.id,
+		}
+	}
+
+	fn at(&self) -> Option<TimeDateTime> {
+		from_timestamp_micros(self.at?)
+	}
+}
+
+/// Timestamps in cursors are kept at the microsecond precision of postgres.
+/// Movies are only listed by name.
+#[derive(Serialize, Deserialize)]
+struct MovieKey {
+	name: String,
+	id: Uuid,
+}
+
+/// `column < value OR (column = value AND tiebreak)`, for listings ordered by
+/// `column` descending before the `tiebreak` columns.
+fn after_desc(
+	column: series::Column,
+	value: impl Into<sea_orm::Value>,
+	tiebreak: SimpleExpr,
+) -> Condition {
+	let value = value.into();
+	Condition::any().add(column.lt(value.clone())).add(
+		Condition::all()
+			.add(column.eq(value))
+			.add(tiebreak.into_condition()),
+	)
+}
+
+/// `(name, id) > (key_name, key_id)`, for listings ordered by name and id.
+fn after_name_id(
+	name: impl IntoColumnRef,
+	id: impl IntoColumnRef,
+	key_name: String,
+	key_id: Uuid,
+) -> SimpleExpr {
+	Expr::tuple([Expr::col(name).into(), Expr::col(id).into()])
+		.gt(Expr::tuple([key_name.into(), key_id.into()]))
 }
 
 #[derive(Deserialize)]
 struct CallbackQuery {
-	// #[serde(default)]
-	page: PageNumber,
+	#[serde(default)]
+	after: Option<Cursor<SeriesKey>>,
+	#[serde(default)]
+	q: Option<String>,
+	#[serde(default)]
+	sort: Option<String>,
+	#[serde(default)]
+	theme: Option<String>,
+	#[serde(default)]
+	genre: Option<String>,
+	#[serde(default)]
+	year: Option<String>,
+	#[serde(default)]
+	status: Option<String>,
+	#[serde(default)]
+	network: Option<String>,
+	#[serde(default)]
+	language: Option<String>,
+	#[serde(default)]
+	letter: Option<String>,
 }
 
-async fn index(
-	Db(db): Db,
-	session: Session,
-	Query(query): Query<CallbackQuery>,
-	OriginalUri(uri): OriginalUri,
-	HxRequestInfo(hx): HxRequestInfo,
-) -> Result<Response<BoxBody>, WebError> {
-	let paginator = series::Entity::find()
+impl CallbackQuery {
+	fn filter_value(value: &Option<String>) -> Option<&str> {
+		value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+	}
+
+	fn sort(&self) -> SeriesSort {
+		Self::filter_value(&self.sort)
+			.and_then(SeriesSort::parse)
+			.unwrap_or_default()
+	}
+
+	fn theme(&self) -> Option<ThemeFilter> {
+		Self::filter_value(&self.theme).and_then(ThemeFilter::parse)
+	}
+
+	fn genre(&self) -> Option<&str> {
+		Self::filter_value(&self.genre)
+	}
+
+	fn year(&self) -> Option<i32> {
+		Self::filter_value(&self.year).and_then(|y| y.parse().ok())
+	}
+
+	fn status(&self) -> Option<&str> {
+		Self::filter_value(&self.status)
+	}
+
+	fn network(&self) -> Option<&str> {
+		Self::filter_value(&self.network)
+	}
+
+	fn language(&self) -> Option<&str> {
+		Self::filter_value(&self.language)
+	}
+
+	/// The letter jumped to, which only applies when sorting by name.
+	fn letter(&self) -> Option<char> {
+		Self::filter_value(&self.letter)
+			.and_then(|l| l.chars().next())
+			.map(|l| l.to_ascii_uppercase())
+			.filter(|l| l.is_ascii_uppercase())
+			.filter(|_| self.sort() == SeriesSort::Name)
+	}
+
+	/// Matches the series passing all of the active filters.
+	fn series_filter(&self) -> Condition {
+		let mut condition = Condition::all();
+		match self.theme() {
+			None => (),
+			Some(ThemeFilter::Has) => {
+				condition = condition.add(series::Column::ThemeSongId.is_not_null())
+			}
+			Some(ThemeFilter::Missing) => {
+				condition = condition.add(series::Column::ThemeSongId.is_null())
+			}
+		}
+
+		if let Some(genre) = self.genre() {
+			let in_genre = sea_query::Query::select()
+				.expr(Expr::val(1))
+				.from(series_genre::Entity)
+				.inner_join(
+					genre::Entity,
+					Expr::col((genre::Entity, genre::Column::Id))
+						.equals((series_genre::Entity, series_genre::Column::GenreId)),
+				)
+				.and_where(
+					Expr::col((series_genre::Entity, series_genre::Column::SeriesId))
+						.equals((series::Entity, series::Column::Id)),
+				)
+				.and_where(Expr::col((genre::Entity, genre::Column::Slug)).eq(genre))
+				.to_owned();
+
+			condition = condition.add(Expr::exists(in_genre));
+		}
+
+		if let Some(year) = self.year() {
+			condition = condition.add(series::Column::Year.eq(year));
+		}
+
+		if let Some(status) = self.status() {
+			condition = condition.add(
+				Expr::expr(Func::lower(Expr::col((
+					series::Entity,
+					series::Column::Status,
+				))))
+				.eq(status.to_lowercase()),
+			);
+		}
+
+		if let Some(network) = self.network() {
+			condition = condition.add(series::Column::NetworkSlug.eq(network));
+		}
+
+		if let Some(language) = self.language() {
+			condition = condition.add(series::Column::OriginalLanguage.eq(language));
+		}
+
+		condition
+	}
+
+	/// Describes the active filters, each with a link to the index without it.
+	async fn active_filters(
+		&self,
+		db: &DatabaseConnection,
+		uri: &Uri,
+	) -> Result<Vec<ActiveFilter>, WebError> {
+		let mut filters = Vec::new();
+		if let Some(theme) = self.theme() {
+			filters.push(("theme", theme.name().to_owned()));
+		}
+
+		if let Some(slug) = self.genre() {
+			let name = genre::Entity::find()
+				.filter(genre::Column::Slug.eq(slug))
+				.one(db)
+				.await?
+				.map(|g| g.name);
+
+			filters.push(("genre", name.unwrap_or_else(|| slug.to_owned())));
+		}
+
+		if let Some(year) = self.year() {
+			filters.push(("year", year.to_string()));
+		}
+
+		if let Some(status) = self.status() {
+			filters.push(("status", status.to_owned()));
+		}
+
+		if let Some(slug) = self.network() {
+			let name = series::Entity::find()
+				.select_only()
+				.column(series::Column::Network)
+				.filter(series::Column::NetworkSlug.eq(slug))
+				.into_tuple::<Option<String>>()
+				.one(db)
+				.await?
+				.flatten();
+
+			filters.push(("network", name.unwrap_or_else(|| slug.to_owned())));
+		}
+
+		if let Some(language) = self.language() {
+			filters.push(("language", language.to_owned()));
+		}
+
+		Ok(
+			filters
+				.into_iter()
+				.map(|(key, label)| ActiveFilter {
+					label,
+					remove_href: without_query_param(uri, key),
+				})
+				.collect(),
+		)
+	}
+
+	/// The state of the index controls. Filters without a control of their own
+	/// are passed along as hidden fields, so changing the controls keeps them.
+	async fn controls(&self, db: &DatabaseConnection) -> Result<IndexControls, WebError> {
+		let genres = genre::Entity::find()
+			.order_by_asc(genre::Column::Name)
+			.all(db)
+			.await?;
+
+		let hidden = [
+			("q", Self::filter_value(&self.q)),
+			("status", self.status()),
+			("network", self.network()),
+			("language", self.language()),
+			("letter", Self::filter_value(&self.letter)),
+		]
+		.into_iter()
+		.filter_map(|(name, value)| Some((name, value?.to_owned())))
+		.collect();
+
+		Ok(IndexControls {
+			sort: self.sort(),
+			theme: self.theme(),
+			genre: self.genre().map(str::to_owned),
+			year: self.year(),
+			genres,
+			hidden,
+		})
+	}
+
+	/// The letter bar, linking every letter that some of the `condition`
+	/// matching series start with. Jumping sorts by name and starts over from
+	/// that letter.
+	async fn letter_bar(
+		&self,
+		db: &DatabaseConnection,
+		letters: &LetterCache,
+		condition: Condition,
+		uri: &Uri,
+	) -> Result<LetterBar, WebError> {
+		let newest = series::Entity::find()
+			.select_only()
+			.column_as(Expr::col(series::Column::Version).max(), "newest")
+			.into_tuple::<Option<TimeDateTime>>()
+			.one(db)
+			.await?
+			.flatten();
+
+		let query = series::Entity::find()
+			.select_only()
+			.column_as(
+				Expr::expr(Func::upper(Func::cust(Alias::new("left")).args([
+					Expr::col((series::Entity, series::Column::Name)).into(),
+					Expr::val(1).into(),
+				]))),
+				"initial",
+			)
+			.distinct()
+			.filter(condition);
+
+		// the filters are part of the query, so its sql is the key
+		let key = query.build(DbBackend::Postgres).to_string();
+		let initials = match letters.get(&key, newest) {
+			Some(initials) => initials,
+			None => {
+				let initials = query.into_tuple::<String>().all(db).await?;
+				let initials: Arc<HashSet<char>> = Arc::new(
+					initials
+						.iter()
+						.filter_map(|initial| initial.chars().next())
+						.collect(),
+				);
+
+				letters.insert(key, newest, initials.clone());
+				initials
+			}
+		};
+
+		// names starting with digits or symbols sort first, so this is the top
+		let top = JumpLink {
+			letter: '#',
+			href: letter_href(uri, None),
+			available: true,
+		};
+
+		let letters = ('A'..='Z').map(|letter| JumpLink {
+			letter,
+			href: letter_href(uri, Some(letter)),
+			available: initials.contains(&letter),
+		});
+
+		Ok(LetterBar {
+			active: self.letter(),
+			links: std::iter::once(top).chain(letters).collect(),
+		})
+	}
+}
+
+/// Links to the same page without the given query parameter, starting over at
+/// the first page.
+fn without_query_param(uri: &Uri, key: &str) -> String {
+	let mut query: IndexMap<&str, Option<String>> =
+		serde_urlencoded::from_str(uri.query().unwrap_or_default()).unwrap_or_default();
+	query.shift_remove(key);
+	query.shift_remove("page");
+	query.shift_remove("after");
+
+	let query = serde_urlencoded::to_string(query).unwrap();
+	match query.is_empty() {
+		true => uri.path().to_owned(),
+		false => format!("{}?{query}", uri.path()),
+	}
+}
+
+/// Links to the index jumped to `letter` (or back to the top), sorted by name.
+fn letter_href(uri: &Uri, letter: Option<char>) -> String {
+	let mut query: IndexMap<&str, Option<String>> =
+		serde_urlencoded::from_str(uri.query().unwrap_or_default()).unwrap_or_default();
+	query.shift_remove("page");
+	query.shift_remove("after");
+	query.shift_remove("sort");
+	match letter {
+		None => query.shift_remove("letter"),
+		Some(letter) => query.insert("letter", Some(letter.to_string())),
+	};
+
+	let query = serde_urlencoded::to_string(query).unwrap();
+	match query.is_empty() {
+		true => uri.path().to_owned(),
+		false => format!("{}?{query}", uri.path()),
+	}
+}
+
+/// Matches series where either the name or one of the aliases is similar to
+/// the search term, using the trigram indices.
+fn series_search(term: &str) -> Condition {
+	let alias_matches = sea_query::Query::select()
+		.expr(Expr::val(1))
+		.from(series_alias::Entity)
+		.and_where(
+			Expr::col((series_alias::Entity, series_alias::Column::SeriesId))
+				.equals((series::Entity, series::Column::Id)),
+		)
+		.and_where(Expr::val(term).binary(
+			PgBinOper::WordSimilarity,
+			Expr::col((series_alias::Entity, series_alias::Column::Name)),
+		))
+		.to_owned();
+
+	Condition::any()
+		.add(Expr::val(term).binary(
+			PgBinOper::WordSimilarity,
+			Expr::col((series::Entity, series::Column::Name)),
+		))
+		.add(Expr::exists(alias_matches))
+}
+
+/// Counts the joined seasons, either the specials (season 0) or the regular
+/// seasons.
+fn season_count(specials: bool) -> SimpleExpr {
+	let number = Expr::col((season::Entity, season::Column::Number));
+	let is_special = match specials {
+		true => number.eq(0),
+		false => number.ne(0),
+	};
+
+	Func::count(Expr::case(
+		is_special,
+		Expr::col((season::Entity, season::Column::Id)),
+	))
+	.into()
+}
+
+/// Fetches a page of series cards matching `condition`, starting after the
+/// `after` cursor, with the last card linking to the next page for infinite
+/// scrolling. Returns `None` if the cursor doesn't fit the sort order.
+async fn series_cards_page(
+	db: &DatabaseConnection,
+	condition: Condition,
+	sort: SeriesSort,
+	after: Option<SeriesKey>,
+	uri: &Uri,
+) -> Result<Option<Vec<SeriesCard>>, WebError> {
+	let sort_column = match sort {
+		SeriesSort::Name => None,
+		SeriesSort::Updated => Some(series::Column::Version),
+		SeriesSort::Added => Some(series::Column::Created),
+	};
+
+	let after = match after {
+		None => None,
+		Some(key) => {
+			let name_id = after_name_id(
+				(series::Entity, series::Column::Name),
+				(series::Entity, series::Column::Id),
+				key.name.clone(),
+				key.id,
+			);
+
+			match (sort_column, key.at()) {
+				(None, _) => Some(name_id.into_condition()),
+				(Some(column), Some(at)) => Some(after_desc(column, at, name_id)),
+				(Some(_), None) => return Ok(None),
+			}
+		}
+	};
+
+	let rows = series::Entity::find()
 		.select_only()
 		.column(series::Column::Name)
 		.column(series::Column::Id)
 		.column(series::Column::Image)
-		.column_as(season::Column::Id.count(), "season_count")
-		.join(JoinType::LeftJoin, series::Relation::Season.def())
-		.filter(season::Column::Number.ne(0))
+		.column(series::Column::ImageBlurhash)
+		.column_as(series::Column::Version, "version")
+		.column_as(series::Column::Created, "created")
+		.column_as(season_count(false), "season_count")
+		.column_as(season_count(true), "special_count")
+		.join(
+			JoinType::LeftJoin,
+			// filtering in the join, so series without (official) seasons are kept
+			series::Relation::Season.def().on_condition(|_, season| {
+				Expr::col((season, season::Column::SeasonType))
+					.eq(SeasonType::Official.as_str())
+					.into_condition()
+			}),
+		)
+		.filter(condition)
+		.filter(after.unwrap_or_else(Condition::all))
 		.group_by(series::Column::Id)
+		.apply_if(sort_column, |q, column| q.order_by_desc(column))
 		.order_by_asc(series::Column::Name)
+		.order_by_asc(series::Column::Id)
+		.limit(PAGE_SIZE + 1)
 		.into_model::<SeriesCardDb>()
-		.paginate(&db, 60);
+		.all(db)
+		.await?;
+
+	let page = CursorPage::new(rows, PAGE_SIZE, uri, |last| SeriesKey::new(sort, last));
+	let next_page_link: Option<Arc<str>> = page.next_page_href().map(Arc::from);
+	let series_count = page.items.len();
+	let series = page
+		.items
+		.into_iter()
+		.enumerate()
+		.map(|(i, s)| {
+			let next_page_link = match i == series_count - 1 {
+				true => next_page_link.clone(),
+				false => None,
+			};
+
+			SeriesCard::new(
+				s.name,
+				s.id,
+				s.image,
+				s.image_blurhash,
+				s.season_count,
+				s.special_count,
+				next_page_link,
+			)
+		})
+		.collect();
+
+	Ok(Some(series))
+}
+
+async fn index(
+	Db(db): Db,
+	session: Session,
+	State(letter_cache): State<LetterCache>,
+	Query(mut query): Query<CallbackQuery>,
+	OriginalUri(uri): OriginalUri,
+	HxRequestInfo(hx): HxRequestInfo,
+) -> Result<Response<BoxBody>, WebError> {
+	let after = query.after.take().map(Cursor::into_key);
+	let search = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+	let condition = query.series_filter().add_option(search.map(series_search));
+
+	// starting at the letter, later pages continue from their cursor
+	let jump = query
+		.letter()
+		.map(|letter| series::Column::Name.gte(letter.to_string()));
 
-	let pages = paginator.num_pages().await?;
-	if pages > 0 && query.page >= pages {
+	let page_condition = condition.clone().add_option(jump);
+	let Some(series) = series_cards_page(&db, page_condition, query.sort(), after, &uri).await?
+	else {
 		return Ok((StatusCode::NOT_FOUND, "Page not found").into_response());
+	};
+
+	// the controls and letter bar swap in the results from the full page, as
+	// does history restoration
+	let fragment = hx.is_some_and(|hx| {
+		!hx.boosted && !hx.history_restore_request && hx.target.as_deref() != Some("series-results")
+	});
+
+	if fragment {
+		return Ok(IndexPage::items_fragment_response(series));
 	}
 
-	let pagination = Pagination::new(pages, query.page, uri);
+	let filters = query.active_filters(&db, &uri).await?;
+	let controls = query.controls(&db).await?;
+	let letters = query
+		.letter_bar(&db, &letter_cache, condition, &uri)
+		.await?;
+	let index = IndexPage::new(&session, series, search, filters, controls, letters);
+	Ok(index.into_response())
+}
 
-	let next_page_link: Option<Arc<str>> = pagination.next_page_href().map(Arc::from);
-	let series = paginator.fetch_page(query.page.index()).await?;
-	let series_count = series.len();
-	let series = series.into_iter().enumerate().map(|(i, s)| {
-		let next_page_link = match i == series_count - 1 {
-			true => next_page_link.clone(),
-			false => None,
-		};
+#[derive(Deserialize)]
+struct CategoryQuery {
+	#[serde(default)]
+	after: Option<Cursor<SeriesKey>>,
+}
 
-		SeriesCard::new(s.name, s.id, s.image, s.season_count, next_page_link)
-	});
+/// Lists the series in a genre or on a network, along with how many of them
+/// still need a theme song.
+async fn category_view(
+	db: DatabaseConnection,
+	session: Session,
+	category: Category,
+	condition: Condition,
+	after: Option<SeriesKey>,
+	uri: Uri,
+	hx: Option<HtmxRequestInfo>,
+) -> Result<Response<BoxBody>, WebError> {
+	let Some(series) =
+		series_cards_page(&db, condition.clone(), SeriesSort::Name, after, &uri).await?
+	else {
+		return Ok((StatusCode::NOT_FOUND, "Page not found").into_response());
+	};
+
+	let page = CategoryPage::new(&session, category, series);
+	if let Some(hx) = hx {
+		if !hx.boosted {
+			return Ok(page.into_items_fragment_response());
+		}
+	}
 
-	let index = IndexPage::new(&session, series);
+	let total = series::Entity::find()
+		.filter(condition.clone())
+		.count(&db)
+		.await?;
 
-	match hx {
-		Some(hx) if !hx.boosted => Ok(index.into_items_fragment_response()),
-		_ => Ok(index.into_response()),
+	let missing_themes = series::Entity::find()
+		.filter(condition)
+		.filter(series::Column::ThemeSongId.is_null())
+		.count(&db)
+		.await?;
+
+	Ok(page.with_counts(total, missing_themes).into_response())
+}
+
+async fn genre(
+	Path(slug): Path<String>,
+	Db(db): Db,
+	session: Session,
+	Query(query): Query<CategoryQuery>,
+	OriginalUri(uri): OriginalUri,
+	HxRequestInfo(hx): HxRequestInfo,
+) -> Result<Response<BoxBody>, WebError> {
+	let genre = genre::Entity::find()
+		.filter(genre::Column::Slug.eq(&*slug))
+		.one(&db)
+		.await?
+		.ok_or(WebError::NotFound)?;
+
+	let in_genre = series_genre::Entity::find()
+		.select_only()
+		.column(series_genre::Column::SeriesId)
+		.filter(series_genre::Column::GenreId.eq(genre.id))
+		.into_query();
+
+	let condition = Condition::all().add(series::Column::Id.in_subquery(in_genre));
+	let category = Category::Genre(genre.name);
+	let after = query.after.map(Cursor::into_key);
+	category_view(db, session, category, condition, after, uri, hx).await
+}
+
+async fn network(
+	Path(slug): Path<String>,
+	Db(db): Db,
+	session: Session,
+	Query(query): Query<CategoryQuery>,
+	OriginalUri(uri): OriginalUri,
+	HxRequestInfo(hx): HxRequestInfo,
+) -> Result<Response<BoxBody>, WebError> {
+	let name = series::Entity::find()
+		.select_only()
+		.column(series::Column::Network)
+		.filter(series::Column::NetworkSlug.eq(&*slug))
+		.into_tuple::<Option<String>>()
+		.one(&db)
+		.await?
+		.flatten()
+		.ok_or(WebError::NotFound)?;
+
+	let condition = Condition::all().add(series::Column::NetworkSlug.eq(&*slug));
+	let category = Category::Network(name);
+	let after = query.after.map(Cursor::into_key);
+	category_view(db, session, category, condition, after, uri, hx).await
+}
+
+#[derive(Deserialize)]
+struct SeriesQuery {
+	#[serde(default)]
+	order: Option<String>,
+}
+
+impl SeriesQuery {
+	fn season_type(&self) -> Option<SeasonType> {
+		self.order.as_deref().and_then(|o| o.parse().ok())
 	}
 }
 
@@ -136,8 +782,10 @@ async fn series_view(
 	series_id: Uuid,
 	db: DatabaseConnection,
 	session: Session,
-	_: Option<HtmxRequestInfo>,
+	csrf_token: CsrfToken,
+	conditional: Conditional,
 	edit: SeriesEdit,
+	order: Option<SeasonType>,
 ) -> Result<Response<BoxBody>, WebError> {
 	let series = series::Entity::find_by_id(series_id)
 		.one(&db)
@@ -150,9 +798,67 @@ async fn series_view(
 		.all(&db)
 		.await?;
 
+	let mut available = seasons
+		.iter()
+		.filter_map(|s| s.season_type.parse::<SeasonType>().ok())
+		.collect::<Vec<_>>();
+	available.sort();
+	available.dedup();
+
+	// fall back to the aired order (which sorts first) for unknown orderings
+	let selected = order
+		.filter(|o| available.contains(o))
+		.or_else(|| available.first().copied())
+		.unwrap_or_default();
+
+	let (specials, seasons): (Vec<_>, Vec<_>) = seasons.into_iter().partition(|s| s.number == 0);
+	let seasons = seasons
+		.into_iter()
+		.filter(|s| s.season_type == selected.as_str())
+		.collect::<Vec<_>>();
+
+	// specials are usually only listed in the aired order, so fall back to
+	// those for orderings that don't have their own
+	let specials_type = match specials.iter().any(|s| s.season_type == selected.as_str()) {
+		true => selected,
+		false => SeasonType::Official,
+	};
+	let specials = specials
+		.into_iter()
+		.filter(|s| s.season_type == specials_type.as_str())
+		.collect::<Vec<_>>();
+
+	let mut episodes = HashMap::<Uuid, Vec<episode::Model>>::new();
+	for episode in episode::Entity::find()
+		.filter(episode::Column::SeasonId.is_in(seasons.iter().chain(&specials).map(|s| s.id)))
+		.order_by_asc(episode::Column::Number)
+		.all(&db)
+		.await?
+	{
+		episodes.entry(episode.season_id).or_default().push(episode);
+	}
+
+	let mut markers = HashMap::<i32, Vec<episode_marker::Model>>::new();
+	for marker in episode_marker::Entity::find()
+		.filter(
+			episode_marker::Column::EpisodeTvdbId.is_in(episodes.values().flatten().map(|e| e.tvdb_id)),
+		)
+		.order_by_asc(episode_marker::Column::Kind)
+		.all(&db)
+		.await?
+	{
+		markers
+			.entry(marker.episode_tvdb_id)
+			.or_default()
+			.push(marker);
+	}
+
 	let theme_ids = Concat::new(
 		series.theme_song_id.into_iter(),
-		seasons.iter().filter_map(|s| s.theme_song_id),
+		seasons
+			.iter()
+			.chain(&specials)
+			.filter_map(|s| s.theme_song_id),
 	);
 
 	let themes = theme_song::Entity::find()
@@ -163,29 +869,734 @@ async fn series_view(
 		.map(|m| (m.id, m))
 		.collect::<IndexMap<_, _>>();
 
-	Ok(SeriesPage::new(&session, series, seasons, themes, edit).into_response())
+	let artworks = artwork::Entity::find()
+		.filter(artwork::Column::SeriesId.eq(series_id))
+		.filter(artwork::Column::SeasonId.is_null())
+		.filter(artwork::Column::Kind.is_in([
+			u8::from(ArtworkKind::SeriesBackground) as i16,
+			u8::from(ArtworkKind::SeriesClearLogo) as i16,
+		]))
+		.all(&db)
+		.await?;
+
+	let artwork = SeriesArtwork {
+		background: best_artwork(&artworks, ArtworkKind::SeriesBackground),
+		logo: best_artwork(&artworks, ArtworkKind::SeriesClearLogo),
+	};
+
+	let seasons = SeriesSeasons {
+		seasons,
+		specials,
+		episodes,
+		markers,
+		orderings: available,
+		selected,
+	};
+
+	let genres = genre::Entity::find()
+		.inner_join(series_genre::Entity)
+		.filter(series_genre::Column::SeriesId.eq(series_id))
+		.order_by_asc(genre::Column::Name)
+		.all(&db)
+		.await?;
+
+	let shown = seasons.seasons.iter().chain(&seasons.specials);
+	let versions = [series.version]
+		.into_iter()
+		.chain(shown.map(|s| s.version))
+		.chain(themes.values().map(|t| t.version))
+		.chain(seasons.markers.values().flatten().map(|m| m.version));
+	let validators = Validators::aggregate(versions)
+		.map(|v| v.page(session.user().as_deref(), Some(csrf_token.as_ref())));
+
+	Ok(conditional.respond(validators, || {
+		SeriesPage::new(
+			&session,
+			&csrf_token,
+			series,
+			genres,
+			seasons,
+			themes,
+			artwork,
+			edit,
+		)
+		.into_response()
+	}))
+}
+
+/// Picks the highest scoring artwork of the given kind, preferring english
+/// artwork over artwork without text, and both over other languages.
+fn best_artwork(artworks: &[artwork::Model], kind: ArtworkKind) -> Option<String> {
+	let kind = u8::from(kind) as i16;
+	artworks
+		.iter()
+		.filter(|a| a.kind == kind)
+		.max_by_key(|a| {
+			let language = match a.language.as_deref() {
+				Some("eng") => 2,
+				None => 1,
+				Some(_) => 0,
+			};
+
+			(language, a.score)
+		})
+		.map(|a| a.url.clone())
 }
 
 async fn series(
 	Path(series_id): Path<Uuid>,
 	Db(db): Db,
 	session: Session,
+	csrf_token: CsrfToken,
+	Query(query): Query<SeriesQuery>,
 	HxRequestInfo(hx): HxRequestInfo,
+	conditional: Conditional,
 ) -> Result<Response<BoxBody>, WebError> {
-	series_view(series_id, db, session, hx, SeriesEdit::None).await
+	// page views prioritise the missing themes worklist, partial updates don't
+	// count
+	if hx.as_ref().is_none_or(|hx| hx.boosted) {
+		series::Entity::update_many()
+			.col_expr(
+				series::Column::ViewCount,
+				Expr::col(series::Column::ViewCount).add(1),
+			)
+			.filter(series::Column::Id.eq(series_id))
+			.exec(&db)
+			.await?;
+	}
+
+	series_view(
+		series_id,
+		db,
+		session,
+		csrf_token,
+		conditional,
+		SeriesEdit::None,
+		query.season_type(),
+	)
+	.await
 }
 
 async fn series_edit_series(
 	Path(series_id): Path<Uuid>,
 	Db(db): Db,
 	session: Session,
-	HxRequestInfo(hx): HxRequestInfo,
+	csrf_token: CsrfToken,
+	Query(query): Query<SeriesQuery>,
+	conditional: Conditional,
 ) -> Result<Response<BoxBody>, WebError> {
 	if session.user().is_none() {
 		return Ok(Redirect::to(&format!("/series/{series_id}")).into_response());
 	}
 
-	series_view(series_id, db, session, hx, SeriesEdit::Series).await
+	series_view(
+		series_id,
+		db,
+		session,
+		csrf_token,
+		conditional,
+		SeriesEdit::Series,
+		query.season_type(),
+	)
+	.await
+}
+
+#[derive(Deserialize)]
+struct MoviesQuery {
+	#[serde(default)]
+	after: Option<Cursor<MovieKey>>,
+}
+
+async fn movies(
+	Db(db): Db,
+	session: Session,
+	Query(query): Query<MoviesQuery>,
+	OriginalUri(uri): OriginalUri,
+	HxRequestInfo(hx): HxRequestInfo,
+) -> Result<Response<BoxBody>, WebError> {
+	let after = query.after.map(|cursor| {
+		let key = cursor.into_key();
+		after_name_id(movie::Column::Name, movie::Column::Id, key.name, key.id)
+	});
+
+	let rows = movie::Entity::find()
+		.apply_if(after, QueryFilter::filter)
+		.order_by_asc(movie::Column::Name)
+		.order_by_asc(movie::Column::Id)
+		.limit(PAGE_SIZE + 1)
+		.all(&db)
+		.await?;
+
+	let page = CursorPage::new(rows, PAGE_SIZE, &uri, |last| MovieKey {
+		name: last.name.clone(),
+		id: last.id,
+	});
+
+	let next_page_link: Option<Arc<str>> = page.next_page_href().map(Arc::from);
+	let movie_count = page.items.len();
+	let movies = page.items.into_iter().enumerate().map(|(i, m)| {
+		let next_page_link = match i == movie_count - 1 {
+			true => next_page_link.clone(),
+			false => None,
+		};
+
+		MovieCard::new(m, next_page_link)
+	});
+
+	let page = MoviesPage::new(&session, movies);
+	match hx {
+		Some(hx) if !hx.boosted => Ok(page.into_items_fragment_response()),
+		_ => Ok(page.into_response()),
+	}
+}
+
+async fn movie(
+	Path(movie_id): Path<Uuid>,
+	Db(db): Db,
+	session: Session,
+	conditional: Conditional,
+) -> Result<Response<BoxBody>, WebError> {
+	let (movie, theme) = movie::Entity::find_by_id(movie_id)
+		.find_also_related(theme_song::Entity)
+		.one(&db)
+		.await?
+		.ok_or(WebError::NotFound)?;
+
+	let versions = [movie.version]
+		.into_iter()
+		.chain(theme.as_ref().map(|t| t.version));
+	let validators = Validators::aggregate(versions).map(|v| v.page(session.user().as_deref(), None));
+
+	Ok(conditional.respond(validators, || {
+		MoviePage::new(&session, movie, theme).into_response()
+	}))
+}
+
+#[derive(Deserialize)]
+struct MarkerForm {
+	csrf_token: String,
+	kind: String,
+	theme_song_id: Uuid,
+	start: String,
+	end: String,
+}
+
+async fn episode_add_marker(
+	Path((series_id, season_id, episode_id)): Path<(Uuid, Uuid, Uuid)>,
+	Db(db): Db,
+	session: Session,
+	csrf_token: CsrfToken,
+	markers: MarkerService,
+	HxRequestInfo(hx): HxRequestInfo,
+	Form(form): Form<MarkerForm>,
+) -> Result<Response<BoxBody>, WebError> {
+	let Some(user) = session.user() else {
+		return Ok(Redirect::to(&format!("/series/{series_id}")).into_response());
+	};
+
+	if !csrf_token.verify(&form.csrf_token) {
+		return Err(WebError::InvalidCsrfToken);
+	}
+
+	let episode = episode::Entity::find_by_id(episode_id)
+		.filter(episode::Column::SeasonId.eq(season_id))
+		.filter(episode::Column::SeriesId.eq(series_id))
+		.one(&db)
+		.await?
+		.ok_or(WebError::NotFound)?;
+
+	let (season, series) = season::Entity::find_by_id(season_id)
+		.find_also_related(series::Entity)
+		.one(&db)
+		.await?
+		.and_then(|(season, series)| Some((season, series?)))
+		.ok_or(WebError::NotFound)?;
+
+	let themes = theme_song::Entity::find()
+		.filter(
+			theme_song::Column::Id.is_in(season.theme_song_id.into_iter().chain(series.theme_song_id)),
+		)
+		.all(&db)
+		.await?;
+
+	let find_theme = |id| themes.iter().find(|t| Some(t.id) == id);
+	let themes = marker_themes(
+		find_theme(season.theme_song_id),
+		find_theme(series.theme_song_id),
+	);
+
+	let error = match (
+		form.kind.parse::<MarkerKind>(),
+		markers::parse_timestamp(&form.start),
+		markers::parse_timestamp(&form.end),
+	) {
+		_ if !themes.iter().any(|t| t.id == form.theme_song_id) => {
+			Some("The theme song does not belong to this season".to_owned())
+		}
+		(Ok(kind), Some(start_ms), Some(end_ms)) => match markers
+			.set_marker(
+				episode.tvdb_id,
+				kind,
+				form.theme_song_id,
+				start_ms,
+				end_ms,
+				Some(user.id),
+			)
+			.await
+		{
+			Ok(_) => None,
+			Err(MarkerServiceError::DbErr(e)) => return Err(e.into()),
+			Err(e @ MarkerServiceError::InvalidRange { .. }) => Some(e.to_string()),
+		},
+		(Err(e), _, _) => Some(e.to_string()),
+		_ => Some("Timestamps must look like 1:42 or 1:02:03".to_owned()),
+	};
+
+	if hx.is_none() {
+		return Ok(Redirect::to(&format!("/series/{series_id}")).into_response());
+	}
+
+	let episode_markers = episode_marker::Entity::find()
+		.filter(episode_marker::Column::EpisodeTvdbId.eq(episode.tvdb_id))
+		.order_by_asc(episode_marker::Column::Kind)
+		.all(&db)
+		.await?;
+
+	Ok(
+		EpisodeRow {
+			series_id,
+			season_id,
+			episode: &episode,
+			markers: &episode_markers,
+			themes: &themes,
+			can_edit: true,
+			csrf_token: csrf_token.as_ref(),
+			error: error.as_deref(),
+		}
+		.into_response(),
+	)
+}
+
+#[derive(Deserialize)]
+struct MissingThemesQuery {
+	#[serde(default)]
+	order: Option<String>,
+	#[serde(default)]
+	after: Option<Cursor<WorklistKey>>,
+}
+
+/// Position in the missing themes worklist: the view count or the time the
+/// series was added (in microseconds), followed by name and id.
+#[derive(Serialize, Deserialize)]
+struct WorklistKey {
+	rank: i64,
+	name: String,
+	id: Uuid,
+}
+
+const WORKLIST_PAGE_SIZE: u64 = 20;
+
+/// Seasons that are worth a theme song of their own, but don't have one.
+/// Specials and alternative orderings are left out.
+fn missing_season_condition() -> Condition {
+	Condition::all()
+		.add(season::Column::ThemeSongId.is_null())
+		.add(season::Column::SeasonType.eq(SeasonType::Official.as_str()))
+		.add(season::Column::Number.ne(0))
+}
+
+/// Loads the seasons missing a theme for each of the series.
+async fn missing_seasons(
+	db: &DatabaseConnection,
+	series_ids: impl IntoIterator<Item = Uuid>,
+) -> Result<HashMap<Uuid, Vec<season::Model>>, WebError> {
+	let mut seasons = HashMap::<Uuid, Vec<season::Model>>::new();
+	for season in season::Entity::find()
+		.filter(season::Column::SeriesId.is_in(series_ids))
+		.filter(missing_season_condition())
+		.order_by_asc(season::Column::Number)
+		.all(db)
+		.await?
+	{
+		seasons.entry(season.series_id).or_default().push(season);
+	}
+
+	Ok(seasons)
+}
+
+/// Worklist for contributors, listing the series that are missing their own
+/// theme song or one for any of their seasons.
+async fn missing_themes(
+	Db(db): Db,
+	session: Session,
+	csrf_token: CsrfToken,
+	Query(query): Query<MissingThemesQuery>,
+	OriginalUri(uri): OriginalUri,
+	HxRequestInfo(hx): HxRequestInfo,
+) -> Result<Response<BoxBody>, WebError> {
+	if session.user().is_none() {
+		return Ok(Redirect::to("/auth/login/github?return_to=/missing-themes").into_response());
+	}
+
+	let order = query
+		.order
+		.as_deref()
+		.and_then(WorklistOrder::parse)
+		.unwrap_or_default();
+
+	let rank_column = match order {
+		WorklistOrder::Popular => series::Column::ViewCount,
+		WorklistOrder::Recent => series::Column::Created,
+	};
+
+	let after = match query.after.map(Cursor::into_key) {
+		None => None,
+		Some(key) => {
+			let name_id = after_name_id(
+				(series::Entity, series::Column::Name),
+				(series::Entity, series::Column::Id),
+				key.name,
+				key.id,
+			);
+
+			Some(match order {
+				WorklistOrder::Popular => after_desc(rank_column, key.rank, name_id),
+				WorklistOrder::Recent => {
+					let at = from_timestamp_micros(key.rank).ok_or(WebError::NotFound)?;
+					after_desc(rank_column, at, name_id)
+				}
+			})
+		}
+	};
+
+	let has_missing_season = sea_query::Query::select()
+		.expr(Expr::val(1))
+		.from(season::Entity)
+		.and_where(
+			Expr::col((season::Entity, season::Column::SeriesId))
+				.equals((series::Entity, series::Column::Id)),
+		)
+		.cond_where(missing_season_condition())
+		.to_owned();
+
+	let rows = series::Entity::find()
+		.filter(
+			Condition::any()
+				.add(series::Column::ThemeSongId.is_null())
+				.add(Expr::exists(has_missing_season)),
+		)
+		.apply_if(after, QueryFilter::filter)
+		.order_by_desc(rank_column)
+		.order_by_asc(series::Column::Name)
+		.order_by_asc(series::Column::Id)
+		.limit(WORKLIST_PAGE_SIZE + 1)
+		.all(&db)
+		.await?;
+
+	let page = CursorPage::new(rows, WORKLIST_PAGE_SIZE, &uri, |last| WorklistKey {
+		rank: match order {
+			WorklistOrder::Popular => last.view_count,
+			WorklistOrder::Recent => timestamp_micros(last.created),
+		},
+		name: last.name.clone(),
+		id: last.id,
+	});
+
+	let next_page_link: Option<Arc<str>> = page.next_page_href().map(Arc::from);
+	let mut seasons = missing_seasons(&db, page.items.iter().map(|s| s.id)).await?;
+	let row_count = page.items.len();
+	let rows = page
+		.items
+		.into_iter()
+		.enumerate()
+		.map(|(i, series)| MissingThemeRow {
+			seasons: seasons.remove(&series.id).unwrap_or_default(),
+			series,
+			next_page_link: match i == row_count - 1 {
+				true => next_page_link.clone(),
+				false => None,
+			},
+			added: None,
+			error: None,
+			csrf_token: csrf_token.clone(),
+		});
+
+	let page = MissingThemesPage::new(&session, order, rows);
+	match hx {
+		Some(hx) if !hx.boosted => Ok(page.into_items_fragment_response()),
+		_ => Ok(page.into_response()),
+	}
+}
+
+#[derive(Deserialize)]
+struct ThemeForm {
+	csrf_token: String,
+	name: String,
+	youtube: String,
+	#[serde(default)]
+	start: String,
+	#[serde(default)]
+	end: String,
+}
+
+async fn series_add_theme(
+	Path(series_id): Path<Uuid>,
+	Db(db): Db,
+	session: Session,
+	csrf_token: CsrfToken,
+	themes: ThemeService,
+	HxRequestInfo(hx): HxRequestInfo,
+	Form(form): Form<ThemeForm>,
+) -> Result<Response<BoxBody>, WebError> {
+	let target = ThemeTarget::Series(series_id);
+	add_theme(db, session, csrf_token, themes, hx, target, form).await
+}
+
+async fn season_add_theme(
+	Path((series_id, season_id)): Path<(Uuid, Uuid)>,
+	Db(db): Db,
+	session: Session,
+	csrf_token: CsrfToken,
+	themes: ThemeService,
+	HxRequestInfo(hx): HxRequestInfo,
+	Form(form): Form<ThemeForm>,
+) -> Result<Response<BoxBody>, WebError> {
+	let target = ThemeTarget::Season {
+		series_id,
+		season_id,
+	};
+	add_theme(db, session, csrf_token, themes, hx, target, form).await
+}
+
+/// Adds a theme from one of the worklist's inline forms, and renders the
+/// series' row again with whatever is still missing.
+async fn add_theme(
+	db: DatabaseConnection,
+	session: Session,
+	csrf_token: CsrfToken,
+	themes: ThemeService,
+	hx: Option<HtmxRequestInfo>,
+	target: ThemeTarget,
+	form: ThemeForm,
+) -> Result<Response<BoxBody>, WebError> {
+	let Some(user) = session.user() else {
+		return Ok(Redirect::to("/auth/login/github?return_to=/missing-themes").into_response());
+	};
+
+	if !csrf_token.verify(&form.csrf_token) {
+		return Err(WebError::InvalidCsrfToken);
+	}
+
+	let (series_id, target_id) = match target {
+		ThemeTarget::Series(series_id) => (series_id, series_id),
+		ThemeTarget::Season {
+			series_id,
+			season_id,
+		} => (series_id, season_id),
+	};
+
+	// both timestamps are optional, the whole video is used without them
+	let timestamp = |value: &str| match value.trim() {
+		"" => Ok(None),
+		value => markers::parse_timestamp(value)
+			.map(|ms| Some(ms / 1000))
+			.ok_or(()),
+	};
+
+	let result = match (timestamp(&form.start), timestamp(&form.end)) {
+		(Ok(starts_at), Ok(ends_at)) => themes
+			.add_theme(
+				target,
+				NewTheme {
+					name: form.name.clone(),
+					youtube: form.youtube.clone(),
+					starts_at,
+					ends_at,
+				},
+				Some(user.id),
+			)
+			.await
+			.map_err(|e| match e {
+				ThemeServiceError::DbErr(e) => Err(e.into()),
+				ThemeServiceError::NotFound => Err(WebError::NotFound),
+				e => Ok(e.to_string()),
+			}),
+		_ => Err(Ok("Timestamps must look like 1:42 or 1:02:03".to_owned())),
+	};
+
+	let (added, error) = match result {
+		Ok(theme) => (Some(theme.name), None),
+		Err(Err(e)) => return Err(e),
+		Err(Ok(message)) => (
+			None,
+			Some(ThemeFormError {
+				target: target_id,
+				values: ThemeFormValues {
+					name: form.name,
+					youtube: form.youtube,
+					start: form.start,
+					end: form.end,
+				},
+				message,
+			}),
+		),
+	};
+
+	if hx.is_none() {
+		return Ok(Redirect::to("/missing-themes").into_response());
+	}
+
+	let series = series::Entity::find_by_id(series_id)
+		.one(&db)
+		.await?
+		.ok_or(WebError::NotFound)?;
+
+	let seasons = missing_seasons(&db, [series_id])
+		.await?
+		.remove(&series_id)
+		.unwrap_or_default();
+
+	Ok(
+		MissingThemeRow {
+			series,
+			seasons,
+			next_page_link: None,
+			added,
+			error,
+			csrf_token,
+		}
+		.into_response(),
+	)
+}
+
+async fn stats(session: Session, stats: StatsService) -> Result<Response<BoxBody>, WebError> {
+	let stats = stats.get_stats().await?;
+	Ok(StatsPage::new(&session, stats).into_response())
+}
+
+async fn api_docs(session: Session) -> impl IntoResponse {
+	ApiDocsPage::new(&session, api::openapi::document()).into_response()
+}
+
+/// Lists the api keys of the user, with the key that was just created or why
+/// it couldn't be.
+async fn render_profile(
+	session: &Session,
+	csrf_token: &CsrfToken,
+	keys: &ApiKeyService,
+	created: Option<(String, String)>,
+	error: Option<String>,
+) -> Result<Response<BoxBody>, WebError> {
+	let Some(user) = session.user() else {
+		return Ok(Redirect::to("/auth/login/github?return_to=/profile").into_response());
+	};
+
+	let api_keys = keys.list_keys(user.id).await?;
+
+	Ok(
+		ProfilePage {
+			session,
+			csrf_token: csrf_token.as_ref(),
+			user: &user,
+			keys: api_keys,
+			now: OffsetDateTime::now_utc().into_primitive_utc(),
+			created,
+			error,
+		}
+		.into_response(),
+	)
+}
+
+async fn profile(
+	session: Session,
+	csrf_token: CsrfToken,
+	keys: ApiKeyService,
+) -> Result<Response<BoxBody>, WebError> {
+	render_profile(&session, &csrf_token, &keys, None, None).await
+}
+
+#[derive(Deserialize)]
+struct ApiKeyForm {
+	csrf_token: String,
+	name: String,
+	#[serde(default)]
+	expires_in_days: String,
+	#[serde(default)]
+	write: bool,
+}
+
+async fn profile_create_api_key(
+	session: Session,
+	csrf_token: CsrfToken,
+	keys: ApiKeyService,
+	Form(form): Form<ApiKeyForm>,
+) -> Result<Response<BoxBody>, WebError> {
+	let Some(user) = session.user() else {
+		return Ok(Redirect::to("/auth/login/github?return_to=/profile").into_response());
+	};
+
+	if !csrf_token.verify(&form.csrf_token) {
+		return Err(WebError::InvalidCsrfToken);
+	}
+
+	let lifetime = match form.expires_in_days.trim() {
+		"" => Some(None),
+		days => KEY_LIFETIMES
+			.iter()
+			.filter_map(|(_, lifetime)| *lifetime)
+			.find(|lifetime| days.parse() == Ok(*lifetime))
+			.map(Some),
+	};
+
+	let Some(lifetime) = lifetime else {
+		let error = Some("Unknown key lifetime".to_owned());
+		return render_profile(&session, &csrf_token, &keys, None, error).await;
+	};
+
+	let now = OffsetDateTime::now_utc().into_primitive_utc();
+	let key = NewApiKey {
+		name: form.name,
+		scopes: Scopes {
+			read: true,
+			write: form.write,
+		},
+		expires_at: lifetime.map(|days| now + Duration::days(days)),
+	};
+
+	let (created, error) = match keys.create_key(user.id, key).await {
+		Ok(created) => (Some((created.key.name, created.token)), None),
+		Err(ApiKeyError::DbErr(e)) => return Err(e.into()),
+		Err(e) => (None, Some(e.to_string())),
+	};
+
+	render_profile(&session, &csrf_token, &keys, created, error).await
+}
+
+#[derive(Deserialize)]
+struct RevokeApiKeyForm {
+	csrf_token: String,
+}
+
+async fn profile_revoke_api_key(
+	Path(key_id): Path<Uuid>,
+	session: Session,
+	csrf_token: CsrfToken,
+	keys: ApiKeyService,
+	Form(form): Form<RevokeApiKeyForm>,
+) -> Result<Response<BoxBody>, WebError> {
+	let Some(user) = session.user() else {
+		return Ok(Redirect::to("/auth/login/github?return_to=/profile").into_response());
+	};
+
+	if !csrf_token.verify(&form.csrf_token) {
+		return Err(WebError::InvalidCsrfToken);
+	}
+
+	if !keys.revoke_key(user.id, key_id).await? {
+		return Err(WebError::NotFound);
+	}
+
+	Ok(Redirect::to("/profile").into_response())
 }
 
 pub fn router() -> Router<AppState> {
@@ -194,4 +1605,23 @@ pub fn router() -> Router<AppState> {
 		.route("/", get(index))
 		.route("/series/:id", get(series))
 		.route("/series/:id/edit", get(series_edit_series))
+		.route("/genres/:slug", get(genre))
+		.route("/networks/:slug", get(network))
+		.route("/movies", get(movies))
+		.route("/movies/:id", get(movie))
+		.route("/missing-themes", get(missing_themes))
+		.route("/stats", get(stats))
+		.route("/api/docs", get(api_docs))
+		.route("/profile", get(profile))
+		.route("/profile/api-keys", post(profile_create_api_key))
+		.route("/profile/api-keys/:id/revoke", post(profile_revoke_api_key))
+		.route("/series/:id/theme", post(series_add_theme))
+		.route(
+			"/series/:id/season/:season_id/theme",
+			post(season_add_theme),
+		)
+		.route(
+			"/series/:id/season/:season_id/episode/:episode_id/markers",
+			post(episode_add_marker),
+		)
 }
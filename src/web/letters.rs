@@ -0,0 +1,76 @@
+use crate::AppState;
+use axum::extract::FromRef;
+use sea_orm::prelude::TimeDateTime;
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, Mutex},
+};
+
+/// Entries are dropped once there are more than this many, so searches don't
+/// keep using memory.
+const MAX_ENTRIES: usize = 1_000;
+
+struct Entry {
+	newest: Option<TimeDateTime>,
+	initials: Arc<HashSet<char>>,
+}
+
+/// The initials of the series matching each filter, for the letter bar.
+/// Entries are keyed on the newest series `_version` when they were made, so
+/// they are made again once any series changed.
+#[derive(Clone, Default)]
+pub struct LetterCache {
+	entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl FromRef<AppState> for LetterCache {
+	fn from_ref(input: &AppState) -> Self {
+		input.letters.clone()
+	}
+}
+
+impl LetterCache {
+	pub fn get(&self, query: &str, newest: Option<TimeDateTime>) -> Option<Arc<HashSet<char>>> {
+		let entries = self.entries.lock().unwrap();
+		let entry = entries.get(query)?;
+		(entry.newest == newest).then(|| entry.initials.clone())
+	}
+
+	pub fn insert(&self, query: String, newest: Option<TimeDateTime>, initials: Arc<HashSet<char>>) {
+		let mut entries = self.entries.lock().unwrap();
+		if entries.len() >= MAX_ENTRIES {
+			entries.retain(|_, entry| entry.newest == newest);
+			if entries.len() >= MAX_ENTRIES {
+				entries.clear();
+			}
+		}
+
+		entries.insert(query, Entry { newest, initials });
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use time::{Date, Month};
+
+	#[test]
+	fn entries_are_only_used_for_the_same_version() {
+		let cache = LetterCache::default();
+		let day = |day| {
+			Date::from_calendar_date(2024, Month::May, day)
+				.unwrap()
+				.midnight()
+		};
+		let old = Some(day(16));
+		let new = Some(day(17));
+		cache.insert("query".to_owned(), old, Arc::new(HashSet::from(['A'])));
+
+		assert_eq!(
+			cache.get("query", old).as_deref(),
+			Some(&HashSet::from(['A']))
+		);
+		assert_eq!(cache.get("query", new), None);
+		assert_eq!(cache.get("other", old), None);
+	}
+}
@@ -1,7 +1,27 @@
+mod api_docs;
+mod artwork;
+mod category;
+mod csrf;
 mod index;
+mod missing;
+mod movies;
+mod profile;
 mod series;
+mod stats;
 mod template;
 
-pub use index::{IndexPage, SeriesCard};
-pub use series::{SeriesEdit, SeriesPage};
+pub use api_docs::ApiDocsPage;
+pub use artwork::ArtworkSources;
+pub use category::{Category, CategoryPage};
+pub use csrf::CsrfField;
+pub use index::{
+	ActiveFilter, IndexControls, IndexPage, JumpLink, LetterBar, SeriesCard, SeriesSort, ThemeFilter,
+};
+pub use missing::{
+	MissingThemeRow, MissingThemesPage, ThemeFormError, ThemeFormValues, WorklistOrder,
+};
+pub use movies::{MovieCard, MoviePage, MoviesPage};
+pub use profile::{ProfilePage, KEY_LIFETIMES};
+pub use series::{marker_themes, EpisodeRow, SeriesArtwork, SeriesEdit, SeriesPage, SeriesSeasons};
+pub use stats::StatsPage;
 pub use template::Template;
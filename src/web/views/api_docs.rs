@@ -0,0 +1,337 @@
+use crate::web::views::Template;
+use axum::response::IntoResponse;
+use dbost_session::Session;
+use rstml_component::{write_html, For, HtmlContent, HtmlFormatter};
+use rstml_component_axum::Html;
+use serde_json::Value;
+use std::fmt;
+
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+/// Name of the schema a `$ref` points at.
+fn ref_name(schema: &Value) -> Option<&str> {
+	schema.get("$ref")?.as_str()?.rsplit('/').next()
+}
+
+/// Short description of the type of a schema, like `string (uuid)` or
+/// `ThemeDto[]`.
+fn type_name(schema: &Value) -> String {
+	if let Some(name) = ref_name(schema) {
+		return name.to_owned();
+	}
+
+	// nullable references are wrapped in `allOf`
+	if let Some(inner) = schema
+		.get("allOf")
+		.and_then(|all| all.as_array())
+		.and_then(|all| all.first())
+	{
+		return type_name(inner);
+	}
+
+	match schema.get("type").and_then(|t| t.as_str()) {
+		Some("array") => format!(
+			"{}[]",
+			schema.get("items").map(type_name).unwrap_or_default()
+		),
+		Some(kind) => match schema.get("format").and_then(|f| f.as_str()) {
+			Some(format) => format!("{kind} ({format})"),
+			None => kind.to_owned(),
+		},
+		None => "any".to_owned(),
+	}
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> &'a str {
+	value.get(key).and_then(|v| v.as_str()).unwrap_or_default()
+}
+
+struct Parameter<'a> {
+	name: &'a str,
+	location: &'a str,
+	kind: String,
+	required: bool,
+	description: &'a str,
+}
+
+struct Response<'a> {
+	status: &'a str,
+	description: &'a str,
+	kind: String,
+}
+
+struct Operation<'a> {
+	method: &'a str,
+	path: &'a str,
+	summary: &'a str,
+	description: &'a str,
+	/// Served without an api key.
+	public: bool,
+	parameters: Vec<Parameter<'a>>,
+	body: Option<String>,
+	responses: Vec<Response<'a>>,
+}
+
+impl<'a> Operation<'a> {
+	fn new(method: &'a str, path: &'a str, operation: &'a Value) -> Self {
+		let parameters = operation
+			.get("parameters")
+			.and_then(|p| p.as_array())
+			.into_iter()
+			.flatten()
+			.map(|p| Parameter {
+				name: text(p, "name"),
+				location: text(p, "in"),
+				kind: p.get("schema").map(type_name).unwrap_or_default(),
+				required: p.get("required").and_then(|r| r.as_bool()).unwrap_or(false),
+				description: text(p, "description"),
+			})
+			.collect();
+
+		let body = operation
+			.pointer("/requestBody/content/application~1json/schema")
+			.map(type_name);
+
+		let responses = operation
+			.get("responses")
+			.and_then(|r| r.as_object())
+			.into_iter()
+			.flatten()
+			.map(|(status, response)| Response {
+				status,
+				description: text(response, "description"),
+				kind: match response.pointer("/content/application~1json/schema") {
+					Some(schema) => type_name(schema),
+					None => "text".to_owned(),
+				},
+			})
+			.collect();
+
+		Self {
+			method,
+			path,
+			summary: text(operation, "summary"),
+			description: text(operation, "description"),
+			public: operation
+				.get("security")
+				.and_then(|s| s.as_array())
+				.is_some_and(|s| s.is_empty()),
+			parameters,
+			body,
+			responses,
+		}
+	}
+}
+
+struct Property<'a> {
+	name: &'a str,
+	kind: String,
+	required: bool,
+	description: &'a str,
+}
+
+struct Schema<'a> {
+	name: &'a str,
+	description: &'a str,
+	properties: Vec<Property<'a>>,
+}
+
+impl<'a> Schema<'a> {
+	fn new(name: &'a str, schema: &'a Value) -> Self {
+		let required = schema
+			.get("required")
+			.and_then(|r| r.as_array())
+			.map(Vec::as_slice)
+			.unwrap_or_default();
+
+		let properties = schema
+			.get("properties")
+			.and_then(|p| p.as_object())
+			.into_iter()
+			.flatten()
+			.map(|(name, property)| Property {
+				name,
+				kind: type_name(property),
+				required: required.iter().any(|r| r.as_str() == Some(name)),
+				description: text(property, "description"),
+			})
+			.collect();
+
+		Self {
+			name,
+			description: text(schema, "description"),
+			properties,
+		}
+	}
+}
+
+/// Links the type of a body or response to the schema it uses.
+struct TypeLink<'a> {
+	kind: &'a str,
+}
+
+impl<'a> HtmlContent for TypeLink<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let schema = self.kind.trim_end_matches("[]");
+		match schema.chars().next() {
+			Some(c) if c.is_uppercase() => write_html!(f,
+				<a class="font-mono link link-hover" href=("#schema-", schema)>{self.kind}</a>
+			),
+			_ => write_html!(f, <span class="font-mono">{self.kind}</span>),
+		}
+	}
+}
+
+pub struct ApiDocsPage<'a> {
+	session: &'a Session,
+	document: &'a Value,
+}
+
+impl<'a> ApiDocsPage<'a> {
+	pub fn new(session: &'a Session, document: &'a Value) -> Self {
+		Self { session, document }
+	}
+
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+}
+
+impl<'a> HtmlContent for ApiDocsPage<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let document = self.document;
+		let title = document.pointer("/info/title").and_then(|t| t.as_str());
+		let description = document
+			.pointer("/info/description")
+			.and_then(|d| d.as_str());
+		let server = document.pointer("/servers/0/url").and_then(|u| u.as_str());
+
+		let operations = document
+			.get("paths")
+			.and_then(|p| p.as_object())
+			.into_iter()
+			.flatten()
+			.flat_map(|(path, item)| {
+				METHODS.iter().filter_map(move |method| {
+					let operation = item.get(*method)?;
+					Some(Operation::new(method, path, operation))
+				})
+			})
+			.collect::<Vec<_>>();
+
+		let schemas = document
+			.pointer("/components/schemas")
+			.and_then(|s| s.as_object())
+			.into_iter()
+			.flatten()
+			.map(|(name, schema)| Schema::new(name, schema))
+			.collect::<Vec<_>>();
+
+		write_html!(f,
+			<Template title="API" session=self.session>
+				<h1 class="mb-2 text-4xl font-bold">{title.unwrap_or("API")} " API"</h1>
+				<p class="mb-2">{description.unwrap_or_default()}</p>
+				<p class="mb-8 opacity-70">
+					"Requests go to " <code>{server.unwrap_or_default()}</code>
+					" and need an api key, from your " <a class="link" href="/profile">"profile"</a> ", in the " <code>"x-api-key"</code> " header. "
+					<a class="link" href="/api/openapi.json" hx-boost="false">"OpenAPI document"</a>
+				</p>
+
+				<h2 class="mb-4 text-2xl font-bold">"Operations"</h2>
+				<For items={&operations}>
+					{ |f, op: &Operation| {
+						let anchor = format!("{}-{}", op.method, op.path.replace(['/', '{', '}'], "-"));
+
+						write_html!(f,
+							<section class="mb-4 shadow card bg-base-200" id=&*anchor>
+								<div class="card-body">
+									<h3 class="card-title">
+										<span class="uppercase badge badge-primary">{op.method}</span>
+										<code>{op.path}</code>
+										{op.public.then_some(|f: &mut HtmlFormatter| write_html!(f,
+											<span class="badge badge-ghost">"No API key needed"</span>
+										))}
+									</h3>
+									<p>{op.summary}</p>
+									{(!op.description.is_empty()).then_some(|f: &mut HtmlFormatter| write_html!(f,
+										<p class="opacity-70">{op.description}</p>
+									))}
+
+									{(!op.parameters.is_empty()).then_some(|f: &mut HtmlFormatter| write_html!(f,
+										<table class="table table-sm">
+											<thead>
+												<tr><th>"Parameter"</th><th>"In"</th><th>"Type"</th><th></th></tr>
+											</thead>
+											<tbody>
+												<For items={&op.parameters}>
+													{ |f, p: &Parameter| write_html!(f,
+														<tr>
+															<td class="font-mono">{p.name}{p.required.then_some("*")}</td>
+															<td>{p.location}</td>
+															<td class="font-mono">{&*p.kind}</td>
+															<td>{p.description}</td>
+														</tr>
+													) }
+												</For>
+											</tbody>
+										</table>
+									))}
+
+									{op.body.as_deref().map(|kind| move |f: &mut HtmlFormatter| write_html!(f,
+										<p>"Body: " <TypeLink kind=kind /></p>
+									))}
+
+									<table class="table table-sm">
+										<thead>
+											<tr><th>"Status"</th><th>"Response"</th><th></th></tr>
+										</thead>
+										<tbody>
+											<For items={&op.responses}>
+												{ |f, r: &Response| write_html!(f,
+													<tr>
+														<td>{r.status}</td>
+														<td><TypeLink kind=&r.kind /></td>
+														<td>{r.description}</td>
+													</tr>
+												) }
+											</For>
+										</tbody>
+									</table>
+								</div>
+							</section>
+						)
+					} }
+				</For>
+
+				<h2 class="mt-8 mb-4 text-2xl font-bold">"Schemas"</h2>
+				<div class="grid grid-cols-1 gap-4 lg:grid-cols-2">
+					<For items={&schemas}>
+						{ |f, schema: &Schema| write_html!(f,
+							<section class="shadow card bg-base-200" id=("schema-", schema.name)>
+								<div class="card-body">
+									<h3 class="font-mono card-title">{schema.name}</h3>
+									{(!schema.description.is_empty()).then_some(|f: &mut HtmlFormatter| write_html!(f,
+										<p class="opacity-70">{schema.description}</p>
+									))}
+									<table class="table table-sm">
+										<tbody>
+											<For items={&schema.properties}>
+												{ |f, p: &Property| write_html!(f,
+													<tr>
+														<td class="font-mono">{p.name}{p.required.then_some("*")}</td>
+														<td><TypeLink kind=&p.kind /></td>
+														<td>{p.description}</td>
+													</tr>
+												) }
+											</For>
+										</tbody>
+									</table>
+								</div>
+							</section>
+						) }
+					</For>
+				</div>
+			</Template>
+		)
+	}
+}
@@ -0,0 +1,31 @@
+use crate::images::{self, ImageFormat};
+use rstml_component::{write_html, HtmlComponent, HtmlContent, HtmlFormatter};
+use std::fmt;
+
+/// `<source>` elements serving the artwork through the image proxy, resized
+/// and in modern formats. Goes inside a `<picture>`, before the `<img>`.
+#[derive(HtmlComponent)]
+pub struct ArtworkSources<'a> {
+	pub url: Option<&'a str>,
+	pub sizes: &'a str,
+	pub max_width: u32,
+}
+
+impl<'a> HtmlContent for ArtworkSources<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let url = match self.url {
+			None => return Ok(()),
+			Some(url) => url,
+		};
+
+		for format in [ImageFormat::Avif, ImageFormat::WebP] {
+			if let Some(srcset) = images::srcset(url, self.max_width, format) {
+				write_html!(f,
+					<source type=format.mime_type() srcset=srcset sizes=self.sizes />
+				)?;
+			}
+		}
+
+		Ok(())
+	}
+}
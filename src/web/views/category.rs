@@ -0,0 +1,106 @@
+use crate::web::views::{SeriesCard, Template};
+use axum::response::IntoResponse;
+use dbost_session::Session;
+use rstml_component::{write_html, For, HtmlContent, HtmlFormatter};
+use rstml_component_axum::Html;
+use std::fmt;
+
+/// A grouping of series with its own listing page.
+pub enum Category {
+	Genre(String),
+	Network(String),
+}
+
+impl Category {
+	fn kind(&self) -> &'static str {
+		match self {
+			Self::Genre(_) => "Genre",
+			Self::Network(_) => "Network",
+		}
+	}
+
+	fn name(&self) -> &str {
+		match self {
+			Self::Genre(name) | Self::Network(name) => name,
+		}
+	}
+}
+
+pub struct CategoryPage<'a, I> {
+	session: &'a Session,
+	category: Category,
+	items: I,
+	counts: Option<(u64, u64)>,
+}
+
+impl<'a, I> CategoryPage<'a, I>
+where
+	I: IntoIterator<Item = SeriesCard>,
+{
+	pub fn new(session: &'a Session, category: Category, items: I) -> Self {
+		Self {
+			session,
+			category,
+			items,
+			counts: None,
+		}
+	}
+
+	/// Shows how many series there are in the category, and how many of them
+	/// are still missing a theme song.
+	pub fn with_counts(self, total: u64, missing_themes: u64) -> Self {
+		Self {
+			counts: Some((total, missing_themes)),
+			..self
+		}
+	}
+
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+
+	pub fn into_items_fragment_response(self) -> axum::response::Response {
+		Html(Self::items_fragment(self.items)).into_response()
+	}
+
+	fn items_fragment(items: I) -> impl HtmlContent {
+		For {
+			items,
+			children: |f, item| item.fmt(f),
+		}
+	}
+}
+
+impl<'a, I> HtmlContent for CategoryPage<'a, I>
+where
+	I: IntoIterator<Item = SeriesCard>,
+{
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let title = format!("{}: {}", self.category.kind(), self.category.name());
+
+		write_html!(f,
+			<Template title=&*title session=self.session>
+				<h1 class="mb-2 text-4xl font-bold" hx-disable>{self.category.name()}</h1>
+				<p class="mb-8 opacity-70">
+					{self.category.kind()}
+					{self.counts.map(|(total, missing)| move |f: &mut HtmlFormatter| match missing {
+						0 => write_html!(f, " · " {total} " series, all with theme songs"),
+						_ => write_html!(f,
+							" · " {total} " series, "
+							<span class="font-bold text-warning">{missing} " missing a theme song"</span>
+						),
+					})}
+				</p>
+
+				<ul
+					class="grid grid-cols-1 gap-4 auto-rows-cards sm:grid-cols-2 md:grid-cols-3 lg:grid-cols-4 xl:grid-cols-5 2xl:grid-cols-6"
+					hx-indicator=".htmx-indicator">
+					{Self::items_fragment(self.items)}
+				</ul>
+				<center>
+					<img class="htmx-indicator" width="60" src="/public/img/bars.svg" />
+				</center>
+			</Template>
+		)
+	}
+}
@@ -0,0 +1,14 @@
+use rstml_component::{write_html, HtmlComponent, HtmlContent, HtmlFormatter};
+use std::fmt;
+
+/// Sends the session's CSRF token back with a form, for the handler to check.
+#[derive(HtmlComponent)]
+pub struct CsrfField<'a> {
+	pub token: &'a str,
+}
+
+impl<'a> HtmlContent for CsrfField<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		write_html!(f, <input type="hidden" name="csrf_token" value=self.token />)
+	}
+}
@@ -1,5 +1,9 @@
-use crate::web::views::Template;
+use crate::{
+	images,
+	web::views::{ArtworkSources, Template},
+};
 use axum::response::IntoResponse;
+use dbost_entities::genre;
 use dbost_session::Session;
 use rstml_component::{
 	write_html, For, HtmlAttributes, HtmlAttributesFormatter, HtmlComponent, HtmlContent,
@@ -10,7 +14,7 @@ use std::{fmt, sync::Arc};
 use uuid::Uuid;
 
 // temp - move to rstml_component
-struct Attrs<I>(I)
+pub(super) struct Attrs<I>(pub I)
 where
 	I: IntoIterator,
 	<I as IntoIterator>::Item: HtmlAttributes;
@@ -34,7 +38,9 @@ pub struct SeriesCard {
 	name: String,
 	id: Uuid,
 	image: Option<String>,
+	image_blurhash: Option<String>,
 	season_count: i64,
+	special_count: i64,
 	next_page_link: Option<Arc<str>>,
 }
 
@@ -43,14 +49,18 @@ impl SeriesCard {
 		name: String,
 		id: Uuid,
 		image: Option<String>,
+		image_blurhash: Option<String>,
 		season_count: i64,
+		special_count: i64,
 		next_page_link: Option<Arc<str>>,
 	) -> Self {
 		Self {
 			name,
 			id,
 			image,
+			image_blurhash,
 			season_count,
+			special_count,
 			next_page_link,
 		}
 	}
@@ -66,6 +76,12 @@ impl HtmlContent for SeriesCard {
 			])
 		});
 
+		let placeholder_attr = self
+			.image_blurhash
+			.as_deref()
+			.and_then(images::placeholder)
+			.map(|url| ("style", format!("background-image: url('{url}');")));
+
 		let id = self.id.to_string();
 		write_html!(formatter,
 			<li
@@ -78,13 +94,27 @@ impl HtmlContent for SeriesCard {
 			>
 				<a class="contents" href=("/series/", &*id)>
 					<picture
-						class="series-image rounded-box"
+						class=match self.image.is_some() {
+							// reserve the poster's space, so the placeholder is visible while loading
+							true => "bg-center bg-cover series-image rounded-box aspect-[2/3]",
+							false => "series-image rounded-box",
+						}
+						{placeholder_attr}
 					>
-						<img src=self.image.as_deref() alt="" referrerpolicy="no-referrer" />
+						<ArtworkSources
+							url=self.image.as_deref()
+							sizes="(min-width: 1536px) 16vw, (min-width: 1280px) 20vw, (min-width: 1024px) 25vw, (min-width: 768px) 33vw, (min-width: 640px) 50vw, 100vw"
+							max_width=960 />
+						<img src=self.image.as_deref().map(images::proxied_url) class="object-cover w-full h-full" alt="" />
 					</picture>
 					<div class="p-4 text-base bg-base-100/80 series-text">
 						<h2 class="card-title text-ellipsis line-clamp-2" hx-disable>{&*self.name}</h2>
-						<p>"Seasons: " {self.season_count}</p>
+						<p>{|f: &mut HtmlFormatter| match (self.season_count, self.special_count) {
+							(0, 0) => f.write_content("No seasons"),
+							(0, _) => f.write_content("Specials only"),
+							(seasons, 0) => write_html!(f, "Seasons: " {seasons}),
+							(seasons, _) => write_html!(f, "Seasons: " {seasons} " + specials"),
+						}}</p>
 					</div>
 				</a>
 			</li>
@@ -92,25 +122,241 @@ impl HtmlContent for SeriesCard {
 	}
 }
 
+/// The orders the index can be sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeriesSort {
+	#[default]
+	Name,
+	Updated,
+	Added,
+}
+
+impl SeriesSort {
+	pub const ALL: [SeriesSort; 3] = [SeriesSort::Name, SeriesSort::Updated, SeriesSort::Added];
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Name => "name",
+			Self::Updated => "updated",
+			Self::Added => "added",
+		}
+	}
+
+	fn name(self) -> &'static str {
+		match self {
+			Self::Name => "Name",
+			Self::Updated => "Recently updated",
+			Self::Added => "Recently added",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|s| s.as_str() == value)
+	}
+}
+
+/// Filters the index on whether series have a theme song.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFilter {
+	Has,
+	Missing,
+}
+
+impl ThemeFilter {
+	pub const ALL: [ThemeFilter; 2] = [ThemeFilter::Has, ThemeFilter::Missing];
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Has => "has",
+			Self::Missing => "missing",
+		}
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Has => "Has theme",
+			Self::Missing => "Missing theme",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|s| s.as_str() == value)
+	}
+}
+
+/// The current state of the sort and filter controls on the index.
+pub struct IndexControls {
+	pub sort: SeriesSort,
+	pub theme: Option<ThemeFilter>,
+	pub genre: Option<String>,
+	pub year: Option<i32>,
+	pub genres: Vec<genre::Model>,
+	/// Active parameters without a control of their own, which are kept when
+	/// the controls change.
+	pub hidden: Vec<(&'static str, String)>,
+}
+
+impl HtmlContent for IndexControls {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let selected = |selected: bool| selected.then_some(("selected", ()));
+
+		write_html!(f,
+			<form
+				id="series-controls"
+				class="flex flex-wrap items-center gap-2 mb-4"
+				action="/"
+				method="get"
+				hx-get="/"
+				hx-trigger="change"
+				hx-target="#series-results"
+				hx-select="#series-results"
+				hx-swap="outerHTML"
+				hx-push-url="true"
+			>
+				<For items={self.hidden}>
+					{ |f, (name, value): (&str, String)| write_html!(f, <input type="hidden" name=name value=value />) }
+				</For>
+				<select name="sort" class="select select-bordered select-sm" aria-label="Sort by">
+					<For items={SeriesSort::ALL}>
+						{ |f, sort: SeriesSort| write_html!(f,
+							<option value=sort.as_str() {selected(sort == self.sort)}>{sort.name()}</option>
+						) }
+					</For>
+				</select>
+				<select name="theme" class="select select-bordered select-sm" aria-label="Theme song">
+					<option value="">"Any theme status"</option>
+					<For items={ThemeFilter::ALL}>
+						{ |f, theme: ThemeFilter| write_html!(f,
+							<option value=theme.as_str() {selected(Some(theme) == self.theme)}>{theme.name()}</option>
+						) }
+					</For>
+				</select>
+				<select name="genre" class="select select-bordered select-sm" aria-label="Genre">
+					<option value="">"Any genre"</option>
+					<For items={&self.genres}>
+						{ |f, genre: &genre::Model| write_html!(f,
+							<option value=&*genre.slug {selected(self.genre.as_deref() == Some(&*genre.slug))}>{&*genre.name}</option>
+						) }
+					</For>
+				</select>
+				<input
+					type="number"
+					name="year"
+					min="1900"
+					max="2100"
+					placeholder="Year"
+					class="w-24 input input-bordered input-sm"
+					aria-label="Year"
+					value=self.year.map(|y| y.to_string()) />
+				<noscript><button class="btn btn-sm">"Apply"</button></noscript>
+			</form>
+		)
+	}
+}
+
+/// A letter in the [`LetterBar`], unavailable when no series start with it.
+pub struct JumpLink {
+	pub letter: char,
+	pub href: String,
+	pub available: bool,
+}
+
+/// Jumps the index to the first series starting with a letter. The links swap
+/// in the results like the controls do, and push the letter into the url.
+pub struct LetterBar {
+	pub active: Option<char>,
+	pub links: Vec<JumpLink>,
+}
+
+impl HtmlContent for LetterBar {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let active = self.active;
+
+		write_html!(f,
+			<nav id="series-letters" class="flex flex-wrap gap-1 mb-4" aria-label="Jump to letter">
+				<For items={self.links}>
+					{ |f, link: JumpLink| {
+						let is_active = active == Some(link.letter);
+						let class = match (is_active, link.available) {
+							(true, _) => "btn btn-xs btn-square btn-primary",
+							(false, true) => "btn btn-xs btn-square btn-ghost",
+							(false, false) => "btn btn-xs btn-square btn-ghost btn-disabled",
+						};
+
+						let current_attribute = is_active.then_some(("aria-current", "true"));
+						let disabled_attribute = (!link.available).then_some(("aria-disabled", "true"));
+						let letter = link.letter.to_string();
+						let label = match link.letter {
+							'#' => "Jump to the top".to_owned(),
+							letter => format!("Jump to {letter}"),
+						};
+
+						write_html!(f,
+							<a
+								class=class
+								href=&*link.href
+								hx-get=&*link.href
+								hx-target="#series-results"
+								hx-select="#series-results"
+								hx-swap="outerHTML show:window:top"
+								hx-push-url="true"
+								{current_attribute}
+								{disabled_attribute}
+								aria-label=label
+							>{letter}</a>
+						)
+					} }
+				</For>
+			</nav>
+		)
+	}
+}
+
+/// A filter applied to the index, with a link to the index without it.
+pub struct ActiveFilter {
+	pub label: String,
+	pub remove_href: String,
+}
+
 pub struct IndexPage<'a, I> {
 	session: &'a Session,
 	items: I,
+	search: Option<&'a str>,
+	filters: Vec<ActiveFilter>,
+	controls: IndexControls,
+	letters: LetterBar,
 }
 
 impl<'a, I> IndexPage<'a, I>
 where
 	I: IntoIterator<Item = SeriesCard>,
 {
-	pub fn new(session: &'a Session, items: I) -> Self {
-		Self { session, items }
+	pub fn new(
+		session: &'a Session,
+		items: I,
+		search: Option<&'a str>,
+		filters: Vec<ActiveFilter>,
+		controls: IndexControls,
+		letters: LetterBar,
+	) -> Self {
+		Self {
+			session,
+			items,
+			search,
+			filters,
+			controls,
+			letters,
+		}
 	}
 
 	pub fn into_response(self) -> axum::response::Response {
 		Html(self).into_response()
 	}
 
-	pub fn into_items_fragment_response(self) -> axum::response::Response {
-		Html(Self::items_fragment(self.items)).into_response()
+	/// Just the cards, for the infinite scroll. Doesn't need the rest of the
+	/// page to be built.
+	pub fn items_fragment_response(items: I) -> axum::response::Response {
+		Html(Self::items_fragment(items)).into_response()
 	}
 
 	fn items_fragment(items: I) -> impl HtmlContent {
@@ -128,13 +374,37 @@ where
 	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
 		write_html!(f,
 			<Template title="Series" session=self.session>
-				<h1 class="mb-8 text-4xl font-bold">Series</h1>
+				{|f: &mut HtmlFormatter| match self.search {
+					None => write_html!(f, <h1 class="mb-8 text-4xl font-bold">Series</h1>),
+					Some(search) => write_html!(f,
+						<h1 class="mb-8 text-4xl font-bold" hx-disable>"Series matching \"" {search} "\""</h1>
+					),
+				}}
+
+				{self.controls}
+
+				// swapped as a whole when the controls change, or jumping to a letter
+				<div id="series-results">
+					{self.letters}
+
+					{(!self.filters.is_empty()).then_some(|f: &mut HtmlFormatter| write_html!(f,
+						<div class="flex flex-wrap gap-2 mb-8">
+							<For items={self.filters}>
+								{ |f, filter: ActiveFilter| write_html!(f,
+									<a class="gap-2 badge badge-lg badge-outline" href=filter.remove_href aria-label=("Remove filter ", &*filter.label)>
+										{&*filter.label} " ✕"
+									</a>
+								) }
+							</For>
+						</div>
+					))}
 
-				<ul
-					class="grid grid-cols-1 gap-4 auto-rows-cards sm:grid-cols-2 md:grid-cols-3 lg:grid-cols-4 xl:grid-cols-5 2xl:grid-cols-6"
-					hx-indicator=".htmx-indicator">
-					{Self::items_fragment(self.items)}
-				</ul>
+					<ul
+						class="grid grid-cols-1 gap-4 mt-4 auto-rows-cards sm:grid-cols-2 md:grid-cols-3 lg:grid-cols-4 xl:grid-cols-5 2xl:grid-cols-6"
+						hx-indicator=".htmx-indicator">
+						{Self::items_fragment(self.items)}
+					</ul>
+				</div>
 				<center>
 					<img class="htmx-indicator" width="60" src="/public/img/bars.svg" />
 				</center>
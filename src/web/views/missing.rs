@@ -0,0 +1,262 @@
+use crate::web::views::{index::Attrs, CsrfField, Template};
+use axum::response::IntoResponse;
+use dbost_entities::{season, series};
+use dbost_session::{CsrfToken, Session};
+use rstml_component::{write_html, For, HtmlComponent, HtmlContent, HtmlFormatter};
+use rstml_component_axum::Html;
+use std::{fmt, sync::Arc};
+use uuid::Uuid;
+
+/// The orders the missing themes worklist can be prioritised in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorklistOrder {
+	#[default]
+	Popular,
+	Recent,
+}
+
+impl WorklistOrder {
+	pub const ALL: [WorklistOrder; 2] = [WorklistOrder::Popular, WorklistOrder::Recent];
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Popular => "popular",
+			Self::Recent => "recent",
+		}
+	}
+
+	fn name(self) -> &'static str {
+		match self {
+			Self::Popular => "Most viewed",
+			Self::Recent => "Recently added",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|o| o.as_str() == value)
+	}
+}
+
+/// The values of a submitted theme form, shown again when it was rejected.
+#[derive(Default)]
+pub struct ThemeFormValues {
+	pub name: String,
+	pub youtube: String,
+	pub start: String,
+	pub end: String,
+}
+
+/// A rejected theme form, for the series or season with the id `target`.
+pub struct ThemeFormError {
+	pub target: Uuid,
+	pub values: ThemeFormValues,
+	pub message: String,
+}
+
+#[derive(HtmlComponent)]
+struct ThemeForm<'a> {
+	row_id: &'a str,
+	action: String,
+	label: &'a str,
+	csrf_token: &'a str,
+	error: Option<&'a ThemeFormError>,
+}
+
+impl<'a> HtmlContent for ThemeForm<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let empty = ThemeFormValues::default();
+		let values = self.error.map(|e| &e.values).unwrap_or(&empty);
+		let open_attribute = self.error.is_some().then_some(("open", ()));
+
+		write_html!(f,
+			<details class="p-3 rounded-lg bg-base-100" {open_attribute}>
+				<summary class="cursor-pointer">
+					<span class="font-bold">{self.label}</span>
+					<span class="opacity-70">" · add theme"</span>
+				</summary>
+				<form
+					class="flex flex-wrap items-end gap-2 mt-2"
+					method="post"
+					action=&*self.action
+					hx-post=&*self.action
+					hx-target=("#", self.row_id)
+					hx-swap="outerHTML"
+				>
+					<CsrfField token=self.csrf_token />
+					<input name="name" required placeholder="Song title" value=&*values.name class="input input-bordered input-sm" />
+					<input name="youtube" required placeholder="YouTube link" value=&*values.youtube class="input input-bordered input-sm" />
+					<input name="start" placeholder="start (m:ss)" value=&*values.start class="w-28 input input-bordered input-sm" />
+					<input name="end" placeholder="end (m:ss)" value=&*values.end class="w-28 input input-bordered input-sm" />
+					<button class="btn btn-sm btn-primary">"Add theme"</button>
+				</form>
+				{self.error.map(|error| move |f: &mut HtmlFormatter| write_html!(f, <p class="mt-1 text-error">{&*error.message}</p>))}
+			</details>
+		)
+	}
+}
+
+/// A series in the worklist, with an inline form for its own theme and for
+/// every season that is still missing one.
+pub struct MissingThemeRow {
+	pub series: series::Model,
+	pub seasons: Vec<season::Model>,
+	pub next_page_link: Option<Arc<str>>,
+	/// Confirmation of a theme that was just added from this row.
+	pub added: Option<String>,
+	pub error: Option<ThemeFormError>,
+	pub csrf_token: CsrfToken,
+}
+
+impl HtmlContent for MissingThemeRow {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let next_page_attr = self.next_page_link.as_deref().map(|next_page_link| {
+			Attrs([
+				("hx-get", next_page_link),
+				("hx-trigger", "revealed"),
+				("hx-swap", "afterend"),
+			])
+		});
+
+		let series = &self.series;
+		let series_id = series.id.to_string();
+		let row_id = format!("missing-{series_id}");
+		let error = self.error.as_ref();
+		let error_for = |id: Uuid| error.filter(|e| e.target == id);
+		let views = match series.view_count {
+			1 => "1 view".to_owned(),
+			n => format!("{n} views"),
+		};
+
+		let series_form = series.theme_song_id.is_none().then(|| ThemeForm {
+			row_id: &row_id,
+			action: format!("/series/{series_id}/theme"),
+			label: "Series theme",
+			csrf_token: self.csrf_token.as_ref(),
+			error: error_for(series.id),
+		});
+
+		let complete = series.theme_song_id.is_some() && self.seasons.is_empty();
+
+		// the gap was filled by someone else in the meantime, so there is no
+		// form left to show the error with
+		let has_form = |id: Uuid| {
+			(series.theme_song_id.is_none() && id == series.id) || self.seasons.iter().any(|s| s.id == id)
+		};
+		let orphan_error = error.filter(|e| !has_form(e.target));
+
+		write_html!(f,
+			<li id=&*row_id class="flex flex-col gap-3 p-4 rounded-lg bg-base-200" {next_page_attr}>
+				<div class="flex flex-wrap items-baseline gap-2">
+					<a class="text-xl font-bold link link-hover" href=("/series/", &*series_id) hx-disable>{&*series.name}</a>
+					<span class="opacity-70">{series.year}</span>
+					<span class="badge badge-ghost">{&*views}</span>
+				</div>
+				{self.added.as_deref().map(|added| move |f: &mut HtmlFormatter| write_html!(f,
+					<p class="text-success" role="status">"Added " {added} "."</p>
+				))}
+				{orphan_error.map(|error| move |f: &mut HtmlFormatter| write_html!(f,
+					<p class="text-error" role="status">"Couldn't add the theme song: " {&*error.message}</p>
+				))}
+				{complete.then_some(|f: &mut HtmlFormatter| write_html!(f,
+					<p class="opacity-70">"All theme songs are in, thanks!"</p>
+				))}
+				{series_form}
+				<For items={&self.seasons}>
+					{ |f, season: &season::Model| {
+						let label = match &season.name {
+							Some(name) => format!("Season {} · {name}", season.number),
+							None => format!("Season {:02}", season.number),
+						};
+
+						ThemeForm {
+							row_id: &row_id,
+							action: format!("/series/{series_id}/season/{}/theme", season.id),
+							label: &label,
+							csrf_token: self.csrf_token.as_ref(),
+							error: error_for(season.id),
+						}
+						.fmt(f)
+					} }
+				</For>
+			</li>
+		)
+	}
+}
+
+impl IntoResponse for MissingThemeRow {
+	fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+}
+
+pub struct MissingThemesPage<'a, I> {
+	session: &'a Session,
+	order: WorklistOrder,
+	items: I,
+}
+
+impl<'a, I> MissingThemesPage<'a, I>
+where
+	I: IntoIterator<Item = MissingThemeRow>,
+{
+	pub fn new(session: &'a Session, order: WorklistOrder, items: I) -> Self {
+		Self {
+			session,
+			order,
+			items,
+		}
+	}
+
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+
+	pub fn into_items_fragment_response(self) -> axum::response::Response {
+		Html(Self::items_fragment(self.items)).into_response()
+	}
+
+	fn items_fragment(items: I) -> impl HtmlContent {
+		For {
+			items,
+			children: |f, item| item.fmt(f),
+		}
+	}
+}
+
+impl<'a, I> HtmlContent for MissingThemesPage<'a, I>
+where
+	I: IntoIterator<Item = MissingThemeRow>,
+{
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let selected = self.order;
+
+		write_html!(f,
+			<Template title="Missing themes" session=self.session>
+				<h1 class="mb-2 text-4xl font-bold">"Missing themes"</h1>
+				<p class="mb-8 opacity-70">"Series and seasons still without a theme song. Add one right from the list."</p>
+
+				<div role="tablist" class="mb-4 tabs tabs-boxed w-fit">
+					<For items={WorklistOrder::ALL}>
+						{ |f, order: WorklistOrder| {
+							let class = match order == selected {
+								true => "tab tab-active",
+								false => "tab",
+							};
+
+							write_html!(f,
+								<a role="tab" class=class href=("/missing-themes?order=", order.as_str())>{order.name()}</a>
+							)
+						} }
+					</For>
+				</div>
+
+				<ul class="flex flex-col gap-4" hx-indicator=".htmx-indicator">
+					{Self::items_fragment(self.items)}
+				</ul>
+				<center>
+					<img class="htmx-indicator" width="60" src="/public/img/bars.svg" />
+				</center>
+			</Template>
+		)
+	}
+}
@@ -0,0 +1,211 @@
+use crate::{
+	images,
+	web::views::{
+		index::Attrs,
+		series::{EditMode, EditTarget, ThemePanel},
+		ArtworkSources, Template,
+	},
+};
+use axum::response::IntoResponse;
+use dbost_entities::{movie, theme_song};
+use dbost_session::Session;
+use rstml_component::{write_html, For, HtmlComponent, HtmlContent, HtmlFormatter};
+use rstml_component_axum::Html;
+use std::{fmt, sync::Arc};
+use uuid::Uuid;
+
+#[derive(HtmlComponent)]
+pub struct MovieCard {
+	name: String,
+	id: Uuid,
+	year: Option<i32>,
+	image: Option<String>,
+	image_blurhash: Option<String>,
+	has_theme: bool,
+	next_page_link: Option<Arc<str>>,
+}
+
+impl MovieCard {
+	pub fn new(movie: movie::Model, next_page_link: Option<Arc<str>>) -> Self {
+		Self {
+			name: movie.name,
+			id: movie.id,
+			year: movie.year,
+			image: movie.image,
+			image_blurhash: movie.image_blurhash,
+			has_theme: movie.theme_song_id.is_some(),
+			next_page_link,
+		}
+	}
+}
+
+impl HtmlContent for MovieCard {
+	fn fmt(self, formatter: &mut HtmlFormatter) -> fmt::Result {
+		let next_page_attr = self.next_page_link.as_deref().map(|next_page_link| {
+			Attrs([
+				("hx-get", next_page_link),
+				("hx-trigger", "revealed"),
+				("hx-swap", "afterend"),
+			])
+		});
+
+		let placeholder_attr = self
+			.image_blurhash
+			.as_deref()
+			.and_then(images::placeholder)
+			.map(|url| ("style", format!("background-image: url('{url}');")));
+
+		let id = self.id.to_string();
+		write_html!(formatter,
+			<li
+				id=("movie-card-", &*id)
+				class="grid grid-cols-1 row-span-2 gap-0 overflow-hidden shadow-xl grid-rows-series-card rounded-box bg-base-100 series-card contain-paint"
+				{next_page_attr}
+			>
+				<a class="contents" href=("/movies/", &*id)>
+					<picture
+						class=match self.image.is_some() {
+							true => "bg-center bg-cover series-image rounded-box aspect-[2/3]",
+							false => "series-image rounded-box",
+						}
+						{placeholder_attr}
+					>
+						<ArtworkSources
+							url=self.image.as_deref()
+							sizes="(min-width: 1536px) 16vw, (min-width: 1280px) 20vw, (min-width: 1024px) 25vw, (min-width: 768px) 33vw, (min-width: 640px) 50vw, 100vw"
+							max_width=960 />
+						<img src=self.image.as_deref().map(images::proxied_url) class="object-cover w-full h-full" alt="" />
+					</picture>
+					<div class="p-4 text-base bg-base-100/80 series-text">
+						<h2 class="card-title text-ellipsis line-clamp-2" hx-disable>{&*self.name}</h2>
+						<p>
+							{self.year}
+							{(!self.has_theme).then_some(|f: &mut HtmlFormatter| {
+								write_html!(f, <span class="ml-2 badge badge-ghost">"No theme"</span>)
+							})}
+						</p>
+					</div>
+				</a>
+			</li>
+		)
+	}
+}
+
+pub struct MoviesPage<'a, I> {
+	session: &'a Session,
+	items: I,
+}
+
+impl<'a, I> MoviesPage<'a, I>
+where
+	I: IntoIterator<Item = MovieCard>,
+{
+	pub fn new(session: &'a Session, items: I) -> Self {
+		Self { session, items }
+	}
+
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+
+	pub fn into_items_fragment_response(self) -> axum::response::Response {
+		Html(Self::items_fragment(self.items)).into_response()
+	}
+
+	fn items_fragment(items: I) -> impl HtmlContent {
+		For {
+			items,
+			children: |f, item| item.fmt(f),
+		}
+	}
+}
+
+impl<'a, I> HtmlContent for MoviesPage<'a, I>
+where
+	I: IntoIterator<Item = MovieCard>,
+{
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		write_html!(f,
+			<Template title="Movies" session=self.session>
+				<h1 class="mb-8 text-4xl font-bold">Movies</h1>
+
+				<ul
+					class="grid grid-cols-1 gap-4 auto-rows-cards sm:grid-cols-2 md:grid-cols-3 lg:grid-cols-4 xl:grid-cols-5 2xl:grid-cols-6"
+					hx-indicator=".htmx-indicator">
+					{Self::items_fragment(self.items)}
+				</ul>
+				<center>
+					<img class="htmx-indicator" width="60" src="/public/img/bars.svg" />
+				</center>
+			</Template>
+		)
+	}
+}
+
+pub struct MoviePage<'a> {
+	session: &'a Session,
+	movie: movie::Model,
+	theme: Option<theme_song::Model>,
+}
+
+impl<'a> MoviePage<'a> {
+	pub fn new(session: &'a Session, movie: movie::Model, theme: Option<theme_song::Model>) -> Self {
+		Self {
+			session,
+			movie,
+			theme,
+		}
+	}
+
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+}
+
+impl<'a> HtmlContent for MoviePage<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let placeholder_attr = self
+			.movie
+			.image_blurhash
+			.as_deref()
+			.and_then(images::placeholder)
+			.map(|url| ("style", format!("background-image: url('{url}');")));
+
+		write_html!(f,
+			<Template title=&*self.movie.name session=self.session>
+				<div class="rounded-lg min-h-72 hero">
+					<div class="flex-col hero-content lg:flex-row">
+						<picture class="flex-none w-full lg:self-start sm:w-96 contain-paint">
+							<ArtworkSources
+								url=self.movie.image.as_deref()
+								sizes="(min-width: 640px) 384px, 100vw"
+								max_width=1280 />
+							<img
+								src=self.movie.image.as_deref().map(images::proxied_url)
+								class=match self.movie.image.is_some() {
+									true => "w-full bg-center bg-cover rounded-lg shadow-2xl aspect-[2/3] object-cover",
+									false => "rounded-lg shadow-2xl",
+								}
+								{placeholder_attr}
+								alt=(&*self.movie.name, " thumbnail") />
+						</picture>
+						<div class="flex-1">
+							<h1 class="text-5xl font-bold">
+								{&*self.movie.name}
+								{self.movie.year.map(|year| move |f: &mut HtmlFormatter| {
+									write_html!(f, <span class="ml-4 text-3xl font-normal opacity-70">{year}</span>)
+								})}
+							</h1>
+							<p class="py-6" hx-disable>{self.movie.description.as_deref()}</p>
+
+							<ThemePanel
+								target=EditTarget::Movie(self.movie.id)
+								mode=EditMode::Normal { can_edit: false }
+								video=self.theme.as_ref() />
+						</div>
+					</div>
+				</div>
+			</Template>
+		)
+	}
+}
@@ -0,0 +1,154 @@
+use crate::web::views::{CsrfField, Template};
+use axum::response::IntoResponse;
+use dbost_entities::{api_key, user};
+use dbost_services::api_keys::{Scope, Scopes};
+use dbost_session::Session;
+use rstml_component::{write_html, For, HtmlContent, HtmlFormatter};
+use rstml_component_axum::Html;
+use sea_orm::prelude::TimeDateTime;
+use std::fmt;
+
+/// How long a new key can be valid for, in days.
+pub const KEY_LIFETIMES: [(&str, Option<i64>); 4] = [
+	("30 days", Some(30)),
+	("90 days", Some(90)),
+	("1 year", Some(365)),
+	("Never expires", None),
+];
+
+struct ApiKeyRow<'a> {
+	key: &'a api_key::Model,
+	now: TimeDateTime,
+	csrf_token: &'a str,
+}
+
+impl<'a> HtmlContent for ApiKeyRow<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let key = self.key;
+		let scopes = Scopes::parse(&key.scopes);
+		let scopes = Scope::ALL
+			.into_iter()
+			.filter(|s| scopes.contains(*s))
+			.map(Scope::as_str)
+			.collect::<Vec<_>>();
+		let created = key.created_at.date().to_string();
+		let last_used = key
+			.last_used_at
+			.map(|at| at.date().to_string())
+			.unwrap_or_else(|| "Never".to_owned());
+		let expired = key.expires_at.is_some_and(|at| at <= self.now);
+		let expires = key
+			.expires_at
+			.map(|at| at.date().to_string())
+			.unwrap_or_else(|| "Never".to_owned());
+		let revoke = format!("/profile/api-keys/{}/revoke", key.id);
+
+		write_html!(f,
+			<tr>
+				<td class="font-bold">{&*key.name}</td>
+				<td>
+					<For items={scopes}>
+						{ |f, scope: &str| write_html!(f, <span class="mr-1 badge badge-ghost">{scope}</span>) }
+					</For>
+				</td>
+				<td>{&*created}</td>
+				<td>
+					{&*expires}
+					{expired.then_some(|f: &mut HtmlFormatter| write_html!(f, <span class="ml-1 badge badge-error">"expired"</span>))}
+				</td>
+				<td>{&*last_used}</td>
+				<td>
+					<form method="post" action=&*revoke>
+						<CsrfField token=self.csrf_token />
+						<button class="btn btn-xs btn-error btn-outline">"Revoke"</button>
+					</form>
+				</td>
+			</tr>
+		)
+	}
+}
+
+pub struct ProfilePage<'a> {
+	pub session: &'a Session,
+	pub csrf_token: &'a str,
+	pub user: &'a user::Model,
+	pub keys: Vec<api_key::Model>,
+	pub now: TimeDateTime,
+	/// A key that was just created, shown this once.
+	pub created: Option<(String, String)>,
+	pub error: Option<String>,
+}
+
+impl<'a> ProfilePage<'a> {
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+}
+
+impl<'a> HtmlContent for ProfilePage<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let user = self.user;
+		let now = self.now;
+		let csrf_token = self.csrf_token;
+
+		write_html!(f,
+			<Template title="Profile" session=self.session>
+				<h1 class="mb-2 text-4xl font-bold">{&*user.display_name}</h1>
+				<p class="mb-8 opacity-70">{&*user.email}</p>
+
+				<h2 class="mb-2 text-2xl font-bold">"API keys"</h2>
+				<p class="mb-4">
+					"Keys give scripts and media server plugins access to the "
+					<a class="link" href="/api/docs">"API"</a>
+					". Send them in the " <code>"x-api-key"</code> " header."
+				</p>
+
+				{self.created.as_ref().map(|(name, token)| move |f: &mut HtmlFormatter| write_html!(f,
+					<div class="flex-col items-start mb-4 alert alert-success">
+						<span>"Created " <span class="font-bold">{&**name}</span> ". Copy the key now, it won't be shown again."</span>
+						<code class="p-2 break-all rounded select-all bg-base-100">{&**token}</code>
+					</div>
+				))}
+				{self.error.as_deref().map(|error| move |f: &mut HtmlFormatter| write_html!(f,
+					<div class="mb-4 alert alert-error">{error}</div>
+				))}
+
+				<form class="flex flex-wrap items-end gap-2 mb-6" method="post" action="/profile/api-keys">
+					<CsrfField token=csrf_token />
+					<input name="name" required placeholder="Key name" class="input input-bordered input-sm" />
+					<select name="expires_in_days" class="select select-bordered select-sm">
+						<For items={KEY_LIFETIMES}>
+							{ |f, (label, days): (&str, Option<i64>)| {
+								let value = days.map(|d| d.to_string()).unwrap_or_default();
+								write_html!(f, <option value=&*value>{label}</option>)
+							} }
+						</For>
+					</select>
+					<label class="gap-2 cursor-pointer label">
+						<input type="checkbox" name="write" value="true" class="checkbox checkbox-sm" />
+						<span class="label-text">"Can add and change theme songs"</span>
+					</label>
+					<button class="btn btn-sm btn-primary">"Create key"</button>
+				</form>
+
+				{(!self.keys.is_empty()).then_some(|f: &mut HtmlFormatter| write_html!(f,
+					<table class="table table-sm">
+						<thead>
+							<tr>
+								<th>"Name"</th><th>"Scopes"</th><th>"Created"</th><th>"Expires"</th><th>"Last used"</th><th></th>
+							</tr>
+						</thead>
+						<tbody>
+							<For items={&self.keys}>
+								{ |f, key: &api_key::Model| ApiKeyRow { key, now, csrf_token }.fmt(f) }
+							</For>
+						</tbody>
+					</table>
+				))}
+				{self.keys.is_empty().then_some(|f: &mut HtmlFormatter| write_html!(f,
+					<p class="opacity-70">"You don't have any API keys yet."</p>
+				))}
+			</Template>
+		)
+	}
+}
@@ -1,14 +1,19 @@
-use crate::web::views::Template;
+use crate::{
+	images::{self, ImageFormat},
+	web::views::{ArtworkSources, CsrfField, Template},
+};
 use axum::response::IntoResponse;
-use dbost_entities::{season, series, theme_song};
-use dbost_session::Session;
+use dbost_entities::{episode, episode_marker, genre, season, series, theme_song};
+use dbost_services::markers::{self, MarkerKind};
+use dbost_session::{CsrfToken, Session};
 use indexmap::IndexMap;
 use rstml_component::{
 	write_html, For, HtmlAttributeFormatter, HtmlAttributeValue, HtmlComponent, HtmlContent,
 	HtmlFormatter,
 };
 use rstml_component_axum::Html;
-use std::fmt;
+use std::{collections::HashMap, fmt};
+use tvdb_client::SeasonType;
 use uuid::Uuid;
 
 #[derive(HtmlComponent)]
@@ -23,10 +28,10 @@ impl<'a> HtmlContent for VideoPlayer<'a> {
 }
 
 #[derive(HtmlComponent)]
-struct ThemePanel<'a> {
-	target: EditTarget,
-	mode: EditMode,
-	video: Option<&'a theme_song::Model>,
+pub(super) struct ThemePanel<'a> {
+	pub target: EditTarget,
+	pub mode: EditMode,
+	pub video: Option<&'a theme_song::Model>,
 }
 
 impl<'a> HtmlContent for ThemePanel<'a> {
@@ -81,6 +86,12 @@ impl<'a> HtmlContent for EditButton<'a> {
 struct SeasonRow<'a> {
 	series: &'a series::Model,
 	season: &'a season::Model,
+	episodes: &'a [episode::Model],
+	markers: &'a HashMap<i32, Vec<episode_marker::Model>>,
+	theme: Option<&'a theme_song::Model>,
+	series_theme: Option<&'a theme_song::Model>,
+	can_edit: bool,
+	csrf_token: &'a str,
 }
 
 impl<'a> HtmlContent for SeasonRow<'a> {
@@ -94,6 +105,20 @@ impl<'a> HtmlContent for SeasonRow<'a> {
 			.as_deref()
 			.unwrap_or(&*season_number_display);
 
+		// seasons without their own image fall back to the series poster
+		let (image, image_blurhash) = match self.season.image.as_deref() {
+			Some(image) => (Some(image), self.season.image_blurhash.as_deref()),
+			None => (
+				self.series.image.as_deref(),
+				self.series.image_blurhash.as_deref(),
+			),
+		};
+		let placeholder_attr = image_blurhash
+			.and_then(images::placeholder)
+			.map(|url| ("style", format!("background-image: url('{url}');")));
+
+		let marker_themes = marker_themes(self.theme, self.series_theme);
+
 		write_html!(f,
 			<li
 				id=(&*series_id, "/season/", &*season_id)
@@ -102,30 +127,203 @@ impl<'a> HtmlContent for SeasonRow<'a> {
 				<picture
 					class="self-center flex-none w-full sm:self-start sm:w-56"
 				>
+					<ArtworkSources
+						url=image
+						sizes="(min-width: 640px) 224px, 100vw"
+						max_width=1280 />
 					<img
-						src=self.season.image.as_deref().or(self.series.image.as_deref())
-						class="mx-auto rounded-lg shadow-2xl"
-						referrerpolicy="no-referrer"
+						src=image.map(images::proxied_url)
+						class=match image.is_some() {
+							true => "w-full mx-auto bg-center bg-cover rounded-lg shadow-2xl aspect-[2/3] object-cover",
+							false => "mx-auto rounded-lg shadow-2xl",
+						}
+						{placeholder_attr}
 						alt=(season_name, " thumbnail") />
 				</picture>
 				<div class="flex-1">
 					<h2 class="text-3xl font-bold tooltip" data-tip=&*season_number_display>{season_name}</h2>
 					<p class="py-6" hx-disable>{self.season.description.as_deref()}</p>
+
+					<ThemePanel
+						target=EditTarget::Season(self.series.id, self.season.id)
+						mode=EditMode::Normal { can_edit: false }
+						video=self.theme />
+
+					<EpisodeList
+						series_id=self.series.id
+						season_id=self.season.id
+						episodes=self.episodes
+						markers=self.markers
+						themes=&marker_themes
+						can_edit=self.can_edit
+						csrf_token=self.csrf_token />
 				</div>
 			</li>
 		)
 	}
 }
 
+/// The themes an episode marker can point at: the season theme, and the series
+/// theme if it differs.
+pub fn marker_themes<'a>(
+	season_theme: Option<&'a theme_song::Model>,
+	series_theme: Option<&'a theme_song::Model>,
+) -> Vec<&'a theme_song::Model> {
+	let mut themes = season_theme.into_iter().collect::<Vec<_>>();
+	themes.extend(series_theme.filter(|t| Some(t.id) != season_theme.map(|s| s.id)));
+	themes
+}
+
+#[derive(HtmlComponent)]
+struct EpisodeList<'a> {
+	series_id: Uuid,
+	season_id: Uuid,
+	episodes: &'a [episode::Model],
+	markers: &'a HashMap<i32, Vec<episode_marker::Model>>,
+	themes: &'a [&'a theme_song::Model],
+	can_edit: bool,
+	csrf_token: &'a str,
+}
+
+impl<'a> HtmlContent for EpisodeList<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		if self.episodes.is_empty() {
+			return Ok(());
+		}
+
+		write_html!(f,
+			<details class="mt-4">
+				<summary class="text-xl font-bold cursor-pointer">"Episodes (" {self.episodes.len()} ")"</summary>
+				<ol class="mt-2 divide-y divide-base-content/10">
+					<For items={self.episodes}>
+						{ |f, e: &episode::Model| {
+							write_html!(f,
+								<EpisodeRow
+									series_id=self.series_id
+									season_id=self.season_id
+									episode=e
+									markers=self.markers.get(&e.tvdb_id).map(Vec::as_slice).unwrap_or_default()
+									themes=self.themes
+									can_edit=self.can_edit
+									csrf_token=self.csrf_token
+									error=None />
+							)
+						} }
+					</For>
+				</ol>
+			</details>
+		)
+	}
+}
+
+/// A single episode with its intro/outro markers. Logged in users get a form
+/// to record a marker, which re-renders the row.
+#[derive(HtmlComponent)]
+pub struct EpisodeRow<'a> {
+	pub series_id: Uuid,
+	pub season_id: Uuid,
+	pub episode: &'a episode::Model,
+	pub markers: &'a [episode_marker::Model],
+	/// The themes a marker can point at.
+	pub themes: &'a [&'a theme_song::Model],
+	pub can_edit: bool,
+	pub csrf_token: &'a str,
+	pub error: Option<&'a str>,
+}
+
+impl<'a> EpisodeRow<'a> {
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+}
+
+impl<'a> HtmlContent for EpisodeRow<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let e = self.episode;
+		let row_id = format!("episode-{}", e.id);
+		let number = format!("E{:02}", e.number);
+		let absolute = e
+			.absolute_number
+			.filter(|n| *n != e.number)
+			.map(|n| ("data-tip", format!("Episode {n} overall")));
+
+		let marker_form = (self.can_edit && !self.themes.is_empty()).then_some(|f: &mut HtmlFormatter| {
+			let action = format!(
+				"/series/{}/season/{}/episode/{}/markers",
+				self.series_id, self.season_id, e.id
+			);
+
+			let open_attribute = self.error.is_some().then_some(("open", ()));
+
+			write_html!(f,
+				<details class="text-sm" {open_attribute}>
+					<summary class="cursor-pointer opacity-70">"Add marker"</summary>
+					<form
+						class="flex flex-wrap items-end gap-2 mt-2"
+						method="post"
+						action=&*action
+						hx-post=&*action
+						hx-target=("#", &*row_id)
+						hx-swap="outerHTML"
+					>
+						<CsrfField token=self.csrf_token />
+						<select name="kind" class="select select-bordered select-sm">
+							<For items={MarkerKind::ALL}>
+								{ |f, kind: MarkerKind| write_html!(f, <option value=kind.as_str()>{kind.name()}</option>) }
+							</For>
+						</select>
+						<select name="theme_song_id" class="select select-bordered select-sm">
+							<For items={self.themes}>
+								{ |f, t: &&theme_song::Model| write_html!(f, <option value=t.id.to_string()>{&*t.name}</option>) }
+							</For>
+						</select>
+						<input name="start" required placeholder="start (m:ss)" class="w-28 input input-bordered input-sm" />
+						<input name="end" required placeholder="end (m:ss)" class="w-28 input input-bordered input-sm" />
+						<button class="btn btn-sm btn-primary">"Save"</button>
+					</form>
+					{self.error.map(|error| move |f: &mut HtmlFormatter| write_html!(f, <p class="mt-1 text-error">{error}</p>))}
+				</details>
+			)
+		});
+
+		write_html!(f,
+			<li id=&*row_id class="py-1">
+				<div class="flex gap-4">
+					<span class="font-mono opacity-70 tooltip tooltip-right" {absolute}>{number}</span>
+					<span class="flex-1" hx-disable>
+						{|f: &mut HtmlFormatter| match e.name.as_deref() {
+							Some(name) => f.write_content(name),
+							None => write_html!(f, "Episode " {e.number}),
+						}}
+					</span>
+					<For items={self.markers}>
+						{ |f, m: &episode_marker::Model| {
+							let kind = m.kind.parse::<MarkerKind>().map(MarkerKind::name).unwrap_or("Marker");
+							write_html!(f,
+								<span class="badge badge-outline">
+									{kind} " " {markers::format_timestamp(m.start_ms as u32)} "–" {markers::format_timestamp(m.end_ms as u32)}
+								</span>
+							)
+						} }
+					</For>
+					<span class="opacity-70">{e.air_date.map(|d| d.to_string())}</span>
+				</div>
+				{marker_form}
+			</li>
+		)
+	}
+}
+
 pub enum SeriesEdit {
 	None,
 	Series,
 	// Season(Uuid),
 }
 
-enum EditTarget {
+pub(super) enum EditTarget {
 	Series(Uuid),
-	// Season(Uuid, Uuid),
+	Season(Uuid, Uuid),
+	Movie(Uuid),
 }
 
 impl EditTarget {
@@ -135,6 +333,8 @@ impl EditTarget {
 			fn fmt(self, f: &mut HtmlAttributeFormatter) -> fmt::Result {
 				match self.0 {
 					EditTarget::Series(series) => HtmlAttributeValue::fmt(series.to_string(), f),
+					EditTarget::Season(_, season) => HtmlAttributeValue::fmt(season.to_string(), f),
+					EditTarget::Movie(movie) => HtmlAttributeValue::fmt(movie.to_string(), f),
 				}
 			}
 		}
@@ -150,6 +350,19 @@ impl EditTarget {
 					EditTarget::Series(series) => {
 						HtmlAttributeValue::fmt(("/series/", series.to_string(), "/edit"), f)
 					}
+					EditTarget::Season(series, season) => HtmlAttributeValue::fmt(
+						(
+							"/series/",
+							series.to_string(),
+							"/season/",
+							season.to_string(),
+							"/edit",
+						),
+						f,
+					),
+					EditTarget::Movie(movie) => {
+						HtmlAttributeValue::fmt(("/movies/", movie.to_string(), "/edit"), f)
+					}
 				}
 			}
 		}
@@ -158,7 +371,7 @@ impl EditTarget {
 	}
 }
 
-enum EditMode {
+pub(super) enum EditMode {
 	Normal { can_edit: bool },
 	Edit,
 }
@@ -172,27 +385,149 @@ impl EditMode {
 	}
 }
 
+/// The seasons shown on the series page, in the selected ordering.
+pub struct SeriesSeasons {
+	pub seasons: Vec<season::Model>,
+	pub specials: Vec<season::Model>,
+	pub episodes: HashMap<Uuid, Vec<episode::Model>>,
+	/// Markers by tvdb episode id.
+	pub markers: HashMap<i32, Vec<episode_marker::Model>>,
+	pub orderings: Vec<SeasonType>,
+	pub selected: SeasonType,
+}
+
+#[derive(HtmlComponent)]
+struct SeasonOrderingTabs<'a> {
+	series_id: Uuid,
+	orderings: &'a [SeasonType],
+	selected: SeasonType,
+}
+
+impl<'a> HtmlContent for SeasonOrderingTabs<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		// nothing to switch between
+		if self.orderings.len() < 2 {
+			return Ok(());
+		}
+
+		let series_id = self.series_id.to_string();
+		write_html!(f,
+			<div role="tablist" class="tabs tabs-boxed w-fit">
+				<For items={self.orderings.iter().copied()}>
+					{ |f, season_type: SeasonType| {
+						let query = match season_type {
+							SeasonType::Official => None,
+							t => Some(("?order=", t.as_str())),
+						};
+						let class = match season_type == self.selected {
+							true => "tab tab-active",
+							false => "tab",
+						};
+
+						write_html!(f,
+							<a role="tab" class=class href=("/series/", &*series_id, query)>
+								{season_type.name()}
+							</a>
+						)
+					} }
+				</For>
+			</div>
+		)
+	}
+}
+
+/// Year, status, network, language and genres. Genres and the network link to
+/// their own pages, the rest to the index filtered by them.
+#[derive(HtmlComponent)]
+struct SeriesMetadata<'a> {
+	series: &'a series::Model,
+	genres: &'a [genre::Model],
+}
+
+impl<'a> HtmlContent for SeriesMetadata<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let facts = [
+			self
+				.series
+				.year
+				.map(|year| (format!("/?year={year}"), year.to_string())),
+			self.series.status.as_deref().map(|status| {
+				(
+					format!("/?status={}", status.to_lowercase()),
+					status.to_owned(),
+				)
+			}),
+			self
+				.series
+				.network
+				.as_deref()
+				.zip(self.series.network_slug.as_deref())
+				.map(|(network, slug)| (format!("/networks/{slug}"), network.to_owned())),
+			self
+				.series
+				.original_language
+				.as_deref()
+				.map(|language| (format!("/?language={language}"), language.to_owned())),
+		];
+
+		let facts = facts.into_iter().flatten().collect::<Vec<_>>();
+		if facts.is_empty() && self.genres.is_empty() {
+			return Ok(());
+		}
+
+		write_html!(f,
+			<div class="flex flex-wrap items-center gap-2 pt-4">
+				<For items={facts}>
+					{ |f, (href, label): (String, String)| write_html!(f,
+						<a class="badge badge-neutral" href=href>{label}</a>
+					) }
+				</For>
+				<For items={self.genres}>
+					{ |f, genre: &genre::Model| write_html!(f,
+						<a class="badge badge-outline" href=("/genres/", &*genre.slug)>{&*genre.name}</a>
+					) }
+				</For>
+			</div>
+		)
+	}
+}
+
+pub struct SeriesArtwork {
+	pub background: Option<String>,
+	pub logo: Option<String>,
+}
+
 pub struct SeriesPage<'a> {
 	session: &'a Session,
+	csrf_token: &'a CsrfToken,
 	series: series::Model,
-	seasons: Vec<season::Model>,
+	genres: Vec<genre::Model>,
+	seasons: SeriesSeasons,
 	themes: IndexMap<Uuid, theme_song::Model>,
+	artwork: SeriesArtwork,
 	edit: SeriesEdit,
 }
 
 impl<'a> SeriesPage<'a> {
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		session: &'a Session,
+		csrf_token: &'a CsrfToken,
 		series: series::Model,
-		seasons: Vec<season::Model>,
+		genres: Vec<genre::Model>,
+		seasons: SeriesSeasons,
 		themes: IndexMap<Uuid, theme_song::Model>,
+		artwork: SeriesArtwork,
 		edit: SeriesEdit,
 	) -> Self {
 		Self {
 			session,
+			csrf_token,
 			series,
+			genres,
 			seasons,
 			themes,
+			artwork,
 			edit,
 		}
 	}
@@ -204,40 +539,119 @@ impl<'a> SeriesPage<'a> {
 
 impl<'a> HtmlContent for SeriesPage<'a> {
 	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let background_attr = self
+			.artwork
+			.background
+			.as_deref()
+			.map(|url| images::resized_url(url, 1920, ImageFormat::WebP))
+			.map(|url| ("style", format!("background-image: url('{url}');")));
+
+		let placeholder_attr = self
+			.series
+			.image_blurhash
+			.as_deref()
+			.and_then(images::placeholder)
+			.map(|url| ("style", format!("background-image: url('{url}');")));
+
+		let series_theme = self
+			.series
+			.theme_song_id
+			.and_then(|id| self.themes.get(&id));
+		let can_edit = self.session.user().is_some();
+
+		let background_overlay = self.artwork.background.is_some().then_some(|f: &mut HtmlFormatter| {
+			write_html!(f, <div class="rounded-lg hero-overlay bg-base-100/80"></div>)
+		});
+
 		write_html!(f,
 			<Template title=&*self.series.name session=self.session>
-				<div class="rounded-lg min-h-72 hero">
+				<div class="bg-center bg-cover rounded-lg min-h-72 hero" {background_attr}>
+					{background_overlay}
 					<div class="flex-col hero-content lg:flex-row">
 						<picture
 							class="flex-none w-full lg:self-start sm:w-96 contain-paint"
 							style="view-transition-name: series-image;"
 						>
+							<ArtworkSources
+								url=self.series.image.as_deref()
+								sizes="(min-width: 640px) 384px, 100vw"
+								max_width=1280 />
 							<img
-								src=self.series.image.as_deref()
-								class="rounded-lg shadow-2xl"
-								referrerpolicy="no-referrer"
+								src=self.series.image.as_deref().map(images::proxied_url)
+								class=match self.series.image.is_some() {
+									true => "w-full bg-center bg-cover rounded-lg shadow-2xl aspect-[2/3] object-cover",
+									false => "rounded-lg shadow-2xl",
+								}
+								{placeholder_attr}
 								alt=(&*self.series.name, " thumbnail") />
 						</picture>
 						<div class="flex-1">
-							<h1 class="text-5xl font-bold">{&*self.series.name}</h1>
+							<h1 class="text-5xl font-bold">
+								{|f: &mut HtmlFormatter| match self.artwork.logo.as_deref() {
+									None => f.write_content(&*self.series.name),
+									Some(logo) => write_html!(f,
+										<img
+											src=images::resized_url(logo, 640, ImageFormat::WebP)
+											class="max-h-32"
+											alt=&*self.series.name />
+									),
+								}}
+							</h1>
+							<SeriesMetadata series=&self.series genres=&self.genres />
 							<p class="py-6" hx-disable>{self.series.description.as_deref()}</p>
 
 							<ThemePanel
 								target=EditTarget::Series(self.series.id)
-								mode=EditMode::series(&self.edit, self.session.user().is_some())
-								video=self.series.theme_song_id.map(|id| &self.themes[&id]) />
+								mode=EditMode::series(&self.edit, can_edit)
+								video=series_theme />
 						</div>
 					</div>
 				</div>
-				<ul class="mt-20 space-y-8">
-					<For items={self.seasons}>
-						{ |f, s| {
-							write_html!(f,
-								<SeasonRow series=&self.series season=&s />
-							)
-						} }
-					</For>
-				</ul>
+				<section class="mt-20 space-y-8">
+					<SeasonOrderingTabs
+						series_id=self.series.id
+						orderings=&self.seasons.orderings
+						selected=self.seasons.selected />
+					<ul class="space-y-8">
+						<For items={&self.seasons.seasons}>
+							{ |f, s: &season::Model| {
+								write_html!(f,
+									<SeasonRow
+										series=&self.series
+										season=s
+										episodes=self.seasons.episodes.get(&s.id).map(Vec::as_slice).unwrap_or_default()
+										markers=&self.seasons.markers
+										theme=s.theme_song_id.and_then(|id| self.themes.get(&id))
+										series_theme=series_theme
+										can_edit=can_edit
+										csrf_token=self.csrf_token.as_ref() />
+								)
+							} }
+						</For>
+					</ul>
+					{(!self.seasons.specials.is_empty()).then_some(|f: &mut HtmlFormatter| write_html!(f,
+						<details class="collapse collapse-arrow bg-base-200">
+							<summary class="text-3xl font-bold collapse-title">"Specials"</summary>
+							<ul class="space-y-8 collapse-content">
+								<For items={&self.seasons.specials}>
+									{ |f, s: &season::Model| {
+										write_html!(f,
+											<SeasonRow
+												series=&self.series
+												season=s
+												episodes=self.seasons.episodes.get(&s.id).map(Vec::as_slice).unwrap_or_default()
+												markers=&self.seasons.markers
+												theme=s.theme_song_id.and_then(|id| self.themes.get(&id))
+												series_theme=series_theme
+												can_edit=can_edit
+												csrf_token=self.csrf_token.as_ref() />
+										)
+									} }
+								</For>
+							</ul>
+						</details>
+					))}
+				</section>
 			</Template>
 		)
 	}
@@ -0,0 +1,165 @@
+use crate::web::views::Template;
+use axum::response::IntoResponse;
+use dbost_services::stats::{
+	percentage, Contributor, GenreCoverage, Stats, WeekCount, YearCoverage,
+};
+use dbost_session::Session;
+use rstml_component::{write_html, For, HtmlContent, HtmlFormatter};
+use rstml_component_axum::Html;
+use std::fmt;
+
+/// Formats a share as a whole percentage.
+fn percent(part: i64, total: i64) -> String {
+	format!("{:.0}%", percentage(part, total))
+}
+
+struct CoverageBar {
+	part: i64,
+	total: i64,
+}
+
+impl HtmlContent for CoverageBar {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let value = percentage(self.part, self.total).round().to_string();
+		let label = percent(self.part, self.total);
+
+		write_html!(f,
+			<div class="flex items-center gap-2">
+				<progress class="w-32 progress progress-primary" value=&*value max="100"></progress>
+				<span class="text-sm tabular-nums">{&*label}</span>
+			</div>
+		)
+	}
+}
+
+pub struct StatsPage<'a> {
+	session: &'a Session,
+	stats: Stats,
+}
+
+impl<'a> StatsPage<'a> {
+	pub fn new(session: &'a Session, stats: Stats) -> Self {
+		Self { session, stats }
+	}
+
+	pub fn into_response(self) -> axum::response::Response {
+		Html(self).into_response()
+	}
+}
+
+impl<'a> HtmlContent for StatsPage<'a> {
+	fn fmt(self, f: &mut HtmlFormatter) -> fmt::Result {
+		let stats = self.stats;
+		let series = stats.series as i64;
+		let series_with_theme = stats.series_with_theme as i64;
+		let seasons = stats.seasons as i64;
+		let seasons_with_theme = stats.seasons_with_theme as i64;
+		let series_coverage = percent(series_with_theme, series);
+		let seasons_coverage = percent(seasons_with_theme, seasons);
+		let busiest_week = stats
+			.themes_per_week
+			.iter()
+			.map(|w| w.themes)
+			.max()
+			.unwrap_or_default();
+		let contributors = &stats.top_contributors;
+
+		write_html!(f,
+			<Template title="Statistics" session=self.session>
+				<h1 class="mb-8 text-4xl font-bold">"Statistics"</h1>
+
+				<div class="w-full mb-8 shadow stats stats-vertical md:stats-horizontal">
+					<div class="stat">
+						<div class="stat-title">"Series"</div>
+						<div class="stat-value">{series}</div>
+						<div class="stat-desc">{&*series_coverage} " with a theme song"</div>
+					</div>
+					<div class="stat">
+						<div class="stat-title">"Seasons"</div>
+						<div class="stat-value">{seasons}</div>
+						<div class="stat-desc">{&*seasons_coverage} " with a theme song"</div>
+					</div>
+					<div class="stat">
+						<div class="stat-title">"Theme songs"</div>
+						<div class="stat-value">{stats.themes}</div>
+					</div>
+				</div>
+
+				<div class="grid grid-cols-1 gap-8 lg:grid-cols-2">
+					<section>
+						<h2 class="mb-4 text-2xl font-bold">"New theme songs per week"</h2>
+						<div class="flex items-end h-40 gap-1 p-4 rounded-lg bg-base-200">
+							<For items={&stats.themes_per_week}>
+								{ |f, week: &WeekCount| {
+									let height = format!("height: {:.0}%", percentage(week.themes, busiest_week.max(1)));
+									let title = format!("Week of {}: {} theme songs", week.week, week.themes);
+
+									write_html!(f,
+										<div class="flex flex-col justify-end flex-1 h-full" title=&*title>
+											<div class="rounded-t bg-primary min-h-[2px]" style=&*height></div>
+										</div>
+									)
+								} }
+							</For>
+						</div>
+
+						<h2 class="mt-8 mb-4 text-2xl font-bold">"Top contributors"</h2>
+						{(!contributors.is_empty()).then_some(|f: &mut HtmlFormatter| write_html!(f,
+							<ol class="list-decimal list-inside">
+								<For items={contributors}>
+									{ |f, contributor: &Contributor| write_html!(f,
+										<li>
+											<span class="font-bold">{&*contributor.display_name}</span>
+											<span class="opacity-70">" · " {contributor.themes} " theme songs"</span>
+										</li>
+									) }
+								</For>
+							</ol>
+						))}
+						{contributors.is_empty().then_some(|f: &mut HtmlFormatter| write_html!(f,
+							<p class="opacity-70">"No theme songs have been credited yet."</p>
+						))}
+					</section>
+
+					<section>
+						<h2 class="mb-4 text-2xl font-bold">"Coverage by genre"</h2>
+						<table class="table table-sm">
+							<thead>
+								<tr><th>"Genre"</th><th>"Series"</th><th>"With theme"</th></tr>
+							</thead>
+							<tbody>
+								<For items={&stats.coverage_by_genre}>
+									{ |f, genre: &GenreCoverage| write_html!(f,
+										<tr>
+											<td><a class="link link-hover" href=("/genres/", &*genre.slug)>{&*genre.name}</a></td>
+											<td>{genre.series}</td>
+											<td><CoverageBar part={genre.with_theme} total={genre.series} /></td>
+										</tr>
+									) }
+								</For>
+							</tbody>
+						</table>
+
+						<h2 class="mt-8 mb-4 text-2xl font-bold">"Coverage by year"</h2>
+						<table class="table table-sm">
+							<thead>
+								<tr><th>"Year"</th><th>"Series"</th><th>"With theme"</th></tr>
+							</thead>
+							<tbody>
+								<For items={&stats.coverage_by_year}>
+									{ |f, year: &YearCoverage| write_html!(f,
+										<tr>
+											<td>{year.year}</td>
+											<td>{year.series}</td>
+											<td><CoverageBar part={year.with_theme} total={year.series} /></td>
+										</tr>
+									) }
+								</For>
+							</tbody>
+						</table>
+					</section>
+				</div>
+			</Template>
+		)
+	}
+}
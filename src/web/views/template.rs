@@ -11,9 +11,9 @@ struct NavSearchBox;
 impl HtmlContent for NavSearchBox {
 	fn fmt(self, formatter: &mut HtmlFormatter) -> fmt::Result {
 		write_html!(formatter,
-			<div class="form-control">
-				<input type="text" placeholder="Search" class="w-24 input input-bordered sm:w-auto" />
-			</div>
+			<form class="form-control" action="/" method="get" role="search">
+				<input type="search" name="q" placeholder="Search" class="w-24 input input-bordered sm:w-auto" />
+			</form>
 		)
 	}
 }
@@ -57,7 +57,8 @@ impl<'a> HtmlContent for UserDropdown<'a> {
 						</label>
 
 						<ul tabindex="0" class="mt-3 z-[1] p-2 shadow menu menu-sm dropdown-content bg-base-100 rounded-box w-52">
-							<li><a>"Profile"</a></li>
+							<li><a href="/profile">"Profile"</a></li>
+							<li><a href="/missing-themes">"Missing themes"</a></li>
 							<li><a href="/auth/logout">"Logout"</a></li>
 						</ul>
 					</div>
@@ -91,6 +92,9 @@ impl<'a> HtmlContent for NavBar<'a> {
 
 				// search
 				<div class="flex-none gap-2">
+					<a class="hidden btn btn-ghost sm:inline-flex" href="/">"Series"</a>
+					<a class="hidden btn btn-ghost sm:inline-flex" href="/movies">"Movies"</a>
+					<a class="hidden btn btn-ghost sm:inline-flex" href="/stats">"Stats"</a>
 					<NavSearchBox />
 					<UserDropdown user=self.user />
 				</div>